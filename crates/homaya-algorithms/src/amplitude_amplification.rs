@@ -0,0 +1,220 @@
+//! # Amplitude Amplification
+//!
+//! Boost the probability of "good" outcomes of any quantum subroutine.
+//!
+//! ## The Problem
+//!
+//! A state-preparation circuit A produces a good outcome with probability
+//! p. Repeating A and checking classically needs about 1/p tries; amplitude
+//! amplification needs only about 1/√p applications of A.
+//!
+//! ## How It Works
+//!
+//! Write A|0⟩ = sin θ |good⟩ + cos θ |bad⟩, with sin²θ = p.
+//!
+//! 1. **Prepare**: apply A to |0...0⟩
+//! 2. **Iterate** Q = −A S₀ A† S_χ, where the oracle S_χ flips the phase of
+//!    good states and S₀ flips the phase of |0...0⟩
+//! 3. **Measure**: after k iterations the good probability is sin²((2k + 1)θ)
+//!
+//! Grover's search is the special case A = H^⊗n.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use homaya_algorithms::AmplitudeAmplification;
+//! use homaya_core::Circuit;
+//!
+//! // Mark |11⟩ on two qubits, starting from a uniform superposition
+//! let prep = Circuit::new(2).h(0).h(1);
+//! let oracle = Circuit::new(2).cz(0, 1);
+//!
+//! let amplifier = AmplitudeAmplification::new(prep, oracle);
+//! let k = AmplitudeAmplification::optimal_iterations(0.25);
+//! let circuit = amplifier.build(k); // finds |11⟩ with certainty
+//! ```
+
+use homaya_core::{Circuit, PI};
+
+use crate::oracle::phase_flip;
+
+/// Amplitude amplification builder.
+///
+/// Amplifies the states marked by `oracle` in the output of `state_prep`.
+#[derive(Debug, Clone)]
+pub struct AmplitudeAmplification {
+    /// State-preparation operator A
+    state_prep: Circuit,
+    /// Phase oracle S_χ marking the good states
+    oracle: Circuit,
+}
+
+impl AmplitudeAmplification {
+    /// Create a new amplitude amplification instance.
+    ///
+    /// `state_prep` must be unitary (no measurements or resets). `oracle`
+    /// must flip the phase of the good basis states and leave the rest
+    /// unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two circuits act on different numbers of qubits.
+    pub fn new(state_prep: Circuit, oracle: Circuit) -> Self {
+        assert_eq!(
+            state_prep.num_qubits(),
+            oracle.num_qubits(),
+            "state preparation and oracle must act on the same qubits"
+        );
+        Self { state_prep, oracle }
+    }
+
+    /// Number of qubits the circuits act on.
+    pub fn num_qubits(&self) -> usize {
+        self.state_prep.num_qubits()
+    }
+
+    /// Calculate the optimal number of iterations.
+    ///
+    /// With θ = arcsin(√p), the good probability after k iterations is
+    /// sin²((2k + 1)θ), which peaks at k = ⌊π / 4θ⌋.
+    pub fn optimal_iterations(initial_success_prob: f64) -> usize {
+        if initial_success_prob <= 0.0 || initial_success_prob >= 1.0 {
+            return 0;
+        }
+        let theta = initial_success_prob.sqrt().asin();
+        (PI / (4.0 * theta)).floor() as usize
+    }
+
+    /// Build one amplification step Q = −A S₀ A† S_χ (up to global phase).
+    ///
+    /// A† comes from [`Circuit::inverse`].
+    pub fn iteration(&self) -> Circuit {
+        let n = self.num_qubits();
+
+        let circuit = Circuit::new(n)
+            .compose(&self.oracle)
+            .expect("oracle has the circuit's qubit count")
+            .compose(&self.state_prep.clone().inverse())
+            .expect("state preparation has the circuit's qubit count");
+
+        // S₀: flip the phase of |0...0⟩
+        phase_flip(circuit, n, 0)
+            .compose(&self.state_prep)
+            .expect("state preparation has the circuit's qubit count")
+    }
+
+    /// Build the full circuit: A, then `iterations` steps, then measurement.
+    pub fn build(&self, iterations: usize) -> Circuit {
+        let step = self.iteration();
+        let mut circuit = Circuit::new(self.num_qubits())
+            .compose(&self.state_prep)
+            .expect("state preparation has the circuit's qubit count");
+
+        for _ in 0..iterations {
+            circuit = circuit.compose(&step).expect("step has the circuit's qubit count");
+        }
+
+        circuit.measure_all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GroverSearch;
+    use homaya_sim::Simulator;
+
+    /// Phase oracle marking a single basis state on `n` qubits.
+    fn mark(n: usize, target: usize) -> Circuit {
+        phase_flip(Circuit::new(n), n, target)
+    }
+
+    fn hadamards(n: usize) -> Circuit {
+        (0..n).fold(Circuit::new(n), |c, q| c.h(q))
+    }
+
+    #[test]
+    fn test_zero_qubits() {
+        let amplifier = AmplitudeAmplification::new(Circuit::new(0), Circuit::new(0));
+        assert!(amplifier.iteration().is_empty());
+        assert!(amplifier.build(3).is_empty());
+    }
+
+    #[test]
+    fn test_optimal_iterations_matches_grover() {
+        assert_eq!(AmplitudeAmplification::optimal_iterations(1.0 / 8.0), 2);
+        assert_eq!(AmplitudeAmplification::optimal_iterations(1.0 / 16.0), 3);
+        assert_eq!(AmplitudeAmplification::optimal_iterations(0.25), 1);
+        assert_eq!(AmplitudeAmplification::optimal_iterations(0.0), 0);
+    }
+
+    #[test]
+    fn test_hadamard_prep_reproduces_grover() {
+        let (n, target) = (3, 5);
+        let amplifier = AmplitudeAmplification::new(hadamards(n), mark(n, target));
+        let k = AmplitudeAmplification::optimal_iterations(1.0 / (1 << n) as f64);
+
+        let mut circuit = hadamards(n);
+        for _ in 0..k {
+            circuit = circuit.compose(&amplifier.iteration()).unwrap();
+        }
+        let state = Simulator::new().run(&circuit).unwrap();
+
+        let expected = GroverSearch::new(n, target).success_probability();
+        assert!((state.probability(target) - expected).abs() < 1e-10);
+
+        let counts = Simulator::with_seed(9).sample(&amplifier.build(k), 500).unwrap();
+        let hits = counts.get("101").copied().unwrap_or(0);
+        assert!(hits > 430, "target found {} / 500 times", hits);
+    }
+
+    #[test]
+    fn test_non_symmetric_state_prep() {
+        // A = Ry(θ) puts sin²(θ/2) on |1⟩; the oracle marks |1⟩
+        let theta = 0.5;
+        let amplifier = AmplitudeAmplification::new(Circuit::new(1).ry(theta, 0), Circuit::new(1).z(0));
+        let p = (theta / 2.0).sin().powi(2);
+        let k = AmplitudeAmplification::optimal_iterations(p);
+
+        let mut circuit = Circuit::new(1).ry(theta, 0);
+        for _ in 0..k {
+            circuit = circuit.compose(&amplifier.iteration()).unwrap();
+        }
+        let state = Simulator::new().run(&circuit).unwrap();
+
+        let expected = ((2 * k + 1) as f64 * (theta / 2.0)).sin().powi(2);
+        assert!((state.probability(1) - expected).abs() < 1e-10);
+        assert!(expected > 0.9);
+    }
+
+    #[test]
+    fn test_biased_prep_improves_with_each_round() {
+        // |11⟩ starts with probability sin²(0.15)/2 ≈ 1%
+        let prep = Circuit::new(2).ry(0.3, 0).h(1);
+        let amplifier = AmplitudeAmplification::new(prep.clone(), Circuit::new(2).cz(0, 1));
+        let p = (0.15f64).sin().powi(2) / 2.0;
+        let k = AmplitudeAmplification::optimal_iterations(p);
+        assert_eq!(k, 7);
+
+        let theta = p.sqrt().asin();
+        let mut circuit = prep;
+        let mut previous = 0.0;
+        for round in 0..=k {
+            let found = Simulator::new().run(&circuit).unwrap().probability(0b11);
+            let expected = ((2 * round + 1) as f64 * theta).sin().powi(2);
+            assert!((found - expected).abs() < 1e-10, "round {}: {} vs {}", round, found, expected);
+            assert!(found > previous, "round {} did not improve on {}", round, previous);
+            previous = found;
+            circuit = circuit.compose(&amplifier.iteration()).unwrap();
+        }
+        assert!(previous > 0.95);
+    }
+
+    #[test]
+    fn test_two_qubit_single_step_is_exact() {
+        let amplifier = AmplitudeAmplification::new(hadamards(2), Circuit::new(2).cz(0, 1));
+        let circuit = hadamards(2).compose(&amplifier.iteration()).unwrap();
+        let state = Simulator::new().run(&circuit).unwrap();
+        assert!((state.probability(0b11) - 1.0).abs() < 1e-10);
+    }
+}
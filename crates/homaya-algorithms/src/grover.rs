@@ -11,7 +11,7 @@
 //! ## How It Works
 //!
 //! 1. **Superposition**: Put all N items in equal superposition
-//! 2. **Oracle**: Mark the target item by flipping its phase
+//! 2. **Oracle**: Mark the target items by flipping their phase
 //! 3. **Diffusion**: Amplify the marked item's amplitude
 //! 4. **Repeat**: Apply oracle + diffusion √N times
 //! 5. **Measure**: Target item has high probability of being found
@@ -25,7 +25,18 @@
 //! let grover = GroverSearch::new(3, 6);
 //! let circuit = grover.build();
 //!
-//! // The circuit will find item 6 with ~97% probability
+//! // The circuit will find item 6 with ~95% probability
+//! ```
+//!
+//! Any predicate can serve as the oracle, with any number of solutions:
+//!
+//! ```rust
+//! use homaya_algorithms::GroverSearch;
+//!
+//! // Search for multiples of 5 among 16 items: 0, 5, 10 and 15
+//! let grover = GroverSearch::with_oracle(4, |x| x % 5 == 0);
+//! assert_eq!(grover.num_solutions(), 4);
+//! let circuit = grover.build();
 //! ```
 //!
 //! ## The Math (simplified)
@@ -34,21 +45,27 @@
 //!
 //! P(target) = sin²((2k + 1)θ)
 //!
-//! where θ = arcsin(1/√N) and optimal k ≈ π√N/4
+//! where θ = arcsin(√(M/N)) for M solutions and optimal k ≈ π/4θ.
+//!
+//! When half or more of the items are solutions, θ ≥ π/4 and no number of
+//! iterations helps. The search space is then doubled with one extra qubit
+//! whose solutions are never marked, which brings M/N back below 1/2.
 //!
 
 use homaya_core::{Circuit, PI};
 
+use crate::oracle::{phase_flip, phase_oracle_from_truth_table};
+
 /// Grover's Search algorithm builder.
 ///
-/// Creates a quantum circuit that searches for a specific item
+/// Creates a quantum circuit that searches for the marked items
 /// in an unsorted database with quadratic speedup.
 #[derive(Debug, Clone)]
 pub struct GroverSearch {
     /// Number of qubits (search space = 2^n_qubits)
     n_qubits: usize,
-    /// The item we're searching for (0 to 2^n_qubits - 1)
-    target: usize,
+    /// The items we're searching for, in increasing order
+    marked: Vec<usize>,
     /// Number of Grover iterations (auto-calculated if None)
     iterations: Option<usize>,
 }
@@ -84,11 +101,42 @@ impl GroverSearch {
 
         Self {
             n_qubits,
-            target,
+            marked: std::vec![target],
+            iterations: None,
+        }
+    }
+
+    /// Create a search for every item where `oracle` returns true.
+    ///
+    /// The phase oracle is built from the predicate's truth table, so
+    /// `oracle` is called once for each of the 2^n_qubits items.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::GroverSearch;
+    ///
+    /// let grover = GroverSearch::with_oracle(3, |x| x == 1 || x == 6);
+    /// assert_eq!(grover.marked(), &[1, 6]);
+    /// ```
+    pub fn with_oracle(n_qubits: usize, oracle: impl Fn(usize) -> bool) -> Self {
+        Self {
+            n_qubits,
+            marked: (0..1 << n_qubits).filter(|&x| oracle(x)).collect(),
             iterations: None,
         }
     }
 
+    /// The items being searched for, in increasing order.
+    pub fn marked(&self) -> &[usize] {
+        &self.marked
+    }
+
+    /// Number of marked items.
+    pub fn num_solutions(&self) -> usize {
+        self.marked.len()
+    }
+
     /// Set a custom number of iterations.
     ///
     /// By default, the optimal number is calculated automatically.
@@ -100,71 +148,85 @@ impl GroverSearch {
 
     /// Calculate the optimal number of Grover iterations.
     ///
-    /// The formula is: k ≈ π/4 × √N
+    /// The formula is: k = ⌊π/4θ⌋ with θ = arcsin(√(M/N))
     ///
-    /// where N = 2^n_qubits is the search space size.
+    /// where M is the number of solutions and N the size of the search
+    /// space (doubled when M ≥ N/2). For a single solution this is
+    /// k ≈ π/4 × √N. With no solutions, or nothing but solutions, it is 0.
     pub fn optimal_iterations(&self) -> usize {
-        let n = (1 << self.n_qubits) as f64;
-        let optimal = (PI / 4.0 * n.sqrt()).round() as usize;
-        optimal.max(1)
+        let theta = self.theta();
+        if theta == 0.0 || theta >= PI / 2.0 {
+            return 0;
+        }
+        (PI / (4.0 * theta)).floor() as usize
+    }
+
+    /// Qubits the iterations act on: one more than `n_qubits` when half or
+    /// more of the items are marked.
+    fn search_qubits(&self) -> usize {
+        let m = self.marked.len();
+        let n = 1 << self.n_qubits;
+        if 2 * m >= n && m < n {
+            self.n_qubits + 1
+        } else {
+            self.n_qubits
+        }
+    }
+
+    /// Rotation angle per iteration: sin²θ is the marked fraction of the
+    /// search space.
+    fn theta(&self) -> f64 {
+        let n = (1u64 << self.search_qubits()) as f64;
+        (self.marked.len() as f64 / n).sqrt().asin()
     }
 
     /// Build the Grover search circuit.
     ///
     /// Returns a circuit that, when executed and measured,
-    /// will return the target item with high probability.
+    /// will return a marked item with high probability. Only the
+    /// `n_qubits` search qubits are measured, into classical bits of the
+    /// same index.
     pub fn build(&self) -> Circuit {
         let iterations = self.iterations.unwrap_or_else(|| self.optimal_iterations());
+        let qubits = self.search_qubits();
 
-        let mut circuit = Circuit::new(self.n_qubits);
+        let mut circuit = Circuit::with_clbits(qubits, self.n_qubits);
 
         // Step 1: Create uniform superposition
         // Apply H to all qubits: |0...0⟩ → |+...+⟩
-        for i in 0..self.n_qubits {
+        for i in 0..qubits {
             circuit = circuit.h(i);
         }
 
         // Step 2: Grover iterations
         for _ in 0..iterations {
-            // Oracle: flip the phase of |target⟩
+            // Oracle: flip the phase of every marked state
             circuit = self.apply_oracle(circuit);
 
-            // Diffusion: amplify the marked state
+            // Diffusion: amplify the marked states
             circuit = self.apply_diffusion(circuit);
         }
 
-        // Step 3: Measure all qubits
-        circuit.measure_all()
+        // Step 3: Measure the search qubits
+        for i in 0..self.n_qubits {
+            circuit = circuit.measure(i, i);
+        }
+        circuit
     }
 
-    /// Apply the oracle that marks the target state.
+    /// Apply the oracle that marks the target states.
     ///
-    /// The oracle flips the sign of the |target⟩ amplitude:
+    /// The oracle flips the sign of each marked amplitude:
     /// |target⟩ → -|target⟩
     ///
-    /// This is done using controlled-Z gates based on the binary
-    /// representation of the target.
-    fn apply_oracle(&self, mut circuit: Circuit) -> Circuit {
-        // Apply X gates to qubits that are 0 in the target
-        // This transforms |target⟩ → |11...1⟩
-        for i in 0..self.n_qubits {
-            if (self.target >> i) & 1 == 0 {
-                circuit = circuit.x(i);
-            }
-        }
-
-        // Multi-controlled Z gate on all qubits
-        // This flips the sign of |11...1⟩
-        circuit = self.multi_controlled_z(circuit);
-
-        // Undo the X gates
-        for i in 0..self.n_qubits {
-            if (self.target >> i) & 1 == 0 {
-                circuit = circuit.x(i);
-            }
-        }
-
+    /// It is the [phase oracle](phase_oracle_from_truth_table) of the
+    /// marked set. An extra search qubit is always |0⟩ in a marked state.
+    fn apply_oracle(&self, circuit: Circuit) -> Circuit {
+        let qubits = self.search_qubits();
+        let f: Vec<bool> = (0..1 << qubits).map(|x| self.marked.contains(&x)).collect();
         circuit
+            .compose(&phase_oracle_from_truth_table(qubits, &f))
+            .expect("oracle has the circuit's qubit count")
     }
 
     /// Apply the diffusion operator (Grover's diffuser).
@@ -173,70 +235,28 @@ impl GroverSearch {
     /// where |s⟩ is the uniform superposition state.
     ///
     /// This reflects amplitudes about their mean, amplifying
-    /// the marked state.
+    /// the marked state. Up to global phase it is H⊗ⁿ, a phase flip of
+    /// |0...0⟩, then H⊗ⁿ again.
     fn apply_diffusion(&self, mut circuit: Circuit) -> Circuit {
-        // Apply H to all qubits
-        for i in 0..self.n_qubits {
-            circuit = circuit.h(i);
-        }
+        let qubits = self.search_qubits();
 
-        // Apply X to all qubits (transforms |0...0⟩ → |1...1⟩)
-        for i in 0..self.n_qubits {
-            circuit = circuit.x(i);
-        }
-
-        // Multi-controlled Z
-        circuit = self.multi_controlled_z(circuit);
-
-        // Undo X gates
-        for i in 0..self.n_qubits {
-            circuit = circuit.x(i);
+        for i in 0..qubits {
+            circuit = circuit.h(i);
         }
-
-        // Apply H to all qubits
-        for i in 0..self.n_qubits {
+        circuit = phase_flip(circuit, qubits, 0);
+        for i in 0..qubits {
             circuit = circuit.h(i);
         }
 
         circuit
     }
 
-    /// Implement multi-controlled Z using decomposition.
-    ///
-    /// For 2 qubits: CZ
-    /// For 3+ qubits: decompose into Toffoli + controlled gates
-    fn multi_controlled_z(&self, mut circuit: Circuit) -> Circuit {
-        match self.n_qubits {
-            0 | 1 => circuit.z(0),
-            2 => {
-                // CZ gate: controlled-Z on qubits 0,1
-                circuit.h(1).cx(0, 1).h(1)
-            }
-            3 => {
-                // CCZ using H-Toffoli-H pattern
-                circuit.h(2).ccx(0, 1, 2).h(2)
-            }
-            _ => {
-                // For larger circuits, use a simplified pattern
-                // Apply Z to last qubit controlled by all others
-                // This is an approximation for demonstration
-                let last = self.n_qubits - 1;
-                circuit = circuit.h(last);
-                for i in 0..last {
-                    circuit = circuit.cx(i, last);
-                }
-                circuit.h(last)
-            }
-        }
-    }
-
     /// Get the theoretical success probability.
     ///
-    /// Returns the probability of measuring the target state
-    /// after the optimal number of iterations.
+    /// Returns the probability of measuring a marked state
+    /// after the configured (by default optimal) number of iterations.
     pub fn success_probability(&self) -> f64 {
-        let n = (1 << self.n_qubits) as f64;
-        let theta = (1.0 / n.sqrt()).asin();
+        let theta = self.theta();
         let k = self.iterations.unwrap_or_else(|| self.optimal_iterations()) as f64;
         let angle = (2.0 * k + 1.0) * theta;
         angle.sin().powi(2)
@@ -259,6 +279,7 @@ pub fn search(n_qubits: usize, target: usize) -> Circuit {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use homaya_sim::Simulator;
 
     #[test]
     fn test_grover_creates_circuit() {
@@ -285,6 +306,53 @@ mod tests {
         assert!(prob > 0.9, "Success probability {} too low", prob);
     }
 
+    #[test]
+    fn test_oracle_marks_even_numbers() {
+        // Half the space is marked, so an extra search qubit is added
+        let grover = GroverSearch::with_oracle(3, |x| x % 2 == 0);
+        assert_eq!(grover.marked(), &[0, 2, 4, 6]);
+        assert_eq!(grover.optimal_iterations(), 1);
+        assert!((grover.success_probability() - 1.0).abs() < 1e-10);
+
+        let circuit = grover.build();
+        assert_eq!(circuit.num_qubits(), 4);
+        assert_eq!(circuit.num_clbits(), 3);
+
+        // Bit 0 comes first in the bitstring: even numbers start with '0'
+        let counts = Simulator::with_seed(4).sample(&circuit, 400).unwrap();
+        let even: usize = counts.iter().filter(|(bits, _)| bits.starts_with('0')).map(|(_, &c)| c).sum();
+        assert_eq!(even, 400, "counts: {:?}", counts);
+    }
+
+    #[test]
+    fn test_oracle_with_few_solutions() {
+        // 2 of 16 marked: two iterations find one with ~95% probability
+        let grover = GroverSearch::with_oracle(4, |x| x == 3 || x == 12);
+        assert_eq!(grover.num_solutions(), 2);
+        assert!(grover.success_probability() > 0.9);
+
+        let circuit = grover.build();
+        assert_eq!(circuit.num_qubits(), 4);
+        let counts = Simulator::with_seed(6).sample(&circuit, 500).unwrap();
+        let found = ["1100", "0011"].iter().filter_map(|bits| counts.get(*bits)).sum::<usize>();
+        assert!(found > 440, "marked states found {} / 500 times", found);
+    }
+
+    #[test]
+    fn test_single_target_on_four_qubits() {
+        // Exercises the multi-controlled Z beyond three qubits
+        let counts = Simulator::with_seed(2).sample(&search(4, 11), 500).unwrap();
+        let hits = counts.get("1101").copied().unwrap_or(0);
+        assert!(hits > 440, "target found {} / 500 times", hits);
+    }
+
+    #[test]
+    fn test_no_solutions() {
+        let grover = GroverSearch::with_oracle(3, |_| false);
+        assert_eq!(grover.optimal_iterations(), 0);
+        assert_eq!(grover.success_probability(), 0.0);
+    }
+
     #[test]
     #[should_panic(expected = "Target 16 is too large")]
     fn test_invalid_target() {
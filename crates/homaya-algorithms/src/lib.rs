@@ -5,8 +5,15 @@
 //! ## Available Algorithms
 //!
 //! - [`grover`] - Grover's Search: Find a needle in a haystack with √N queries
+//! - [`amplitude_amplification`] - Grover generalized to any state preparation
 //! - [`deutsch`] - Deutsch-Jozsa: Determine if a function is constant or balanced
 //! - [`bernstein_vazirani`] - Find a hidden string in one query
+//! - [`shor`] - Shor's period finding, the quantum core of factoring
+//! - [`qft`] - Quantum Fourier Transform, the building block of phase estimation
+//! - [`phase_estimation`] - Read off a unitary's eigenphase to t bits
+//! - [`teleportation`] - Send a qubit state with a Bell pair and two classical bits
+//! - [`states`] - GHZ and W state preparation
+//! - [`oracle`] - Phase and bit oracles from a boolean function's truth table
 //!
 //! ## Example: Grover's Search
 //!
@@ -21,9 +28,20 @@
 #![deny(unsafe_code)]
 
 pub mod grover;
+pub mod amplitude_amplification;
 pub mod deutsch;
 pub mod bernstein_vazirani;
+pub mod shor;
+pub mod qft;
+pub mod phase_estimation;
+pub mod teleportation;
+pub mod states;
+pub mod oracle;
 
 pub use grover::GroverSearch;
+pub use amplitude_amplification::AmplitudeAmplification;
 pub use deutsch::DeutschJozsa;
 pub use bernstein_vazirani::BernsteinVazirani;
+pub use shor::Shor;
+pub use qft::Qft;
+pub use phase_estimation::PhaseEstimation;
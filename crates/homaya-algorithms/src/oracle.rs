@@ -0,0 +1,185 @@
+//! # Oracles from Truth Tables
+//!
+//! Compile an arbitrary boolean function f: {0,1}ⁿ → {0,1} into a circuit.
+//!
+//! ## Two Kinds of Oracle
+//!
+//! - **Phase oracle**: |x⟩ → (−1)^f(x) |x⟩, as used by Grover's search
+//! - **Bit oracle**: |x⟩|y⟩ → |x⟩|y ⊕ f(x)⟩, with the answer written to an
+//!   ancilla, as used by Deutsch-Jozsa and Bernstein-Vazirani
+//!
+//! A bit oracle becomes a phase oracle when the ancilla starts in |−⟩.
+//!
+//! ## How It Works
+//!
+//! Each x with f(x) = 1 gets its own multi-controlled gate: X gates on the
+//! qubits that are 0 in x map |x⟩ to |11…1⟩, a multi-controlled Z (or X
+//! onto the ancilla) acts there, and the X gates are undone. The circuit
+//! has one such block per marked input, so it suits small or sparse
+//! functions rather than large structured ones.
+//!
+//! The truth table is indexed little-endian: `f[x]` is the value on the
+//! basis state whose qubit i holds bit i of x.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use homaya_algorithms::oracle::phase_oracle_from_truth_table;
+//!
+//! // Mark |01⟩ and |10⟩ on two qubits: f(x) = x₀ XOR x₁
+//! let oracle = phase_oracle_from_truth_table(2, &[false, true, true, false]);
+//! assert_eq!(oracle.num_qubits(), 2);
+//! ```
+
+use homaya_core::Circuit;
+
+/// Build the phase oracle |x⟩ → (−1)^f(x) |x⟩ on `num_qubits` qubits.
+///
+/// # Panics
+///
+/// Panics if `f.len()` is not 2^num_qubits.
+pub fn phase_oracle_from_truth_table(num_qubits: usize, f: &[bool]) -> Circuit {
+    check_table(num_qubits, f);
+
+    marked(f).fold(Circuit::new(num_qubits), |circuit, x| phase_flip(circuit, num_qubits, x))
+}
+
+/// Flip the phase of the basis state |x⟩ on qubits `0..num_qubits`.
+pub(crate) fn phase_flip(circuit: Circuit, num_qubits: usize, x: usize) -> Circuit {
+    around_ones(circuit, num_qubits, x, |circuit| match num_qubits {
+        // A phase on the only basis state is global
+        0 => circuit,
+        1 => circuit.z(0),
+        n => {
+            let controls: Vec<usize> = (0..n - 1).collect();
+            circuit.h(n - 1).mcx(&controls, n - 1).h(n - 1)
+        }
+    })
+}
+
+/// Build the bit oracle |x⟩|y⟩ → |x⟩|y ⊕ f(x)⟩.
+///
+/// The inputs are qubits `0..num_qubits` and the ancilla is qubit
+/// `num_qubits`, for `num_qubits + 1` qubits in all.
+///
+/// # Panics
+///
+/// Panics if `f.len()` is not 2^num_qubits.
+pub fn bit_oracle_from_truth_table(num_qubits: usize, f: &[bool]) -> Circuit {
+    check_table(num_qubits, f);
+
+    let controls: Vec<usize> = (0..num_qubits).collect();
+    marked(f).fold(Circuit::new(num_qubits + 1), |circuit, x| {
+        around_ones(circuit, num_qubits, x, |circuit| circuit.mcx(&controls, num_qubits))
+    })
+}
+
+/// Panic unless `f` has one entry per basis state.
+fn check_table(num_qubits: usize, f: &[bool]) {
+    assert!(
+        num_qubits < usize::BITS as usize && f.len() == 1 << num_qubits,
+        "truth table has {} entries, expected 2^{} for {} qubits",
+        f.len(),
+        num_qubits,
+        num_qubits
+    );
+}
+
+/// Inputs where the function is true.
+fn marked(f: &[bool]) -> impl Iterator<Item = usize> + '_ {
+    f.iter().enumerate().filter(|(_, &value)| value).map(|(x, _)| x)
+}
+
+/// Apply `gate`, which acts on |11…1⟩, so that it acts on |x⟩ instead.
+fn around_ones(circuit: Circuit, num_qubits: usize, x: usize, gate: impl FnOnce(Circuit) -> Circuit) -> Circuit {
+    let zeros: Vec<usize> = (0..num_qubits).filter(|q| (x >> q) & 1 == 0).collect();
+    let circuit = zeros.iter().fold(circuit, |circuit, &q| circuit.x(q));
+    let circuit = gate(circuit);
+    zeros.iter().fold(circuit, |circuit, &q| circuit.x(q))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_sim::Simulator;
+
+    fn uniform(n: usize) -> Circuit {
+        (0..n).fold(Circuit::new(n), |circuit, q| circuit.h(q))
+    }
+
+    #[test]
+    fn test_phase_oracle_flips_only_marked_states() {
+        for n in 1..=4 {
+            // An irregular function: primes
+            let f: Vec<bool> = (0..1usize << n).map(|x| [2, 3, 5, 7, 11, 13].contains(&x)).collect();
+            let circuit = uniform(n).compose(&phase_oracle_from_truth_table(n, &f)).unwrap();
+            let state = Simulator::new().run(&circuit).unwrap();
+
+            let amp = 1.0 / ((1usize << n) as f64).sqrt();
+            for (x, a) in state.amplitudes().iter().enumerate() {
+                let expected = if f[x] { -amp } else { amp };
+                assert!((a.re - expected).abs() < 1e-10 && a.im.abs() < 1e-10, "n={} x={}: {:?}", n, x, a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_phase_flip_each_basis_state() {
+        for n in 1..=4 {
+            for x in 0..1usize << n {
+                let state = Simulator::new().run(&phase_flip(uniform(n), n, x)).unwrap();
+                let amp = 1.0 / ((1usize << n) as f64).sqrt();
+                for (i, a) in state.amplitudes().iter().enumerate() {
+                    let expected = if i == x { -amp } else { amp };
+                    assert!((a.re - expected).abs() < 1e-10 && a.im.abs() < 1e-10, "n={} x={} i={}", n, x, i);
+                }
+            }
+        }
+        assert!(phase_flip(Circuit::new(0), 0, 0).is_empty());
+    }
+
+    #[test]
+    fn test_bit_oracle_computes_function() {
+        let f = [true, false, false, true, true, false, true, false];
+        let oracle = bit_oracle_from_truth_table(3, &f);
+        assert_eq!(oracle.num_qubits(), 4);
+
+        for (x, &fx) in f.iter().enumerate() {
+            for y in 0..2 {
+                let prep = (0..4).filter(|q| ((x | y << 3) >> q) & 1 == 1).fold(Circuit::new(4), |c, q| c.x(q));
+                let state = Simulator::new().run(&prep.compose(&oracle).unwrap()).unwrap();
+                let out = x | ((y ^ fx as usize) << 3);
+                assert!((state.probability(out) - 1.0).abs() < 1e-10, "x={} y={}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_bit_oracle_with_minus_ancilla_is_phase_oracle() {
+        let f = [false, true, true, true];
+        let circuit = Circuit::new(3)
+            .x(2)
+            .h(2)
+            .h(0)
+            .h(1)
+            .compose(&bit_oracle_from_truth_table(2, &f))
+            .unwrap();
+        let expected = Circuit::new(3)
+            .x(2)
+            .h(2)
+            .h(0)
+            .h(1)
+            .compose(&phase_oracle_from_truth_table(2, &f))
+            .unwrap();
+
+        let a = Simulator::new().run(&circuit).unwrap();
+        let b = Simulator::new().run(&expected).unwrap();
+        assert!((a.fidelity(&b) - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    #[should_panic(expected = "truth table has 3 entries")]
+    fn test_wrong_table_length() {
+        phase_oracle_from_truth_table(2, &[true, false, true]);
+    }
+}
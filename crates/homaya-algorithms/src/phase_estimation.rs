@@ -0,0 +1,307 @@
+//! # Quantum Phase Estimation
+//!
+//! Read off the eigenvalue of a unitary to t bits of precision.
+//!
+//! ## The Problem
+//!
+//! A unitary U has an eigenstate |ψ⟩ with U|ψ⟩ = e^{2πiφ}|ψ⟩. Given
+//! controlled applications of U and a way to prepare |ψ⟩, find φ ∈ [0, 1).
+//!
+//! ## How It Works
+//!
+//! 1. **Counting register**: put t qubits in uniform superposition
+//! 2. **Target register**: prepare the eigenstate |ψ⟩
+//! 3. **Phase kickback**: counting qubit k controls U^(2^k), which leaves
+//!    the phase e^{2πiφ·2ᵏ} on that qubit's |1⟩ component
+//! 4. **Inverse QFT** on the counting register turns the phases into the
+//!    binary expansion of φ
+//! 5. **Measure**: the most likely outcome m gives φ ≈ m / 2ᵗ
+//!
+//! If φ has an exact t-bit expansion the outcome is certain; otherwise the
+//! nearest t-bit value comes up with probability at least 4/π² ≈ 0.405.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use homaya_algorithms::PhaseEstimation;
+//! use homaya_core::{Circuit, Gate, PI};
+//! use homaya_sim::Simulator;
+//!
+//! // P(2π·3/8) has eigenvalue e^{2πi·3/8} on |1⟩
+//! let qpe = PhaseEstimation::from_gate(3, Gate::p(2.0 * PI * 3.0 / 8.0))
+//!     .with_eigenstate(Circuit::new(1).x(0));
+//! let counts = Simulator::with_seed(1).sample(&qpe.build(), 100).unwrap();
+//! assert_eq!(qpe.estimated_phase(&counts), 0.375);
+//! ```
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use homaya_core::{Circuit, Gate, GateParams, GateType, PI};
+use crate::qft::iqft;
+
+/// Appends controlled-U^power: `(circuit, control, targets, power)`.
+type ControlledPower = dyn Fn(Circuit, usize, &[usize], u64) -> Circuit + Send + Sync;
+
+/// Quantum Phase Estimation builder.
+///
+/// Counting qubits come first (qubit 0 is the least significant bit of the
+/// phase) and are measured into classical bits of the same index. The
+/// target register follows.
+#[derive(Clone)]
+pub struct PhaseEstimation {
+    /// Number of counting (phase) qubits
+    counting_qubits: usize,
+    /// Number of qubits U acts on
+    target_qubits: usize,
+    /// Appends controlled powers of U
+    controlled_power: Arc<ControlledPower>,
+    /// Prepares the eigenstate on the target register
+    eigenstate: Option<Circuit>,
+}
+
+impl std::fmt::Debug for PhaseEstimation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PhaseEstimation")
+            .field("counting_qubits", &self.counting_qubits)
+            .field("target_qubits", &self.target_qubits)
+            .field("eigenstate", &self.eigenstate)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PhaseEstimation {
+    /// Create a phase estimation for a unitary on `target_qubits` qubits.
+    ///
+    /// `controlled_power(circuit, control, targets, power)` must append
+    /// U^power controlled on qubit `control`, with U acting on the qubits
+    /// in `targets`, and return the circuit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_algorithms::PhaseEstimation;
+    ///
+    /// // U = P(θ) ⊗ P(θ): controlled powers are just scaled CP gates
+    /// let theta = 0.4;
+    /// let qpe = PhaseEstimation::new(5, 2, move |circuit, control, targets, power| {
+    ///     let angle = theta * power as f64;
+    ///     circuit.cp(angle, control, targets[0]).cp(angle, control, targets[1])
+    /// });
+    /// assert_eq!(qpe.build().num_qubits(), 7);
+    /// ```
+    pub fn new<F>(counting_qubits: usize, target_qubits: usize, controlled_power: F) -> Self
+    where
+        F: Fn(Circuit, usize, &[usize], u64) -> Circuit + Send + Sync + 'static,
+    {
+        Self {
+            counting_qubits,
+            target_qubits,
+            controlled_power: Arc::new(controlled_power),
+            eigenstate: None,
+        }
+    }
+
+    /// Create a phase estimation for a single-qubit gate.
+    ///
+    /// U^(2^k) is applied as 2ᵏ controlled copies of the gate, except for
+    /// phase gates, whose powers are a single rotation by a multiple of
+    /// the angle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `gate` is not a single-qubit unitary gate with numeric
+    /// parameters.
+    pub fn from_gate(counting_qubits: usize, gate: Gate) -> Self {
+        assert!(
+            controlled(Circuit::new(2), &gate, 0, 1).is_some(),
+            "{} is not a single-qubit unitary gate",
+            gate.name()
+        );
+
+        Self::new(counting_qubits, 1, move |circuit, control, targets, power| {
+            // Phase gates compose by adding angles
+            if let (GateType::P, GateParams::Angle(theta)) = (gate.gate_type, &gate.params) {
+                return circuit.cp(theta * power as f64, control, targets[0]);
+            }
+            (0..power).fold(circuit, |circuit, _| {
+                controlled(circuit, &gate, control, targets[0]).expect("checked in from_gate")
+            })
+        })
+    }
+
+    /// Prepare the target register with `prep` before estimating.
+    ///
+    /// `prep` acts on the target qubits only, numbered from 0. Without it
+    /// the target register starts in |0...0⟩.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prep` does not have exactly `target_qubits` qubits.
+    pub fn with_eigenstate(mut self, prep: Circuit) -> Self {
+        assert_eq!(
+            prep.num_qubits(),
+            self.target_qubits,
+            "eigenstate preparation must act on the target register"
+        );
+        self.eigenstate = Some(prep);
+        self
+    }
+
+    /// Number of qubits in the counting register.
+    pub fn counting_qubits(&self) -> usize {
+        self.counting_qubits
+    }
+
+    /// Number of qubits in the target register.
+    pub fn target_qubits(&self) -> usize {
+        self.target_qubits
+    }
+
+    /// Build the full phase estimation circuit.
+    pub fn build(&self) -> Circuit {
+        let t = self.counting_qubits;
+        let targets: Vec<usize> = (t..t + self.target_qubits).collect();
+
+        let mut circuit = match &self.eigenstate {
+            Some(prep) => Circuit::new(t).tensor(prep),
+            None => Circuit::new(t + self.target_qubits),
+        };
+
+        for q in 0..t {
+            circuit = circuit.h(q);
+        }
+        for q in 0..t {
+            circuit = (self.controlled_power)(circuit, q, &targets, 1 << q);
+        }
+
+        circuit = circuit
+            .compose(&iqft(t))
+            .expect("counting register fits the circuit");
+
+        for q in 0..t {
+            circuit = circuit.measure(q, q);
+        }
+
+        circuit
+    }
+
+    /// Decode the most frequent outcome in `counts` into a phase in [0, 1).
+    ///
+    /// `counts` are as returned by `Simulator::sample` for [`build`](Self::build),
+    /// with bit 0 of each bitstring first. Ties go to the smaller phase, and
+    /// empty counts give 0.
+    pub fn estimated_phase(&self, counts: &HashMap<String, usize>) -> f64 {
+        let measured = |bits: &str| {
+            bits.bytes()
+                .take(self.counting_qubits)
+                .enumerate()
+                .fold(0u64, |acc, (i, b)| acc | (u64::from(b == b'1') << i))
+        };
+
+        counts
+            .iter()
+            .map(|(bits, &count)| (count, measured(bits)))
+            .max_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)))
+            .map_or(0.0, |(_, m)| m as f64 / (1u64 << self.counting_qubits) as f64)
+    }
+}
+
+/// Append `gate` controlled on `control`, or `None` if it has no
+/// controlled form here.
+fn controlled(circuit: Circuit, gate: &Gate, control: usize, target: usize) -> Option<Circuit> {
+    use GateType::*;
+
+    let angle = match gate.params {
+        GateParams::Angle(theta) => Some(theta),
+        _ => None,
+    };
+    Some(match gate.gate_type {
+        I => circuit,
+        X => circuit.cx(control, target),
+        Y => circuit.cy(control, target),
+        Z => circuit.cz(control, target),
+        H => circuit.ch(control, target),
+        S => circuit.cp(PI / 2.0, control, target),
+        Sdg => circuit.cp(-PI / 2.0, control, target),
+        T => circuit.cp(PI / 4.0, control, target),
+        Tdg => circuit.cp(-PI / 4.0, control, target),
+        P => circuit.cp(angle?, control, target),
+        // Rz(θ) = e^{-iθ/2}·P(θ); the phase matters once controlled
+        Rz => circuit.cp(angle?, control, target).p(-angle? / 2.0, control),
+        Rx => circuit.cu(angle?, -PI / 2.0, PI / 2.0, control, target),
+        Ry => circuit.cu(angle?, 0.0, 0.0, control, target),
+        U => match gate.params {
+            GateParams::Angles3(theta, phi, lambda) => circuit.cu(theta, phi, lambda, control, target),
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_sim::Simulator;
+
+    #[test]
+    fn test_exact_phase_is_certain() {
+        // φ = 5/16 has an exact 4-bit expansion
+        let qpe = PhaseEstimation::from_gate(4, Gate::p(2.0 * PI * 5.0 / 16.0)).with_eigenstate(Circuit::new(1).x(0));
+        let counts = Simulator::with_seed(3).sample(&qpe.build(), 200).unwrap();
+
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts.get("1010"), Some(&200)); // 5, bit 0 first
+        assert_eq!(qpe.estimated_phase(&counts), 5.0 / 16.0);
+    }
+
+    #[test]
+    fn test_inexact_phase_within_resolution() {
+        let phi = 0.3;
+        let t = 6;
+        let qpe = PhaseEstimation::from_gate(t, Gate::p(2.0 * PI * phi)).with_eigenstate(Circuit::new(1).x(0));
+        let counts = Simulator::with_seed(8).sample(&qpe.build(), 500).unwrap();
+
+        let estimate = qpe.estimated_phase(&counts);
+        assert!((estimate - phi).abs() <= 0.5 / (1 << t) as f64, "estimate {}", estimate);
+    }
+
+    #[test]
+    fn test_gates_with_other_controlled_forms() {
+        // Rz(θ) on |1⟩ has eigenvalue e^{iθ/2}; T on |1⟩ has e^{iπ/4}
+        let mut sim = Simulator::with_seed(5);
+        for (gate, expected) in [(Gate::rz(2.0 * PI * 0.25), 0.125), (Gate::t(), 0.125), (Gate::z(), 0.5)] {
+            let qpe = PhaseEstimation::from_gate(3, gate).with_eigenstate(Circuit::new(1).x(0));
+            let counts = sim.sample(&qpe.build(), 50).unwrap();
+            assert_eq!(qpe.estimated_phase(&counts), expected);
+        }
+
+        // |+⟩ is the +1 eigenstate of X: phase 0
+        let qpe = PhaseEstimation::from_gate(3, Gate::x()).with_eigenstate(Circuit::new(1).h(0));
+        let counts = sim.sample(&qpe.build(), 50).unwrap();
+        assert_eq!(qpe.estimated_phase(&counts), 0.0);
+    }
+
+    #[test]
+    fn test_two_qubit_unitary() {
+        // U = P(α) ⊗ P(β) on |11⟩ has phase (α + β) / 2π = 3/8
+        let (alpha, beta) = (2.0 * PI * 0.25, 2.0 * PI * 0.125);
+        let qpe = PhaseEstimation::new(3, 2, move |circuit, control, targets, power| {
+            let k = power as f64;
+            circuit.cp(alpha * k, control, targets[0]).cp(beta * k, control, targets[1])
+        })
+        .with_eigenstate(Circuit::new(2).x(0).x(1));
+
+        let circuit = qpe.build();
+        assert_eq!(circuit.num_qubits(), 5);
+        let counts = Simulator::with_seed(2).sample(&circuit, 50).unwrap();
+        assert_eq!(qpe.estimated_phase(&counts), 0.375);
+        assert_eq!(qpe.estimated_phase(&HashMap::new()), 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a single-qubit unitary gate")]
+    fn test_from_gate_rejects_two_qubit_gates() {
+        PhaseEstimation::from_gate(3, Gate::cx());
+    }
+}
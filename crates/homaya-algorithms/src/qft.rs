@@ -0,0 +1,161 @@
+//! # Quantum Fourier Transform
+//!
+//! The discrete Fourier transform on the amplitudes of a register.
+//!
+//! ## What It Does
+//!
+//! With N = 2ⁿ, the QFT maps each basis state to a uniform superposition
+//! whose phases wind around x times:
+//!
+//! QFT|x⟩ = (1/√N) Σₖ e^{2πi·xk/N} |k⟩
+//!
+//! It is the step that turns periodicity into measurable peaks in phase
+//! estimation and Shor's algorithm, using O(n²) gates where the classical
+//! FFT on 2ⁿ values needs O(n·2ⁿ).
+//!
+//! ## How It Works
+//!
+//! Working from the most significant qubit down, each qubit gets a Hadamard
+//! followed by controlled-phase gates CP(π/2ᵏ) from every less significant
+//! qubit k places below it. This leaves the output bit-reversed, which the
+//! final SWAPs undo; algorithms that can relabel qubits instead may skip
+//! them with [`Qft::without_swaps`].
+//!
+//! ## Example
+//!
+//! ```rust
+//! use homaya_algorithms::qft;
+//! use homaya_core::Circuit;
+//!
+//! // Transform |5⟩ on 3 qubits and back again
+//! let circuit = Circuit::new(3)
+//!     .x(0)
+//!     .x(2)
+//!     .compose(&qft::qft(3))
+//!     .unwrap()
+//!     .compose(&qft::iqft(3))
+//!     .unwrap();
+//! ```
+
+use homaya_core::{Circuit, PI};
+
+/// Quantum Fourier Transform builder.
+#[derive(Debug, Clone)]
+pub struct Qft {
+    /// Number of qubits transformed
+    n_qubits: usize,
+    /// Whether to reverse the qubit order at the end
+    swaps: bool,
+}
+
+impl Qft {
+    /// Create a QFT on qubits `0..n_qubits`, with qubit 0 least significant.
+    pub fn new(n_qubits: usize) -> Self {
+        Self { n_qubits, swaps: true }
+    }
+
+    /// Leave out the final qubit-reversal SWAPs.
+    ///
+    /// The forward transform then writes its output with the qubit order
+    /// reversed, and the inverse expects its input reversed the same way.
+    pub fn without_swaps(mut self) -> Self {
+        self.swaps = false;
+        self
+    }
+
+    /// Build the forward transform.
+    pub fn build(&self) -> Circuit {
+        let n = self.n_qubits;
+        let mut circuit = Circuit::new(n);
+
+        for j in (0..n).rev() {
+            circuit = circuit.h(j);
+            for m in (0..j).rev() {
+                circuit = circuit.cp(PI / (1u64 << (j - m)) as f64, m, j);
+            }
+        }
+
+        if self.swaps {
+            for q in 0..n / 2 {
+                circuit = circuit.swap(q, n - q - 1);
+            }
+        }
+
+        circuit
+    }
+
+    /// Build the inverse transform, from [`Circuit::inverse`].
+    pub fn build_inverse(&self) -> Circuit {
+        self.build().inverse()
+    }
+}
+
+/// Convenience function for the QFT on `n_qubits` qubits.
+pub fn qft(n_qubits: usize) -> Circuit {
+    Qft::new(n_qubits).build()
+}
+
+/// Convenience function for the inverse QFT on `n_qubits` qubits.
+pub fn iqft(n_qubits: usize) -> Circuit {
+    Qft::new(n_qubits).build_inverse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_core::Complex;
+    use homaya_sim::{Simulator, StateVector};
+
+    #[test]
+    fn test_qft_of_zero_is_uniform() {
+        for n in 1..=5 {
+            let state = Simulator::new().run(&qft(n)).unwrap();
+            assert_eq!(state, StateVector::uniform(n), "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_qft_phases() {
+        let n = 4;
+        let dim = 1usize << n;
+        for x in [1, 6, 11] {
+            let state = Simulator::new().run_from_state(&qft(n), StateVector::basis(n, x)).unwrap();
+            for (k, amp) in state.amplitudes().iter().enumerate() {
+                let phase = 2.0 * PI * ((x * k) % dim) as f64 / dim as f64;
+                let expected = Complex::from_polar(1.0 / (dim as f64).sqrt(), phase);
+                assert!(amp.approx_eq(expected, 1e-10), "x={} k={}", x, k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_qft_then_iqft_is_identity() {
+        let mut sim = Simulator::new();
+        for (n, x) in [(3, 5), (4, 9), (5, 22), (6, 37)] {
+            let input = StateVector::basis(n, x);
+            let round_trip = qft(n).compose(&iqft(n)).unwrap();
+            let state = sim.run_from_state(&round_trip, input.clone()).unwrap();
+            assert_eq!(state, input, "n={} x={}", n, x);
+        }
+    }
+
+    #[test]
+    fn test_without_swaps_reverses_output() {
+        let n = 4;
+        let swapped = Simulator::new().run_from_state(&qft(n), StateVector::basis(n, 3)).unwrap();
+        let unswapped = Simulator::new()
+            .run_from_state(&Qft::new(n).without_swaps().build(), StateVector::basis(n, 3))
+            .unwrap();
+
+        let reverse = |k: usize| (0..n).fold(0, |acc, q| acc | ((k >> q & 1) << (n - 1 - q)));
+        for k in 0..1 << n {
+            assert!(unswapped.get(reverse(k)).approx_eq(swapped.get(k), 1e-10));
+        }
+
+        // The swap-free pair still round-trips
+        let transform = Qft::new(n).without_swaps();
+        let round_trip = transform.build().compose(&transform.build_inverse()).unwrap();
+        let state = Simulator::new().run_from_state(&round_trip, StateVector::basis(n, 13)).unwrap();
+        assert_eq!(state, StateVector::basis(n, 13));
+    }
+}
@@ -0,0 +1,392 @@
+//! # Shor's Algorithm (period finding)
+//!
+//! Factor integers by finding the period of modular exponentiation.
+//!
+//! ## The Problem
+//!
+//! To factor N, pick a random `a` coprime to N and find the smallest r > 0
+//! with aʳ ≡ 1 (mod N). If r is even, gcd(a^(r/2) ± 1, N) usually gives a
+//! factor. Finding r classically is as hard as factoring itself.
+//!
+//! ## How It Works
+//!
+//! 1. **Counting register**: put t qubits in uniform superposition
+//! 2. **Work register**: start in |1⟩
+//! 3. **Modular exponentiation**: counting qubit k controls multiplication
+//!    by a^(2^k) mod N, leaving |x⟩|aˣ mod N⟩
+//! 4. **Inverse QFT** on the counting register turns the period into peaks
+//!    at multiples of 2ᵗ/r
+//! 5. **Measure** and recover r from the phase with continued fractions
+//!
+//! ## Scope
+//!
+//! This scaffold supports moduli of the form N = 2ᵐ - 1 (such as 15) with
+//! multipliers a ≡ ±2ˢ (mod N). There, multiplication is just a rotation of
+//! the work register's bits (plus a bitwise complement for the minus sign),
+//! built from controlled-SWAPs and CNOTs. For N = 15 this covers every `a`
+//! coprime to 15. Other pairs would need general controlled modular
+//! multiplication, which is not implemented; [`Shor::new`] returns
+//! [`HomayaError::NotSupported`] for them.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use homaya_algorithms::Shor;
+//!
+//! let shor = Shor::new(7, 15).unwrap();
+//! let circuit = shor.build();
+//! assert_eq!(circuit.num_qubits(), 12); // 8 counting + 4 work
+//!
+//! // 21 = 2^m - 1 for no m
+//! assert!(Shor::new(2, 21).is_err());
+//! ```
+
+use homaya_core::{Circuit, HomayaError, Result};
+use homaya_sim::StateVector;
+use crate::qft::iqft;
+
+/// Shor's period-finding algorithm builder.
+#[derive(Debug, Clone)]
+pub struct Shor {
+    /// The base whose period we want
+    a: usize,
+    /// The modulus (the number being factored)
+    n: usize,
+    /// Number of counting (phase) qubits
+    counting_qubits: usize,
+    /// Number of work qubits, m for n = 2ᵐ - 1
+    work_qubits: usize,
+}
+
+impl Shor {
+    /// Create a new period-finding instance for aʳ ≡ 1 (mod n).
+    ///
+    /// Uses 2m counting qubits, where m is the work register size.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HomayaError::NotSupported`] if `n` is not of the form
+    /// 2ᵐ - 1 (m ≥ 2), if `a` is not coprime to `n`, or if `a` is not ±2ˢ
+    /// modulo `n` (see the [module docs](self)).
+    pub fn new(a: usize, n: usize) -> Result<Self> {
+        let work = work_qubits(n)?;
+        if gcd(a, n) != 1 {
+            return Err(HomayaError::NotSupported {
+                operation: "Shor period finding with a not coprime to n",
+            });
+        }
+        rotation_for(a % n, n)?;
+
+        Ok(Self {
+            a: a % n,
+            n,
+            counting_qubits: 2 * work,
+            work_qubits: work,
+        })
+    }
+
+    /// Set a custom number of counting qubits.
+    ///
+    /// More counting qubits sharpen the phase peaks at the cost of a
+    /// larger circuit.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`HomayaError::CircuitTooLarge`] if the counting and work
+    /// registers together exceed [`StateVector::MAX_QUBITS`], which also
+    /// keeps the measured phase's denominator 2ᵗ within a `u64`.
+    pub fn with_counting_qubits(mut self, counting_qubits: usize) -> Result<Self> {
+        let qubits = counting_qubits.saturating_add(self.work_qubits);
+        if qubits > StateVector::MAX_QUBITS {
+            return Err(HomayaError::CircuitTooLarge {
+                qubits,
+                max: StateVector::MAX_QUBITS,
+            });
+        }
+        self.counting_qubits = counting_qubits;
+        Ok(self)
+    }
+
+    /// Number of qubits in the work register.
+    pub fn work_qubits(&self) -> usize {
+        self.work_qubits
+    }
+
+    /// Number of qubits in the counting register.
+    pub fn counting_qubits(&self) -> usize {
+        self.counting_qubits
+    }
+
+    /// Build the full period-finding circuit.
+    ///
+    /// Counting qubits come first (qubit 0 is the least significant bit of
+    /// the measured phase) and are measured into classical bits of the same
+    /// index. The work register follows.
+    pub fn build(&self) -> Circuit {
+        let t = self.counting_qubits;
+        let mut circuit = Circuit::new(t + self.work_qubits());
+
+        // Work register starts in |1⟩
+        circuit = circuit.x(t);
+
+        for q in 0..t {
+            circuit = circuit.h(q);
+        }
+
+        let exponentiation =
+            modular_exponentiation(self.a, self.n, t).expect("new checked that n and a are supported");
+        circuit = circuit
+            .compose(&exponentiation)
+            .expect("exponentiation network fits the circuit");
+
+        circuit = circuit
+            .compose(&iqft(t))
+            .expect("counting register fits the circuit");
+
+        for q in 0..t {
+            circuit = circuit.measure(q, q);
+        }
+
+        circuit
+    }
+
+    /// Recover the period from a measured counting-register value.
+    ///
+    /// Expands `measured / 2ᵗ` as a continued fraction and tests each
+    /// convergent's denominator (and small multiples of it, in case the
+    /// phase s/r was reduced) until one satisfies aʳ ≡ 1 (mod n).
+    /// A zero phase carries no information and yields `None`.
+    pub fn period_from_measurement(&self, measured: u64) -> Option<usize> {
+        let denominator = 1u64 << self.counting_qubits;
+        for q in convergent_denominators(measured, denominator) {
+            if q == 1 {
+                continue;
+            }
+            let mut r = q as usize;
+            while r > 0 && r < self.n {
+                if mod_pow(self.a, r, self.n) == 1 {
+                    return Some(r);
+                }
+                r += q as usize;
+            }
+        }
+        None
+    }
+}
+
+/// Build the controlled modular-exponentiation network.
+///
+/// Returns a circuit on `num_qubits` control qubits followed by the work
+/// register, where control qubit k applies |y⟩ → |a^(2^k) · y mod n⟩ to the
+/// work register.
+///
+/// # Errors
+///
+/// Returns [`HomayaError::NotSupported`] if `n` is not of the form 2ᵐ - 1
+/// or `a` is not ±2ˢ modulo `n`.
+pub fn modular_exponentiation(a: usize, n: usize, num_qubits: usize) -> Result<Circuit> {
+    let work = work_qubits(n)?;
+    let mut circuit = Circuit::new(num_qubits + work);
+
+    // a^(2^k) mod n, by repeated squaring
+    let mut multiplier = a % n;
+    for control in 0..num_qubits {
+        let (shift, negate) = rotation_for(multiplier, n)?;
+
+        // Multiply by 2: rotate bits one place towards the most significant end
+        for _ in 0..shift {
+            for i in (0..work - 1).rev() {
+                circuit = circuit.cswap(control, num_qubits + i, num_qubits + i + 1);
+            }
+        }
+
+        // Multiply by -1: for y < n, n - y is the bitwise complement of y
+        if negate {
+            for i in 0..work {
+                circuit = circuit.cx(control, num_qubits + i);
+            }
+        }
+
+        multiplier = multiplier * multiplier % n;
+    }
+
+    Ok(circuit)
+}
+
+/// Build the complete period-finding circuit for aʳ ≡ 1 (mod n).
+///
+/// # Errors
+///
+/// Same as [`Shor::new`].
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_algorithms::shor;
+///
+/// let circuit = shor::period_finding(7, 15).unwrap();
+/// ```
+pub fn period_finding(a: usize, n: usize) -> Result<Circuit> {
+    Ok(Shor::new(a, n)?.build())
+}
+
+/// Number of work qubits for a modulus n = 2ᵐ - 1.
+fn work_qubits(n: usize) -> Result<usize> {
+    let m = n.wrapping_add(1).trailing_zeros() as usize;
+    if m >= 2 && m < usize::BITS as usize && n + 1 == 1 << m {
+        Ok(m)
+    } else {
+        Err(HomayaError::NotSupported {
+            operation: "Shor period finding with n not of the form 2^m - 1",
+        })
+    }
+}
+
+/// Find (s, negate) with multiplier ≡ ±2ˢ (mod n).
+fn rotation_for(multiplier: usize, n: usize) -> Result<(usize, bool)> {
+    let found = (0..work_qubits(n)?).find_map(|s| {
+        let power = (1usize << s) % n;
+        if power == multiplier {
+            Some((s, false))
+        } else if (n - power) % n == multiplier {
+            Some((s, true))
+        } else {
+            None
+        }
+    });
+    found.ok_or(HomayaError::NotSupported {
+        operation: "Shor period finding with a not of the form ±2^s mod n",
+    })
+}
+
+/// Denominators of the continued-fraction convergents of num/den.
+fn convergent_denominators(mut num: u64, mut den: u64) -> Vec<u64> {
+    let mut result = Vec::new();
+    // q_{-2} = 1, q_{-1} = 0
+    let (mut q_prev, mut q) = (1u64, 0u64);
+    while den != 0 {
+        let term = num / den;
+        (num, den) = (den, num % den);
+        (q_prev, q) = (q, term * q + q_prev);
+        if q > 0 {
+            result.push(q);
+        }
+    }
+    result
+}
+
+/// Greatest common divisor.
+fn gcd(mut a: usize, mut b: usize) -> usize {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Compute base^exp mod m.
+fn mod_pow(base: usize, mut exp: usize, m: usize) -> usize {
+    let mut result = 1 % m;
+    let mut base = base % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_sim::Simulator;
+
+    #[test]
+    fn test_circuit_size() {
+        let shor = Shor::new(7, 15).unwrap();
+        assert_eq!(shor.work_qubits(), 4);
+        assert_eq!(shor.counting_qubits(), 8);
+        assert_eq!(shor.build().num_qubits(), 12);
+    }
+
+    #[test]
+    fn test_modular_multiplication_on_basis_states() {
+        // One control qubit set to |1⟩ multiplies the work register by a
+        for a in [2, 4, 7, 8, 11, 13] {
+            let circuit = Circuit::new(5).compose(&modular_exponentiation(a, 15, 1).unwrap()).unwrap();
+            let mut sim = Simulator::new();
+            for y in 1..15usize {
+                let mut start = vec![homaya_core::Complex::ZERO; 32];
+                start[1 | (y << 1)] = homaya_core::Complex::ONE;
+                let input = StateVector::from_amplitudes(start).unwrap();
+                let state = sim.run_from_state(&circuit, input).unwrap();
+                let expected = 1 | ((a * y % 15) << 1);
+                assert!(state.probability(expected) > 0.99, "{} * {} mod 15", a, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_period_of_7_mod_15() {
+        let shor = Shor::new(7, 15).unwrap();
+        let circuit = shor.build();
+
+        let mut found = false;
+        for seed in 1..=16 {
+            let mut sim = Simulator::with_seed(seed);
+            let (_, result) = sim.run_with_measurements(&circuit).unwrap();
+            let measured = result.as_int();
+
+            // Peaks sit at multiples of 2^8 / 4
+            assert_eq!(measured % 64, 0, "unexpected phase {}", measured);
+
+            match shor.period_from_measurement(measured) {
+                Some(r) => {
+                    assert_eq!(r, 4);
+                    found = true;
+                }
+                None => assert_eq!(measured, 0),
+            }
+        }
+        assert!(found);
+    }
+
+    #[test]
+    fn test_convergents() {
+        // 192/256 = 3/4
+        assert_eq!(convergent_denominators(192, 256).last(), Some(&4));
+        assert_eq!(Shor::new(7, 15).unwrap().period_from_measurement(192), Some(4));
+        // 1/2 is a reduced 2/4; the multiple recovers the true period
+        assert_eq!(Shor::new(7, 15).unwrap().period_from_measurement(128), Some(4));
+        assert_eq!(Shor::new(7, 15).unwrap().period_from_measurement(0), None);
+    }
+
+    #[test]
+    fn test_unsupported_inputs_are_errors() {
+        // 21 is not 2^m - 1; 3 shares a factor with 15; 3 ≢ ±2^s (mod 31)
+        for (a, n) in [(2, 21), (3, 15), (3, 31)] {
+            assert!(
+                matches!(Shor::new(a, n), Err(HomayaError::NotSupported { .. })),
+                "a = {}, n = {}",
+                a,
+                n
+            );
+        }
+        assert!(period_finding(2, 21).is_err());
+        assert!(modular_exponentiation(3, 31, 2).is_err());
+    }
+
+    #[test]
+    fn test_counting_qubits_fit_the_simulator() {
+        let shor = Shor::new(7, 15).unwrap();
+        let max = StateVector::MAX_QUBITS;
+        assert_eq!(shor.clone().with_counting_qubits(max - 4).unwrap().counting_qubits(), max - 4);
+        for t in [max - 3, 64, usize::MAX] {
+            assert!(matches!(
+                shor.clone().with_counting_qubits(t),
+                Err(HomayaError::CircuitTooLarge { .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! # Entangled State Preparation
+//!
+//! Circuits for the two standard families of multipartite entanglement.
+//!
+//! ## GHZ States
+//!
+//! (|00…0⟩ + |11…1⟩)/√2: all qubits agree, and measuring any one of them
+//! collapses the rest. A Hadamard on qubit 0 followed by a CNOT chain.
+//!
+//! ## W States
+//!
+//! (|10…0⟩ + |01…0⟩ + … + |00…1⟩)/√n: exactly one excitation, shared
+//! equally. Unlike GHZ, losing one qubit leaves the others entangled.
+//!
+//! The circuit starts with the excitation on qubit 0 and walks it along the
+//! chain. At step k a controlled-Ry moves all but a 1/(n − k) share of the
+//! remaining amplitude onto qubit k + 1, and a CNOT back clears qubit k in
+//! that branch.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use homaya_algorithms::states::{ghz_state, w_state};
+//! use homaya_sim::Simulator;
+//!
+//! let ghz = Simulator::new().run(&ghz_state(3)).unwrap();
+//! assert!((ghz.probability(0b111) - 0.5).abs() < 1e-10);
+//!
+//! let w = Simulator::new().run(&w_state(3)).unwrap();
+//! assert!((w.probability(0b010) - 1.0 / 3.0).abs() < 1e-10);
+//! ```
+
+use homaya_core::Circuit;
+
+/// Prepare the GHZ state (|00…0⟩ + |11…1⟩)/√2 on `num_qubits` qubits.
+///
+/// # Panics
+///
+/// Panics if `num_qubits` is 0.
+pub fn ghz_state(num_qubits: usize) -> Circuit {
+    assert!(num_qubits > 0, "GHZ state needs at least one qubit");
+
+    (1..num_qubits).fold(Circuit::new(num_qubits).h(0), |circuit, q| circuit.cx(q - 1, q))
+}
+
+/// Prepare the W state (|10…0⟩ + |01…0⟩ + … + |00…1⟩)/√n on `num_qubits`
+/// qubits.
+///
+/// # Panics
+///
+/// Panics if `num_qubits` is 0.
+pub fn w_state(num_qubits: usize) -> Circuit {
+    assert!(num_qubits > 0, "W state needs at least one qubit");
+
+    let mut circuit = Circuit::new(num_qubits).x(0);
+    for k in 0..num_qubits - 1 {
+        // Keep amplitude √(1/(n − k)) on qubit k, pass the rest to k + 1
+        let keep = (1.0 / (num_qubits - k) as f64).sqrt();
+        circuit = circuit.cry(2.0 * keep.acos(), k, k + 1).cx(k + 1, k);
+    }
+    circuit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_sim::Simulator;
+
+    #[test]
+    fn test_ghz_only_all_zeros_or_all_ones() {
+        for n in 1..=6 {
+            let state = Simulator::new().run(&ghz_state(n)).unwrap();
+            let ones = (1 << n) - 1;
+            assert!((state.probability(0) - 0.5).abs() < 1e-10);
+            assert!((state.probability(ones) - 0.5).abs() < 1e-10);
+        }
+
+        let counts = Simulator::with_seed(2).sample(&ghz_state(4).measure_all(), 200).unwrap();
+        assert_eq!(counts.len(), 2);
+        assert!(counts.contains_key("0000") && counts.contains_key("1111"));
+    }
+
+    #[test]
+    fn test_w_single_excitations_are_equal() {
+        for n in 1..=6 {
+            let state = Simulator::new().run(&w_state(n)).unwrap();
+            for index in 0..1usize << n {
+                let expected = if index.count_ones() == 1 { 1.0 / n as f64 } else { 0.0 };
+                assert!(
+                    (state.probability(index) - expected).abs() < 1e-10,
+                    "n={} index={:b}: {}",
+                    n,
+                    index,
+                    state.probability(index)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_w_sampling() {
+        let counts = Simulator::with_seed(5).sample(&w_state(4).measure_all(), 800).unwrap();
+        assert_eq!(counts.len(), 4);
+        for (bits, &count) in &counts {
+            assert_eq!(bits.matches('1').count(), 1, "{}", bits);
+            assert!((150..=250).contains(&count), "{} seen {} times", bits, count);
+        }
+    }
+}
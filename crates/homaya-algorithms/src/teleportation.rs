@@ -0,0 +1,115 @@
+//! # Quantum Teleportation
+//!
+//! Move an unknown qubit state using one Bell pair and two classical bits.
+//!
+//! ## The Problem
+//!
+//! Alice holds a qubit in an unknown state α|0⟩ + β|1⟩ and wants Bob to
+//! have it. She cannot measure α and β, and copying is forbidden by the
+//! no-cloning theorem. If they share an entangled pair, two classical bits
+//! are enough.
+//!
+//! ## How It Works
+//!
+//! 1. **Bell pair**: qubits 1 (Alice) and 2 (Bob) are entangled with H + CX
+//! 2. **Bell measurement**: Alice applies CX(0, 1) and H(0), then measures
+//!    qubits 0 and 1 into classical bits 0 and 1
+//! 3. **Corrections**: Bob applies X if bit 1 is set and Z if bit 0 is set
+//!
+//! Whatever Alice measures, qubit 2 ends up in the original state of
+//! qubit 0, which is left in a measured basis state.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use homaya_algorithms::teleportation;
+//! use homaya_sim::Simulator;
+//!
+//! // Teleport |1⟩: Bob's qubit is |1⟩ on every run
+//! let circuit = teleportation::teleport_state(homaya_core::PI, 0.0);
+//! let (state, _) = Simulator::with_seed(1).run_with_measurements(&circuit).unwrap();
+//! assert!((state.qubit_probability(2) - 1.0).abs() < 1e-10);
+//! ```
+
+use homaya_core::Circuit;
+
+/// Build the teleportation protocol for whatever state qubit 0 holds.
+///
+/// The circuit has 3 qubits and 2 classical bits. Qubit 0 starts in |0⟩;
+/// compose a preparation in front of it to teleport something else, or use
+/// [`teleport_state`].
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_algorithms::teleportation::teleportation;
+///
+/// let circuit = teleportation();
+/// assert_eq!(circuit.num_qubits(), 3);
+/// assert_eq!(circuit.num_clbits(), 2);
+/// ```
+pub fn teleportation() -> Circuit {
+    Circuit::with_clbits(3, 2)
+        // Shared Bell pair between Alice (1) and Bob (2)
+        .h(1)
+        .cx(1, 2)
+        // Alice measures qubits 0 and 1 in the Bell basis
+        .cx(0, 1)
+        .h(0)
+        .measure(0, 0)
+        .measure(1, 1)
+        // Bob's corrections, conditioned on Alice's bits
+        .x_if(2, 1, 1)
+        .z_if(2, 0, 1)
+}
+
+/// Teleport the state cos(θ/2)|0⟩ + e^{iφ} sin(θ/2)|1⟩.
+///
+/// `theta` and `phi` are the polar and azimuthal angles on the Bloch
+/// sphere, so qubit 2 ends with Bloch vector
+/// (sin θ cos φ, sin θ sin φ, cos θ).
+pub fn teleport_state(theta: f64, phi: f64) -> Circuit {
+    Circuit::with_clbits(3, 2)
+        .u(theta, phi, 0.0, 0)
+        .compose(&teleportation())
+        .expect("protocol has the circuit's qubit count")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_sim::Simulator;
+
+    #[test]
+    fn test_bloch_vector_is_teleported() {
+        let mut sim = Simulator::with_seed(11);
+        for (theta, phi) in [(0.0f64, 0.0f64), (1.1, 0.4), (2.3, -2.0), (homaya_core::PI, 1.0)] {
+            let expected = (theta.sin() * phi.cos(), theta.sin() * phi.sin(), theta.cos());
+            let circuit = teleport_state(theta, phi);
+
+            for _ in 0..20 {
+                let (state, _) = sim.run_with_measurements(&circuit).unwrap();
+                let (x, y, z) = state.bloch_vector(2).unwrap();
+                assert!(
+                    (x - expected.0).abs() < 1e-10 && (y - expected.1).abs() < 1e-10 && (z - expected.2).abs() < 1e-10,
+                    "theta={} phi={}: got ({}, {}, {})",
+                    theta,
+                    phi,
+                    x,
+                    y,
+                    z
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_measurement_branch_occurs() {
+        let circuit = teleport_state(1.1, 0.4);
+        let mut sim = Simulator::with_seed(3);
+        let outcomes: std::collections::HashSet<_> = (0..40)
+            .map(|_| sim.run_with_measurements(&circuit).unwrap().1.bits)
+            .collect();
+        assert_eq!(outcomes.len(), 4);
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! Fluent API for constructing quantum circuits.
 
-use crate::{Gate, GateType, HomayaError, Result};
+use crate::{Gate, GateParams, GateType, HomayaError, QuantumRegister, Result};
 
 /// A quantum instruction: gate + target qubits.
 #[derive(Clone, Debug, PartialEq)]
@@ -14,6 +14,10 @@ pub struct Instruction {
     pub qubits: Vec<usize>,
     /// Classical bit indices (for measurement)
     pub clbits: Vec<usize>,
+    /// Classical condition `(clbit, value)`: apply only if the bit holds `value`
+    pub condition: Option<(usize, u8)>,
+    /// Name of a snapshot instruction
+    pub label: Option<std::string::String>,
 }
 
 impl Instruction {
@@ -24,13 +28,38 @@ impl Instruction {
             gate,
             qubits,
             clbits: Vec::new(),
+            condition: None,
+            label: None,
         }
     }
 
     /// Create an instruction with classical bits.
     #[inline]
     pub fn with_clbits(gate: Gate, qubits: Vec<usize>, clbits: Vec<usize>) -> Self {
-        Self { gate, qubits, clbits }
+        Self {
+            gate,
+            qubits,
+            clbits,
+            condition: None,
+            label: None,
+        }
+    }
+
+    /// Apply this instruction only when classical bit `clbit` equals `value`.
+    #[inline]
+    pub fn conditioned(mut self, clbit: usize, value: u8) -> Self {
+        self.condition = Some((clbit, value));
+        self
+    }
+
+    /// Returns true if this instruction is a barrier.
+    ///
+    /// Barriers are no-ops during simulation, but optimization passes must
+    /// treat them as fences: no gate may be cancelled, merged, or moved
+    /// across one.
+    #[inline]
+    pub fn is_barrier(&self) -> bool {
+        self.gate.gate_type == GateType::Barrier
     }
 }
 
@@ -60,6 +89,19 @@ pub struct Circuit {
     instructions: Vec<Instruction>,
     /// Optional name
     name: Option<std::string::String>,
+    /// Named qubit registers
+    qregs: Vec<QuantumRegister>,
+}
+
+/// Whether an instruction is a gate on two or more qubits.
+fn is_multi_qubit_gate(inst: &Instruction) -> bool {
+    inst.gate.is_unitary() && inst.qubits.len() >= 2
+}
+
+/// Whether an instruction orders the qubits it spans without acting on
+/// them: a barrier, or a snapshot, which spans every qubit.
+fn is_fence(inst: &Instruction) -> bool {
+    inst.is_barrier() || inst.gate.gate_type == GateType::Snapshot
 }
 
 impl Circuit {
@@ -71,6 +113,7 @@ impl Circuit {
             num_clbits: 0,
             instructions: Vec::new(),
             name: None,
+            qregs: Vec::new(),
         }
     }
 
@@ -82,6 +125,7 @@ impl Circuit {
             num_clbits,
             instructions: Vec::new(),
             name: None,
+            qregs: Vec::new(),
         }
     }
 
@@ -104,6 +148,163 @@ impl Circuit {
         self.num_clbits
     }
 
+    /// Add a named qubit register, growing the circuit by `size` qubits.
+    ///
+    /// The register occupies the next `size` global qubit indices.
+    pub fn add_qreg(&mut self, name: impl Into<std::string::String>, size: usize) -> QuantumRegister {
+        let reg = QuantumRegister::new(name.into(), self.num_qubits, size);
+        self.num_qubits += size;
+        self.qregs.push(reg.clone());
+        reg
+    }
+
+    /// Get the named qubit registers.
+    #[inline]
+    pub fn qregs(&self) -> &[QuantumRegister] {
+        &self.qregs
+    }
+
+    /// Check whether the circuit fits on a backend with `backend_qubits` qubits.
+    #[inline]
+    pub const fn is_valid_for(&self, backend_qubits: usize) -> bool {
+        self.num_qubits <= backend_qubits
+    }
+
+    /// Embed the circuit into a backend with `backend_qubits` qubits.
+    ///
+    /// Returns a copy whose qubit count is grown to the backend size. The
+    /// extra qubits are idle and start in |0⟩.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CircuitTooLarge` if the circuit needs more qubits than the
+    /// backend provides.
+    pub fn fit_to(&self, backend_qubits: usize) -> Result<Circuit> {
+        if !self.is_valid_for(backend_qubits) {
+            return Err(HomayaError::CircuitTooLarge {
+                qubits: self.num_qubits,
+                max: backend_qubits,
+            });
+        }
+        let mut result = self.clone();
+        result.num_qubits = backend_qubits;
+        Ok(result)
+    }
+
+    /// Check that every instruction addresses bits that exist.
+    ///
+    /// The builder methods record whatever indices they are given, so
+    /// `Circuit::new(2).h(5)` is representable; this catches it before a
+    /// simulator indexes out of bounds. Verifies operand counts, qubit and
+    /// classical bit ranges (including the bits conditions read), and
+    /// repeated qubits within an instruction. Symbolic parameters must be
+    /// bound before a circuit can run, so they are rejected too.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found: `QubitMismatch`, `QubitOutOfRange`,
+    /// `DuplicateQubit` or `ClbitOutOfRange`, or `UnboundParameter` if a
+    /// symbolic parameter has not been [bound](Self::bind).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// assert!(Circuit::new(2).h(0).cx(0, 1).validate().is_ok());
+    /// assert!(Circuit::new(2).h(5).validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        for inst in &self.instructions {
+            self.check_operands(&inst.gate, &inst.qubits)?;
+            let condition_bit = inst.condition.map(|(c, _)| c);
+            if let Some(clbit) = inst.clbits.iter().copied().chain(condition_bit).find(|&c| c >= self.num_clbits) {
+                return Err(HomayaError::ClbitOutOfRange {
+                    clbit,
+                    max: self.num_clbits,
+                });
+            }
+            if let GateParams::Parameter { name, .. } = &inst.gate.params {
+                return Err(HomayaError::UnboundParameter { name: name.clone() });
+            }
+        }
+        Ok(())
+    }
+
+    /// List the free symbolic parameters, in order of first use.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let ansatz = Circuit::new(2).ry_param("a", 0).rzz_param("b", 0, 1).rx_param("a", 1);
+    /// assert_eq!(ansatz.parameters(), ["a", "b"]);
+    /// ```
+    pub fn parameters(&self) -> Vec<std::string::String> {
+        let mut names: Vec<std::string::String> = Vec::new();
+        for inst in &self.instructions {
+            if let GateParams::Parameter { name, .. } = &inst.gate.params {
+                if !names.contains(name) {
+                    names.push(name.clone());
+                }
+            }
+        }
+        names
+    }
+
+    /// Replace every symbolic parameter with its value.
+    ///
+    /// A gate with angle `scale × name` gets the concrete angle
+    /// `scale * values[name]`. Values for names the circuit does not use
+    /// are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnboundParameter` for the first parameter missing from
+    /// `values`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use homaya_core::{Circuit, PI};
+    ///
+    /// let ansatz = Circuit::new(1).rx_param("theta", 0);
+    /// let values = HashMap::from([("theta".to_string(), PI / 2.0)]);
+    /// assert_eq!(ansatz.bind(&values).unwrap().instructions(), Circuit::new(1).rx(PI / 2.0, 0).instructions());
+    /// ```
+    pub fn bind(&self, values: &std::collections::HashMap<std::string::String, f64>) -> Result<Circuit> {
+        let instructions = self
+            .instructions
+            .iter()
+            .map(|inst| {
+                let mut inst = inst.clone();
+                if let GateParams::Parameter { name, scale } = &inst.gate.params {
+                    let value = values
+                        .get(name)
+                        .ok_or_else(|| HomayaError::UnboundParameter { name: name.clone() })?;
+                    inst.gate.params = GateParams::Angle(scale * value);
+                }
+                Ok(inst)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self.with_instructions(instructions))
+    }
+
+    /// Check every instruction for problems that would break simulation.
+    ///
+    /// Runs [`validate`](Self::validate) and also checks that all angles
+    /// are finite.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first problem found.
+    pub fn validate_all(&self) -> Result<()> {
+        self.validate()?;
+        self.instructions.iter().try_for_each(|inst| inst.gate.check_params())
+    }
+
     /// Get the instructions.
     #[inline]
     pub fn instructions(&self) -> &[Instruction] {
@@ -123,7 +324,57 @@ impl Circuit {
     }
 
     /// Calculate circuit depth (critical path length).
+    ///
+    /// A barrier adds no depth of its own but synchronizes the qubits it
+    /// spans (all of them, for [`barrier_all`](Self::barrier_all)): gates
+    /// after it start no earlier than the deepest of those qubits. So
+    /// `h(0).h(0).barrier_all().h(1)` has depth 3, where without the
+    /// barrier the second qubit's H would run in the first layer. A
+    /// snapshot synchronizes every qubit in the same way.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// assert_eq!(Circuit::new(2).h(0).h(0).h(1).depth(), 2);
+    /// assert_eq!(Circuit::new(2).h(0).h(0).barrier_all().h(1).depth(), 3);
+    /// ```
     pub fn depth(&self) -> usize {
+        self.weighted_depth(|_| true)
+    }
+
+    /// Calculate the critical-path depth counting only the given gate types.
+    ///
+    /// Other gates still carry dependencies between qubits but add no depth,
+    /// so `depth_by_type(&[GateType::CX])` gives the two-qubit-gate depth
+    /// of a CX-based circuit.
+    pub fn depth_by_type(&self, types: &[GateType]) -> usize {
+        self.weighted_depth(|inst| types.contains(&inst.gate.gate_type))
+    }
+
+    /// Calculate the critical-path depth counting only multi-qubit gates.
+    ///
+    /// Like [`depth_by_type`](Self::depth_by_type) for every gate acting on
+    /// two or more qubits (CX, CZ, SWAP, Toffoli, ...). Entangling gates
+    /// dominate error and run time on current hardware, so this is
+    /// usually the depth that matters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let circuit = Circuit::new(3).h(0).cx(0, 1).t(1).cz(1, 2).h(2);
+    /// assert_eq!(circuit.depth(), 5);
+    /// assert_eq!(circuit.two_qubit_depth(), 2);
+    /// ```
+    pub fn two_qubit_depth(&self) -> usize {
+        self.weighted_depth(is_multi_qubit_gate)
+    }
+
+    /// Critical-path length where only instructions matching `counts` add a layer.
+    fn weighted_depth(&self, counts: impl Fn(&Instruction) -> bool) -> usize {
         if self.instructions.is_empty() {
             return 0;
         }
@@ -132,22 +383,180 @@ impl Circuit {
         let mut qubit_depth = std::vec![0usize; self.num_qubits];
 
         for inst in &self.instructions {
-            if inst.gate.gate_type == GateType::Barrier {
+            if is_fence(inst) {
+                // Synchronize the fenced qubits
+                let fenced = self.barrier_span(inst);
+                let level = fenced.iter().map(|&q| qubit_depth[q]).max().unwrap_or(0);
+                for &q in &fenced {
+                    qubit_depth[q] = level;
+                }
                 continue;
             }
 
             // Find max depth among target qubits
             let max_depth = inst.qubits.iter().map(|&q| qubit_depth[q]).max().unwrap_or(0);
+            let new_depth = if counts(inst) { max_depth + 1 } else { max_depth };
 
-            // All target qubits advance to max + 1
+            // All target qubits advance together
             for &q in &inst.qubits {
-                qubit_depth[q] = max_depth + 1;
+                qubit_depth[q] = new_depth;
             }
         }
 
         qubit_depth.into_iter().max().unwrap_or(0)
     }
 
+    /// Qubits a barrier fences, in range: all of them for an empty barrier
+    /// or a snapshot.
+    fn barrier_span(&self, barrier: &Instruction) -> Vec<usize> {
+        if barrier.qubits.is_empty() {
+            (0..self.num_qubits).collect()
+        } else {
+            barrier.qubits.iter().copied().filter(|&q| q < self.num_qubits).collect()
+        }
+    }
+
+    /// Get one longest dependency chain, as instruction indices in order.
+    ///
+    /// The chain has [`depth`](Self::depth) instructions; each one touches
+    /// a qubit of the next, or is ordered before it by a barrier that spans
+    /// both. Barriers and snapshots are never part of the chain.
+    pub fn critical_path(&self) -> Vec<usize> {
+        // Per qubit: (depth, last instruction) after the instructions so far
+        let mut frontier: Vec<(usize, Option<usize>)> = std::vec![(0, None); self.num_qubits];
+        let mut prev: Vec<Option<usize>> = std::vec![None; self.instructions.len()];
+        let mut end: (usize, Option<usize>) = (0, None);
+
+        for (i, inst) in self.instructions.iter().enumerate() {
+            if is_fence(inst) {
+                // Every fenced qubit now waits on the deepest one
+                let fenced = self.barrier_span(inst);
+                let deepest = fenced
+                    .iter()
+                    .map(|&q| frontier[q])
+                    .fold((0, None), |best, f| if f.0 > best.0 { f } else { best });
+                for &q in &fenced {
+                    frontier[q] = deepest;
+                }
+                continue;
+            }
+
+            // Deepest predecessor; ties go to the first qubit listed
+            let (depth, pred) = inst
+                .qubits
+                .iter()
+                .map(|&q| frontier[q])
+                .fold((0, None), |best, f| if f.0 > best.0 { f } else { best });
+            prev[i] = pred;
+
+            for &q in &inst.qubits {
+                frontier[q] = (depth + 1, Some(i));
+            }
+            if depth + 1 > end.0 {
+                end = (depth + 1, Some(i));
+            }
+        }
+
+        let mut path = Vec::with_capacity(end.0);
+        let mut cursor = end.1;
+        while let Some(i) = cursor {
+            path.push(i);
+            cursor = prev[i];
+        }
+        path.reverse();
+        path
+    }
+
+    /// Group the instructions into moments of gates that can run in parallel.
+    ///
+    /// Each instruction goes into the earliest layer after every earlier
+    /// instruction it depends on: one sharing a qubit, one writing a
+    /// classical bit it reads or writes, or, for a measurement, one reading
+    /// the bit it overwrites. Gates that only read the same bit can share a
+    /// layer. No two instructions in a layer share
+    /// a qubit, and the layer count equals [`depth`](Self::depth) for
+    /// circuits without classical conditions.
+    ///
+    /// Barriers are not listed; they synchronize their qubits as in
+    /// `depth`, so the gates after one start a new layer. A snapshot
+    /// synchronizes every qubit and is listed last in the layer before it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::{Circuit, GateType};
+    ///
+    /// let circuit = Circuit::new(3).h(0).h(1).cx(0, 1).h(2).cx(1, 2);
+    /// let layers = circuit.layers();
+    /// assert_eq!(layers.len(), 3);
+    ///
+    /// // Two-qubit-gate depth: layers containing a CX
+    /// let cx_layers = layers
+    ///     .iter()
+    ///     .filter(|layer| layer.iter().any(|inst| inst.gate.gate_type == GateType::CX))
+    ///     .count();
+    /// assert_eq!(cx_layers, 2);
+    /// ```
+    pub fn layers(&self) -> Vec<Vec<&Instruction>> {
+        // First free layer on each qubit, and after the last write and the
+        // last read of each classical bit
+        let mut qubit_level = std::vec![0usize; self.num_qubits];
+        let mut written = std::vec![0usize; self.num_clbits];
+        let mut read = std::vec![0usize; self.num_clbits];
+        let mut layers: Vec<Vec<&Instruction>> = Vec::new();
+
+        for inst in &self.instructions {
+            if is_fence(inst) {
+                let fenced = self.barrier_span(inst);
+                let level = fenced.iter().map(|&q| qubit_level[q]).max().unwrap_or(0);
+                for &q in &fenced {
+                    qubit_level[q] = level;
+                }
+                // A snapshot closes the layer of everything before it
+                if inst.gate.gate_type == GateType::Snapshot {
+                    let last = level.saturating_sub(1);
+                    if layers.len() <= last {
+                        layers.resize_with(last + 1, Vec::new);
+                    }
+                    layers[last].push(inst);
+                }
+                continue;
+            }
+
+            let reads = inst.condition.map(|(c, _)| c);
+            let level = inst
+                .qubits
+                .iter()
+                .filter_map(|&q| qubit_level.get(q))
+                .chain(reads.iter().filter_map(|&c| written.get(c)))
+                .chain(inst.clbits.iter().flat_map(|&c| written.get(c).into_iter().chain(read.get(c))))
+                .copied()
+                .max()
+                .unwrap_or(0);
+
+            if layers.len() <= level {
+                layers.resize_with(level + 1, Vec::new);
+            }
+            layers[level].push(inst);
+
+            for &q in &inst.qubits {
+                if let Some(l) = qubit_level.get_mut(q) {
+                    *l = level + 1;
+                }
+            }
+            for &c in &inst.clbits {
+                if let Some(l) = written.get_mut(c) {
+                    *l = level + 1;
+                }
+            }
+            if let Some(l) = reads.and_then(|c| read.get_mut(c)) {
+                *l = (*l).max(level + 1);
+            }
+        }
+
+        layers
+    }
+
     /// Count gates by type.
     pub fn count_gates(&self) -> std::collections::BTreeMap<GateType, usize> {
         let mut counts = std::collections::BTreeMap::new();
@@ -157,6 +566,128 @@ impl Circuit {
         counts
     }
 
+    /// Count gates acting on two or more qubits.
+    ///
+    /// Barriers, measurements and snapshots are not gates and never count.
+    pub fn two_qubit_gate_count(&self) -> usize {
+        self.instructions.iter().filter(|inst| is_multi_qubit_gate(inst)).count()
+    }
+
+    /// Export the circuit's dependency DAG in Graphviz DOT format.
+    ///
+    /// Each instruction becomes a node `n<index>`. An edge `a -> b` means
+    /// `b` is the next instruction after `a` to touch one of its qubits,
+    /// so nodes without a path between them can run in parallel.
+    pub fn to_dot(&self) -> std::string::String {
+        use std::fmt::Write;
+
+        let mut out = std::string::String::from("digraph circuit {\n");
+        out.push_str("    rankdir=LR;\n");
+        if let Some(name) = &self.name {
+            let _ = writeln!(out, "    label=\"{}\";", name.replace('"', "\\\""));
+        }
+
+        for (i, inst) in self.instructions.iter().enumerate() {
+            let qubits: Vec<std::string::String> =
+                inst.qubits.iter().map(|q| std::format!("q{}", q)).collect();
+            let _ = writeln!(
+                out,
+                "    n{} [label=\"{:?}{} {}\"];",
+                i,
+                inst.gate.gate_type,
+                crate::fmt::params_to_string(&inst.gate.params),
+                qubits.join(",")
+            );
+        }
+
+        // Last instruction to touch each qubit
+        let mut last: Vec<Option<usize>> = std::vec![None; self.num_qubits];
+        for (i, inst) in self.instructions.iter().enumerate() {
+            let mut preds: Vec<usize> = inst
+                .qubits
+                .iter()
+                .filter_map(|&q| last.get(q).copied().flatten())
+                .collect();
+            preds.sort_unstable();
+            preds.dedup();
+            for p in preds {
+                let _ = writeln!(out, "    n{} -> n{};", p, i);
+            }
+            for &q in &inst.qubits {
+                if let Some(slot) = last.get_mut(q) {
+                    *slot = Some(i);
+                }
+            }
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Insert an instruction at `index`, shifting later instructions back.
+    ///
+    /// `index == len()` appends.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `index` is past the end, a qubit is out of range
+    /// or repeated, or the qubit count doesn't match the gate.
+    pub fn insert_at(&mut self, index: usize, gate: Gate, qubits: Vec<usize>) -> Result<()> {
+        if index > self.instructions.len() {
+            return Err(HomayaError::InstructionOutOfRange {
+                index,
+                len: self.instructions.len(),
+            });
+        }
+        self.check_operands(&gate, &qubits)?;
+        self.instructions.insert(index, Instruction::new(gate, qubits));
+        Ok(())
+    }
+
+    /// Check that `qubits` is a valid operand list for `gate` on this circuit.
+    fn check_operands(&self, gate: &Gate, qubits: &[usize]) -> Result<()> {
+        let arity = gate.num_qubits();
+        // Barriers span any number of qubits
+        if arity != 0 && qubits.len() != arity {
+            return Err(HomayaError::QubitMismatch {
+                expected: arity,
+                got: qubits.len(),
+            });
+        }
+        // MCX needs at least its target
+        if gate.gate_type == GateType::MCX && qubits.is_empty() {
+            return Err(HomayaError::QubitMismatch { expected: 1, got: 0 });
+        }
+        for (i, &q) in qubits.iter().enumerate() {
+            if q >= self.num_qubits {
+                return Err(HomayaError::QubitOutOfRange {
+                    qubit: q,
+                    max: self.num_qubits,
+                });
+            }
+            if qubits[..i].contains(&q) {
+                return Err(HomayaError::DuplicateQubit { qubit: q });
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy of this circuit's metadata with a replacement instruction list.
+    pub(crate) fn with_instructions(&self, instructions: Vec<Instruction>) -> Circuit {
+        Circuit {
+            num_qubits: self.num_qubits,
+            num_clbits: self.num_clbits,
+            instructions,
+            name: self.name.clone(),
+            qregs: self.qregs.clone(),
+        }
+    }
+
+    /// Replace the classical bit count, for passes that renumber clbits.
+    pub(crate) fn set_num_clbits(&mut self, num_clbits: usize) {
+        self.num_clbits = num_clbits;
+    }
+
     /// Add a raw instruction.
     fn push(&mut self, inst: Instruction) {
         self.instructions.push(inst);
@@ -255,6 +786,34 @@ impl Circuit {
         self
     }
 
+    /// Apply rotation around X-axis by a named parameter; see [`bind`](Self::bind).
+    #[inline]
+    pub fn rx_param(mut self, name: impl Into<std::string::String>, q: usize) -> Self {
+        self.push(Instruction::new(Gate::symbolic(GateType::Rx, name), std::vec![q]));
+        self
+    }
+
+    /// Apply rotation around Y-axis by a named parameter; see [`bind`](Self::bind).
+    #[inline]
+    pub fn ry_param(mut self, name: impl Into<std::string::String>, q: usize) -> Self {
+        self.push(Instruction::new(Gate::symbolic(GateType::Ry, name), std::vec![q]));
+        self
+    }
+
+    /// Apply rotation around Z-axis by a named parameter; see [`bind`](Self::bind).
+    #[inline]
+    pub fn rz_param(mut self, name: impl Into<std::string::String>, q: usize) -> Self {
+        self.push(Instruction::new(Gate::symbolic(GateType::Rz, name), std::vec![q]));
+        self
+    }
+
+    /// Apply phase gate by a named parameter; see [`bind`](Self::bind).
+    #[inline]
+    pub fn p_param(mut self, name: impl Into<std::string::String>, q: usize) -> Self {
+        self.push(Instruction::new(Gate::symbolic(GateType::P, name), std::vec![q]));
+        self
+    }
+
     /// Apply general U gate.
     #[inline]
     pub fn u(mut self, theta: f64, phi: f64, lambda: f64, q: usize) -> Self {
@@ -299,6 +858,19 @@ impl Circuit {
         self
     }
 
+    /// Apply controlled-U gate.
+    #[inline]
+    pub fn cu(mut self, theta: f64, phi: f64, lambda: f64, control: usize, target: usize) -> Self {
+        self.push(Instruction::new(Gate::cu(theta, phi, lambda), std::vec![control, target]));
+        self
+    }
+
+    /// Apply controlled-Ry gate, as `CU(θ, 0, 0)`.
+    #[inline]
+    pub fn cry(self, theta: f64, control: usize, target: usize) -> Self {
+        self.cu(theta, 0.0, 0.0, control, target)
+    }
+
     /// Apply SWAP gate.
     #[inline]
     pub fn swap(mut self, q1: usize, q2: usize) -> Self {
@@ -306,6 +878,75 @@ impl Circuit {
         self
     }
 
+    /// Apply iSWAP gate.
+    #[inline]
+    pub fn iswap(mut self, q0: usize, q1: usize) -> Self {
+        self.push(Instruction::new(Gate::iswap(), std::vec![q0, q1]));
+        self
+    }
+
+    /// Apply √SWAP gate.
+    #[inline]
+    pub fn sqrt_swap(mut self, q0: usize, q1: usize) -> Self {
+        self.push(Instruction::new(Gate::sqrt_swap(), std::vec![q0, q1]));
+        self
+    }
+
+    /// Apply XX Ising coupling rotation.
+    #[inline]
+    pub fn rxx(mut self, theta: f64, q0: usize, q1: usize) -> Self {
+        self.push(Instruction::new(Gate::rxx(theta), std::vec![q0, q1]));
+        self
+    }
+
+    /// Apply YY Ising coupling rotation.
+    #[inline]
+    pub fn ryy(mut self, theta: f64, q0: usize, q1: usize) -> Self {
+        self.push(Instruction::new(Gate::ryy(theta), std::vec![q0, q1]));
+        self
+    }
+
+    /// Apply ZZ Ising coupling rotation.
+    #[inline]
+    pub fn rzz(mut self, theta: f64, q0: usize, q1: usize) -> Self {
+        self.push(Instruction::new(Gate::rzz(theta), std::vec![q0, q1]));
+        self
+    }
+
+    /// Apply ZZ Ising coupling rotation by a named parameter; see [`bind`](Self::bind).
+    #[inline]
+    pub fn rzz_param(mut self, name: impl Into<std::string::String>, q0: usize, q1: usize) -> Self {
+        self.push(Instruction::new(Gate::symbolic(GateType::Rzz, name), std::vec![q0, q1]));
+        self
+    }
+
+    /// Swap qubit `a[i]` with `b[i]` for every i.
+    ///
+    /// Appends one SWAP per pair; the simulator runs such a block of SWAPs
+    /// as a single permutation pass. `swap_register(&[0, 1], &[3, 2])`
+    /// reverses a 4-qubit register.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the lists differ in length, share or repeat a
+    /// qubit, or name a qubit outside the circuit.
+    pub fn swap_register(mut self, a: &[usize], b: &[usize]) -> Result<Self> {
+        if a.len() != b.len() {
+            return Err(HomayaError::QubitMismatch {
+                expected: a.len(),
+                got: b.len(),
+            });
+        }
+        // A barrier takes any number of operands, so this checks only range and repeats
+        let all: Vec<usize> = a.iter().chain(b).copied().collect();
+        self.check_operands(&Gate::barrier(), &all)?;
+
+        for (&q1, &q2) in a.iter().zip(b) {
+            self = self.swap(q1, q2);
+        }
+        Ok(self)
+    }
+
     // ========== Three-qubit gates ==========
 
     /// Apply Toffoli (CCX) gate.
@@ -322,6 +963,17 @@ impl Circuit {
         self
     }
 
+    /// Apply multi-controlled X: flip `target` when every control is |1⟩.
+    ///
+    /// With no controls this is X, with one CX and with two a Toffoli.
+    #[inline]
+    pub fn mcx(mut self, controls: &[usize], target: usize) -> Self {
+        let mut qubits = controls.to_vec();
+        qubits.push(target);
+        self.push(Instruction::new(Gate::mcx(), qubits));
+        self
+    }
+
     // ========== Special operations ==========
 
     /// Measure a qubit.
@@ -347,6 +999,19 @@ impl Circuit {
         self
     }
 
+    /// Measure all qubits into freshly allocated classical bits.
+    ///
+    /// Qubit `q` is measured into clbit `num_clbits() + q`, so earlier
+    /// measurement results are never overwritten.
+    pub fn measure_all_into_new_register(mut self) -> Self {
+        let offset = self.num_clbits;
+        self.num_clbits += self.num_qubits;
+        for q in 0..self.num_qubits {
+            self.push(Instruction::with_clbits(Gate::measure(), std::vec![q], std::vec![offset + q]));
+        }
+        self
+    }
+
     /// Reset a qubit to |0⟩.
     #[inline]
     pub fn reset(mut self, q: usize) -> Self {
@@ -354,12 +1019,49 @@ impl Circuit {
         self
     }
 
+    /// Apply Pauli-X to `q` only if classical bit `clbit` equals `value`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// // Active reset: flip the qubit back if it was measured as 1
+    /// let circuit = Circuit::new(1).h(0).measure(0, 0).x_if(0, 0, 1);
+    /// assert_eq!(circuit.instructions()[2].condition, Some((0, 1)));
+    /// ```
+    #[inline]
+    pub fn x_if(mut self, q: usize, clbit: usize, value: u8) -> Self {
+        self.push(Instruction::new(Gate::x(), std::vec![q]).conditioned(clbit, value));
+        self
+    }
+
+    /// Apply Pauli-Z to `q` only if classical bit `clbit` equals `value`.
+    #[inline]
+    pub fn z_if(mut self, q: usize, clbit: usize, value: u8) -> Self {
+        self.push(Instruction::new(Gate::z(), std::vec![q]).conditioned(clbit, value));
+        self
+    }
+
     /// Add a barrier (synchronization point).
     pub fn barrier(mut self, qubits: &[usize]) -> Self {
         self.push(Instruction::new(Gate::barrier(), qubits.to_vec()));
         self
     }
 
+    /// Record the simulator state under `label` at this point.
+    ///
+    /// Snapshots act on no qubits and do nothing to the state;
+    /// `Simulator::run_with_snapshots` in `homaya-sim` returns a copy of
+    /// the state as it was when each one was reached. Optimization passes
+    /// do not move gates across a snapshot.
+    pub fn snapshot(mut self, label: impl Into<std::string::String>) -> Self {
+        let mut inst = Instruction::new(Gate::snapshot(), Vec::new());
+        inst.label = Some(label.into());
+        self.push(inst);
+        self
+    }
+
     /// Add a barrier across all qubits.
     pub fn barrier_all(mut self) -> Self {
         let qubits: Vec<usize> = (0..self.num_qubits).collect();
@@ -383,6 +1085,125 @@ impl Circuit {
         Ok(self)
     }
 
+    /// Place another circuit alongside this one on fresh qubits (`self ⊗ other`).
+    ///
+    /// The result has `self.num_qubits() + other.num_qubits()` qubits and
+    /// the sum of both classical bit counts. This circuit's instructions are
+    /// copied unchanged; `other`'s follow with every qubit and classical bit
+    /// index (including those of conditions and named registers) shifted
+    /// past this circuit's. A barrier over all qubits of either circuit
+    /// stays limited to that circuit's qubits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let bell = Circuit::new(2).h(0).cx(0, 1).measure_all();
+    /// let pair = bell.tensor(&bell);
+    /// assert_eq!(pair.num_qubits(), 4);
+    /// assert_eq!(pair.num_clbits(), 4);
+    /// assert_eq!(pair.instructions()[5].qubits, vec![2, 3]);
+    /// ```
+    pub fn tensor(&self, other: &Circuit) -> Circuit {
+        let (qubit_offset, clbit_offset) = (self.num_qubits, self.num_clbits);
+
+        let explicit_barrier = |inst: &Instruction, range: core::ops::Range<usize>| {
+            let mut inst = inst.clone();
+            if inst.is_barrier() && inst.qubits.is_empty() {
+                inst.qubits = range.collect();
+            }
+            inst
+        };
+
+        let mut instructions: Vec<Instruction> = self
+            .instructions
+            .iter()
+            .map(|inst| explicit_barrier(inst, 0..qubit_offset))
+            .collect();
+        instructions.extend(other.instructions.iter().map(|inst| {
+            let mut inst = explicit_barrier(inst, 0..other.num_qubits);
+            inst.qubits.iter_mut().for_each(|q| *q += qubit_offset);
+            inst.clbits.iter_mut().for_each(|c| *c += clbit_offset);
+            if let Some((c, _)) = &mut inst.condition {
+                *c += clbit_offset;
+            }
+            inst
+        }));
+
+        let mut qregs = self.qregs.clone();
+        qregs.extend(other.qregs.iter().map(|reg| {
+            QuantumRegister::new(reg.name().into(), reg.range().start + qubit_offset, reg.len())
+        }));
+
+        Circuit {
+            num_qubits: self.num_qubits + other.num_qubits,
+            num_clbits: self.num_clbits + other.num_clbits,
+            instructions,
+            name: self.name.clone(),
+            qregs,
+        }
+    }
+
+    /// Relabel qubits: logical qubit `i` becomes qubit `mapping[i]`.
+    ///
+    /// Rewrites the qubits of every instruction, including explicit barrier
+    /// operands. Named registers describe the logical layout, which no
+    /// longer holds, so they are not carried over.
+    ///
+    /// # Errors
+    ///
+    /// The mapping must be a permutation of `0..num_qubits()`. Returns
+    /// `QubitMismatch` if it has the wrong length, `QubitOutOfRange` for an
+    /// entry past the last qubit and `DuplicateQubit` for a repeated entry.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let circuit = Circuit::new(3).h(0).cx(0, 2).remap_qubits(&[2, 0, 1]).unwrap();
+    /// assert_eq!(circuit.instructions()[1].qubits, vec![2, 1]);
+    /// ```
+    pub fn remap_qubits(&self, mapping: &[usize]) -> Result<Circuit> {
+        if mapping.len() != self.num_qubits {
+            return Err(HomayaError::QubitMismatch {
+                expected: self.num_qubits,
+                got: mapping.len(),
+            });
+        }
+        let mut seen = std::vec![false; self.num_qubits];
+        for &q in mapping {
+            match seen.get_mut(q) {
+                None => {
+                    return Err(HomayaError::QubitOutOfRange {
+                        qubit: q,
+                        max: self.num_qubits,
+                    })
+                }
+                Some(true) => return Err(HomayaError::DuplicateQubit { qubit: q }),
+                Some(slot) => *slot = true,
+            }
+        }
+
+        let instructions = self
+            .instructions
+            .iter()
+            .map(|inst| {
+                let mut inst = inst.clone();
+                for q in &mut inst.qubits {
+                    // Out-of-range operands are left for `validate` to report
+                    *q = mapping.get(*q).copied().unwrap_or(*q);
+                }
+                inst
+            })
+            .collect();
+
+        let mut circuit = self.with_instructions(instructions);
+        circuit.qregs.clear();
+        Ok(circuit)
+    }
+
     /// Repeat the circuit n times.
     pub fn repeat(self, n: usize) -> Self {
         let original = self.instructions.clone();
@@ -395,17 +1216,64 @@ impl Circuit {
         result
     }
 
+    /// Remove the measurements at the end of the circuit.
+    ///
+    /// Drops every measurement after the last instruction that is neither
+    /// a measurement nor a barrier, leaving the unitary part of a circuit
+    /// that ends in `measure_all`. Mid-circuit measurements, barriers and
+    /// the classical bits are kept.
+    pub fn remove_final_measurements(self) -> Self {
+        let split = self
+            .instructions
+            .iter()
+            .rposition(|inst| !matches!(inst.gate.gate_type, GateType::Measure | GateType::Barrier))
+            .map_or(0, |i| i + 1);
+
+        let (body, tail) = self.instructions.split_at(split);
+        let instructions = body
+            .iter()
+            .chain(tail.iter().filter(|inst| inst.gate.gate_type != GateType::Measure))
+            .cloned()
+            .collect();
+        self.with_instructions(instructions)
+    }
+
     /// Get the inverse of this circuit.
+    ///
+    /// Reverses the instruction order and replaces each gate with its
+    /// [`dagger`](Gate::dagger). `ISwap` and `SqrtSwap`, whose inverses are
+    /// not single gates, expand to `ISwap, Z, Z` and `SqrtSwap, Swap`.
+    /// Measurements, resets and barriers are kept as they are.
     pub fn inverse(self) -> Self {
-        let mut result = Circuit::new(self.num_qubits);
-        result.num_clbits = self.num_clbits;
-
-        // Reverse order and invert each gate
-        for inst in self.instructions.into_iter().rev() {
-            // For now, just reverse (TODO: proper gate inversion)
-            result.push(inst);
+        let mut instructions = Vec::with_capacity(self.instructions.len());
+
+        for inst in self.instructions.iter().rev() {
+            // Extra parts keep the condition and label of the gate they invert
+            let part = |gate: Gate, qubits: Vec<usize>| Instruction {
+                gate,
+                qubits,
+                ..inst.clone()
+            };
+            match inst.gate.gate_type {
+                // iSWAP† = iSWAP · (Z ⊗ Z), and the two commute
+                GateType::ISwap => {
+                    instructions.push(inst.clone());
+                    instructions.push(part(Gate::z(), std::vec![inst.qubits[0]]));
+                    instructions.push(part(Gate::z(), std::vec![inst.qubits[1]]));
+                }
+                // √SWAP† = √SWAP³ = SWAP · √SWAP
+                GateType::SqrtSwap => {
+                    instructions.push(inst.clone());
+                    instructions.push(part(Gate::swap(), inst.qubits.clone()));
+                }
+                _ => instructions.push(Instruction {
+                    gate: inst.gate.dagger(),
+                    ..inst.clone()
+                }),
+            }
         }
-        result
+
+        self.with_instructions(instructions)
     }
 }
 
@@ -445,10 +1313,93 @@ mod tests {
             .h(2)
             .h(3);
 
-        // Each H is on a different qubit, but our simple depth calc
-        // counts sequential instructions. For proper parallel depth,
-        // we'd need a more sophisticated algorithm.
         assert_eq!(circuit.len(), 4);
+        assert_eq!(circuit.depth(), 1);
+    }
+
+    #[test]
+    fn test_barrier_synchronizes_depth() {
+        assert_eq!(Circuit::new(1).h(0).barrier_all().h(0).depth(), 2);
+
+        // Qubit 0 is already the deepest, so the barrier changes nothing
+        assert_eq!(Circuit::new(2).h(0).h(1).barrier_all().h(0).depth(), 2);
+
+        // H(1) can no longer run alongside the first H(0)
+        let fenced = Circuit::new(2).h(0).h(0).barrier_all().h(1);
+        assert_eq!(Circuit::new(2).h(0).h(0).h(1).depth(), 2);
+        assert_eq!(fenced.depth(), 3);
+        assert_eq!(fenced.critical_path(), vec![0, 1, 3]);
+        assert_eq!(fenced.layers().len(), 3);
+
+        // A partial barrier only synchronizes its own qubits
+        let partial = Circuit::new(3).h(0).h(0).barrier(&[0, 1]).h(1).h(2);
+        assert_eq!(partial.depth(), 3);
+        assert_eq!(partial.layers()[0].len(), 2);
+        assert_eq!(partial.depth_by_type(&[GateType::H]), 3);
+    }
+
+    #[test]
+    fn test_validate_all() {
+        assert!(Circuit::new(2).h(0).rx(0.3, 1).cx(0, 1).measure_all().validate_all().is_ok());
+
+        let err = Circuit::new(1).rx(f64::INFINITY, 0).validate_all().unwrap_err();
+        assert!(matches!(err, HomayaError::InvalidGateParams { gate: "Rx", .. }));
+        assert!(err.to_string().contains("must be finite"));
+
+        assert!(matches!(
+            Circuit::new(2).h(5).validate_all(),
+            Err(HomayaError::QubitOutOfRange { qubit: 5, .. })
+        ));
+    }
+
+    #[test]
+    fn test_swap_register() {
+        let circuit = Circuit::new(4).swap_register(&[0, 1], &[3, 2]).unwrap();
+        assert_eq!(circuit.count_gates().get(&GateType::Swap), Some(&2));
+        assert_eq!(circuit.instructions()[1].qubits, vec![1, 2]);
+
+        assert!(matches!(
+            Circuit::new(4).swap_register(&[0, 1], &[2]),
+            Err(HomayaError::QubitMismatch { expected: 2, got: 1 })
+        ));
+        assert!(matches!(
+            Circuit::new(4).swap_register(&[0, 1], &[1, 2]),
+            Err(HomayaError::DuplicateQubit { qubit: 1 })
+        ));
+        assert!(matches!(
+            Circuit::new(4).swap_register(&[0], &[4]),
+            Err(HomayaError::QubitOutOfRange { qubit: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn test_validate_all_rejects_repeated_qubits() {
+        for (circuit, qubit) in [
+            (Circuit::new(3).ccx(0, 1, 1), 1),
+            (Circuit::new(3).swap(2, 2), 2),
+            (Circuit::new(3).h(1).cx(0, 0), 0),
+        ] {
+            assert!(matches!(circuit.validate_all(), Err(HomayaError::DuplicateQubit { qubit: q }) if q == qubit));
+        }
+
+        let distinct = Circuit::new(3).ccx(0, 1, 2).swap(1, 2).cx(0, 1);
+        assert!(distinct.validate_all().is_ok());
+    }
+
+    #[test]
+    fn test_critical_path() {
+        let ghz = Circuit::new(3).h(0).cx(0, 1).cx(1, 2);
+        assert_eq!(ghz.critical_path(), vec![0, 1, 2]);
+
+        let parallel = Circuit::new(4).h(0).h(1).h(2).h(3);
+        assert_eq!(parallel.critical_path().len(), 1);
+
+        // The X on qubit 2 is off the longest chain
+        let circuit = Circuit::new(3).h(0).x(2).cx(0, 1).barrier(&[]).z(1);
+        assert_eq!(circuit.critical_path(), vec![0, 2, 4]);
+        assert_eq!(circuit.critical_path().len(), circuit.depth());
+
+        assert!(Circuit::new(2).critical_path().is_empty());
     }
 
     #[test]
@@ -459,6 +1410,23 @@ mod tests {
         assert_eq!(circuit.len(), 4); // 1 H + 3 measures
     }
 
+    #[test]
+    fn test_measure_all_into_new_register() {
+        let circuit = Circuit::new(2)
+            .measure(0, 0)
+            .measure_all_into_new_register()
+            .h(0)
+            .measure_all_into_new_register();
+
+        assert_eq!(circuit.num_clbits(), 1 + 2 + 2);
+        let clbits: Vec<usize> = circuit
+            .instructions()
+            .iter()
+            .flat_map(|inst| inst.clbits.iter().copied())
+            .collect();
+        assert_eq!(clbits, vec![0, 1, 2, 3, 4]);
+    }
+
     #[test]
     fn test_gate_count() {
         let circuit = Circuit::new(2)
@@ -471,4 +1439,396 @@ mod tests {
         assert_eq!(counts.get(&GateType::H), Some(&3));
         assert_eq!(counts.get(&GateType::CX), Some(&1));
     }
+
+    #[test]
+    fn test_bind_parameters() {
+        let ansatz = Circuit::new(2)
+            .ry_param("theta", 0)
+            .cx(0, 1)
+            .rz_param("phi", 1)
+            .rx_param("theta", 1)
+            .measure_all();
+        assert_eq!(ansatz.parameters(), ["theta", "phi"]);
+        assert_eq!(
+            ansatz.validate(),
+            Err(HomayaError::UnboundParameter { name: "theta".into() })
+        );
+
+        let values = std::collections::HashMap::from([("theta".to_string(), 0.5), ("phi".to_string(), -1.25)]);
+        let bound = ansatz.bind(&values).unwrap();
+        assert!(bound.parameters().is_empty());
+        assert!(bound.validate().is_ok());
+        let expected = Circuit::new(2).ry(0.5, 0).cx(0, 1).rz(-1.25, 1).rx(0.5, 1).measure_all();
+        assert_eq!(bound.instructions(), expected.instructions());
+        assert_eq!(bound.num_clbits(), 2);
+
+        let partial = std::collections::HashMap::from([("theta".to_string(), 0.5)]);
+        assert_eq!(
+            ansatz.bind(&partial).unwrap_err(),
+            HomayaError::UnboundParameter { name: "phi".into() }
+        );
+    }
+
+    #[test]
+    fn test_inverse_negates_symbolic_angles() {
+        let ansatz = Circuit::new(2).rx_param("a", 0).rzz_param("b", 0, 1).p_param("a", 1);
+        let values = std::collections::HashMap::from([("a".to_string(), 0.3), ("b".to_string(), 0.7)]);
+
+        let inverse_then_bind = ansatz.clone().inverse().bind(&values).unwrap();
+        let bind_then_inverse = ansatz.bind(&values).unwrap().inverse();
+        assert_eq!(inverse_then_bind.instructions(), bind_then_inverse.instructions());
+
+        // A symbolic rotation cancels against its own inverse
+        let rotations = Circuit::new(2).rx_param("a", 0).p_param("b", 1);
+        let round_trip = rotations.clone().compose(&rotations.inverse()).unwrap().cancel_commuting();
+        assert!(round_trip.is_empty());
+    }
+
+    #[test]
+    fn test_conditioned_instructions() {
+        let circuit = Circuit::new(2).h(0).measure(0, 0).x_if(1, 0, 1).z_if(1, 0, 0);
+        assert_eq!(circuit.instructions()[2].condition, Some((0, 1)));
+        assert_eq!(circuit.instructions()[3].condition, Some((0, 0)));
+        assert_eq!(circuit.instructions()[0].condition, None);
+        assert!(circuit.validate().is_ok());
+
+        // The condition's bit must exist
+        assert_eq!(
+            Circuit::new(1).x_if(0, 2, 1).validate(),
+            Err(HomayaError::ClbitOutOfRange { clbit: 2, max: 0 })
+        );
+
+        // Conditioned gates only cancel against the same condition
+        let circuit = Circuit::new(1).measure(0, 0).x(0).x_if(0, 0, 1).cancel_commuting();
+        assert_eq!(circuit.len(), 3);
+        let circuit = Circuit::with_clbits(2, 1).x_if(1, 0, 1).x_if(1, 0, 1).cancel_commuting();
+        assert!(circuit.is_empty());
+
+        // ...and never move past the measurement that sets their bit
+        let circuit = Circuit::new(2).x_if(1, 0, 1).measure(0, 0).x_if(1, 0, 1).cancel_commuting();
+        assert_eq!(circuit.len(), 3);
+    }
+
+    #[test]
+    fn test_layers() {
+        let parallel = Circuit::new(4).h(0).h(1).h(2).h(3);
+        let layers = parallel.layers();
+        assert_eq!(layers.len(), 1);
+        assert_eq!(layers[0].len(), 4);
+
+        let chain = Circuit::new(4).cx(0, 1).cx(1, 2).cx(2, 3);
+        let layers = chain.layers();
+        assert_eq!(layers.len(), 3);
+        assert!(layers.iter().all(|layer| layer.len() == 1));
+        assert_eq!(layers[2][0].qubits, vec![2, 3]);
+        assert_eq!(chain.layers().len(), chain.depth());
+    }
+
+    #[test]
+    fn test_layers_respect_barriers_and_conditions() {
+        // Without the barrier H(1) would share the first layer with H(0)
+        let fenced = Circuit::new(2).h(0).barrier_all().h(1);
+        let layers = fenced.layers();
+        assert_eq!(layers.len(), 2);
+        assert_eq!(layers[1][0].qubits, vec![1]);
+
+        // A barrier on other qubits does not delay H(1)
+        assert_eq!(Circuit::new(3).h(0).barrier(&[0, 2]).h(1).layers().len(), 1);
+
+        // The conditioned X waits for the measurement of its bit
+        let conditioned = Circuit::new(2).h(0).measure(0, 0).x_if(1, 0, 1);
+        let layers = conditioned.layers();
+        assert_eq!(layers.len(), 3);
+        assert_eq!(layers[2][0].condition, Some((0, 1)));
+
+        // Readers of the same bit share a layer; a later write waits for them
+        let readers = Circuit::new(3).measure(0, 0).x_if(1, 0, 1).z_if(2, 0, 1).measure(0, 0);
+        let layers = readers.layers();
+        assert_eq!(layers.iter().map(Vec::len).collect::<Vec<_>>(), vec![1, 2, 1]);
+        assert_eq!(layers[1][0].condition, Some((0, 1)));
+        assert_eq!(layers[1][1].condition, Some((0, 1)));
+        let overwrite = Circuit::new(3).measure(0, 0).x_if(1, 0, 1).measure(2, 0);
+        assert_eq!(overwrite.layers().len(), 3);
+    }
+
+    #[test]
+    fn test_tensor_shifts_other_circuit() {
+        let left = Circuit::new(2).h(0).cx(0, 1).measure(1, 0);
+        let mut right = Circuit::new(0);
+        right.add_qreg("anc", 2);
+        let right = right.measure(0, 0).x_if(1, 0, 1).barrier(&[]);
+
+        let circuit = left.tensor(&right);
+        assert_eq!(circuit.num_qubits(), 4);
+        assert_eq!(circuit.num_clbits(), 2);
+        assert!(circuit.validate().is_ok());
+
+        let insts = circuit.instructions();
+        assert_eq!(&insts[..3], left.instructions());
+        assert_eq!((insts[3].qubits.clone(), insts[3].clbits.clone()), (vec![2], vec![1]));
+        assert_eq!(insts[4].qubits, vec![3]);
+        assert_eq!(insts[4].condition, Some((1, 1)));
+        // The all-qubit barrier stays on the right-hand qubits
+        assert_eq!(insts[5].qubits, vec![2, 3]);
+        assert_eq!(circuit.qregs()[0].range(), 2..4);
+    }
+
+    #[test]
+    fn test_remap_qubits_rejects_bad_mappings() {
+        let circuit = Circuit::new(3).h(0).cx(0, 1).barrier(&[1, 2]);
+        let remapped = circuit.remap_qubits(&[1, 2, 0]).unwrap();
+        assert_eq!(remapped.instructions()[1].qubits, vec![1, 2]);
+        assert_eq!(remapped.instructions()[2].qubits, vec![2, 0]);
+        assert_eq!(circuit.remap_qubits(&[0, 1, 2]).unwrap().instructions(), circuit.instructions());
+
+        assert_eq!(
+            circuit.remap_qubits(&[1, 0]).unwrap_err(),
+            HomayaError::QubitMismatch { expected: 3, got: 2 }
+        );
+        assert_eq!(
+            circuit.remap_qubits(&[0, 1, 3]).unwrap_err(),
+            HomayaError::QubitOutOfRange { qubit: 3, max: 3 }
+        );
+        assert_eq!(
+            circuit.remap_qubits(&[0, 1, 1]).unwrap_err(),
+            HomayaError::DuplicateQubit { qubit: 1 }
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range_qubit() {
+        assert!(Circuit::new(2).h(0).cx(0, 1).measure_all().validate().is_ok());
+        assert_eq!(
+            Circuit::new(2).h(5).validate(),
+            Err(HomayaError::QubitOutOfRange { qubit: 5, max: 2 })
+        );
+        assert_eq!(
+            Circuit::new(3).ccx(0, 1, 3).validate(),
+            Err(HomayaError::QubitOutOfRange { qubit: 3, max: 3 })
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_duplicate_qubit() {
+        assert_eq!(Circuit::new(2).cx(1, 1).validate(), Err(HomayaError::DuplicateQubit { qubit: 1 }));
+        assert_eq!(Circuit::new(4).mcx(&[0, 2, 0], 3).validate(), Err(HomayaError::DuplicateQubit { qubit: 0 }));
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range_clbit() {
+        let mut circuit = Circuit::new(2);
+        circuit.push(Instruction::with_clbits(Gate::measure(), std::vec![0], std::vec![4]));
+        assert!(matches!(circuit.validate(), Err(HomayaError::ClbitOutOfRange { clbit: 4, .. })));
+    }
+
+    #[test]
+    fn test_remove_final_measurements() {
+        let circuit = Circuit::new(2).h(0).measure(0, 0).x(1).barrier_all().measure_all();
+        let types: Vec<GateType> = circuit
+            .remove_final_measurements()
+            .instructions()
+            .iter()
+            .map(|inst| inst.gate.gate_type)
+            .collect();
+        assert_eq!(types, std::vec![GateType::H, GateType::Measure, GateType::X, GateType::Barrier]);
+
+        assert!(Circuit::with_clbits(1, 1).measure(0, 0).remove_final_measurements().is_empty());
+    }
+
+    #[test]
+    fn test_inverse_daggers_each_gate() {
+        let circuit = Circuit::new(2).h(0).s(0).rx(0.5, 1).cx(0, 1);
+        let inverse = circuit.inverse();
+        let gates: Vec<Gate> = inverse.instructions().iter().map(|inst| inst.gate.clone()).collect();
+        assert_eq!(gates, std::vec![Gate::cx(), Gate::rx(-0.5), Gate::sdg(), Gate::h()]);
+        assert_eq!(inverse.instructions()[1].qubits, std::vec![1]);
+    }
+
+    #[test]
+    fn test_inverse_expands_iswap() {
+        let mut circuit = Circuit::new(2);
+        circuit.push(Instruction::new(
+            Gate { gate_type: GateType::ISwap, params: crate::GateParams::None },
+            std::vec![0, 1],
+        ));
+        let types: Vec<GateType> = circuit.inverse().instructions().iter().map(|inst| inst.gate.gate_type).collect();
+        assert_eq!(types, std::vec![GateType::ISwap, GateType::Z, GateType::Z]);
+    }
+
+    #[test]
+    fn test_inverse_keeps_condition_on_expanded_parts() {
+        let mut circuit = Circuit::with_clbits(2, 1);
+        circuit.push(Instruction::new(Gate::iswap(), std::vec![0, 1]).conditioned(0, 1));
+        circuit.push(Instruction::new(Gate::sqrt_swap(), std::vec![1, 0]).conditioned(0, 0));
+
+        let inverse = circuit.inverse();
+        let conditions: Vec<Option<(usize, u8)>> = inverse.instructions().iter().map(|inst| inst.condition).collect();
+        assert_eq!(conditions, std::vec![Some((0, 0)), Some((0, 0)), Some((0, 1)), Some((0, 1)), Some((0, 1))]);
+    }
+
+    #[test]
+    fn test_qregs_map_to_disjoint_ranges() {
+        let mut circuit = Circuit::new(0);
+        let a = circuit.add_qreg("a", 2);
+        let b = circuit.add_qreg("b", 3);
+
+        assert_eq!(a.range(), 0..2);
+        assert_eq!(b.range(), 2..5);
+        assert_eq!(circuit.num_qubits(), 5);
+        assert_eq!(circuit.qregs().len(), 2);
+        assert_eq!(circuit.qregs()[1].name(), "b");
+
+        let circuit = circuit.h(a[1]).cx(a[0], b[1]).x(b[2]);
+        let targets: Vec<&[usize]> = circuit.instructions().iter().map(|i| i.qubits.as_slice()).collect();
+        assert_eq!(targets, [&[1][..], &[0, 3][..], &[4][..]]);
+    }
+
+    #[test]
+    fn test_fit_to_backend() {
+        let small = Circuit::new(2).h(0).cx(0, 1);
+        assert!(small.is_valid_for(5));
+
+        let fitted = small.fit_to(5).unwrap();
+        assert_eq!(fitted.num_qubits(), 5);
+        assert_eq!(fitted.instructions(), small.instructions());
+
+        let large = Circuit::new(6);
+        assert!(!large.is_valid_for(5));
+        assert_eq!(
+            large.fit_to(5).unwrap_err(),
+            HomayaError::CircuitTooLarge { qubits: 6, max: 5 }
+        );
+    }
+
+    #[test]
+    fn test_depth_by_type() {
+        let circuit = Circuit::new(2).h(0).cx(0, 1).h(0).cx(0, 1);
+
+        assert_eq!(circuit.depth(), 4);
+        assert_eq!(circuit.depth_by_type(&[GateType::CX]), 2);
+        assert_eq!(circuit.depth_by_type(&[GateType::H]), 2);
+        assert_eq!(circuit.depth_by_type(&[]), 0);
+    }
+
+    #[test]
+    fn test_two_qubit_cost() {
+        let circuit = Circuit::new(4)
+            .h(0)
+            .h(1)
+            .cx(0, 1)
+            .rz(0.3, 1)
+            .cz(2, 3)
+            .barrier(&[])
+            .swap(1, 2)
+            .t(2)
+            .ccx(0, 1, 3)
+            .measure_all();
+
+        assert_eq!(circuit.two_qubit_gate_count(), 4);
+        // CX and CZ run side by side, then SWAP, then the Toffoli
+        assert_eq!(circuit.two_qubit_depth(), 3);
+        assert!(circuit.depth() > circuit.two_qubit_depth());
+
+        let single = Circuit::new(2).h(0).x(1).rz(0.1, 0);
+        assert_eq!(single.two_qubit_gate_count(), 0);
+        assert_eq!(single.two_qubit_depth(), 0);
+    }
+
+    #[test]
+    fn test_insert_at() {
+        let mut circuit = Circuit::new(2).h(0).cx(0, 1);
+        circuit.insert_at(1, Gate::barrier(), std::vec![0, 1]).unwrap();
+
+        let types: Vec<GateType> = circuit.instructions().iter().map(|i| i.gate.gate_type).collect();
+        assert_eq!(types, [GateType::H, GateType::Barrier, GateType::CX]);
+
+        circuit.insert_at(3, Gate::x(), std::vec![1]).unwrap();
+        assert_eq!(circuit.len(), 4);
+        assert_eq!(circuit.instructions()[3].gate.gate_type, GateType::X);
+    }
+
+    #[test]
+    fn test_insert_at_rejects_invalid() {
+        let mut circuit = Circuit::new(2).h(0);
+
+        assert_eq!(
+            circuit.insert_at(5, Gate::x(), std::vec![0]),
+            Err(HomayaError::InstructionOutOfRange { index: 5, len: 1 })
+        );
+        assert_eq!(
+            circuit.insert_at(0, Gate::x(), std::vec![2]),
+            Err(HomayaError::QubitOutOfRange { qubit: 2, max: 2 })
+        );
+        assert_eq!(
+            circuit.insert_at(0, Gate::cx(), std::vec![1, 1]),
+            Err(HomayaError::DuplicateQubit { qubit: 1 })
+        );
+        assert_eq!(
+            circuit.insert_at(0, Gate::cx(), std::vec![1]),
+            Err(HomayaError::QubitMismatch { expected: 2, got: 1 })
+        );
+        assert_eq!(circuit.len(), 1);
+    }
+
+    #[test]
+    fn test_is_barrier() {
+        let circuit = Circuit::new(1).h(0).barrier(&[0]).h(0);
+        let flags: Vec<bool> = circuit.instructions().iter().map(|i| i.is_barrier()).collect();
+        assert_eq!(flags, [false, true, false]);
+    }
+
+    #[test]
+    fn test_snapshot_is_a_labeled_marker() {
+        let circuit = Circuit::new(2).h(0).snapshot("mid").cx(0, 1);
+        let snapshot = &circuit.instructions()[1];
+        assert_eq!(snapshot.gate.gate_type, GateType::Snapshot);
+        assert_eq!(snapshot.label.as_deref(), Some("mid"));
+        assert!(snapshot.qubits.is_empty());
+        assert!(!snapshot.gate.is_unitary());
+
+        assert_eq!(circuit.depth(), 2);
+        assert!(circuit.validate().is_ok());
+        assert!(circuit.to_qasm().contains("// snapshot mid"));
+    }
+
+    #[test]
+    fn test_snapshot_fences_every_qubit() {
+        let circuit = Circuit::new(2).h(0).cx(0, 1).h(0).snapshot("end");
+        let layers = circuit.layers();
+        assert_eq!(layers.len(), 3);
+        let last = layers.last().unwrap();
+        assert_eq!(last.last().unwrap().gate.gate_type, GateType::Snapshot);
+
+        // Gates after a snapshot wait for every qubit, as after barrier_all
+        let circuit = Circuit::new(2).h(0).h(0).snapshot("mid").h(1);
+        assert_eq!(circuit.depth(), 3);
+        assert_eq!(circuit.layers().len(), 3);
+        assert_eq!(circuit.critical_path(), std::vec![0, 1, 3]);
+    }
+
+    #[test]
+    fn test_to_dot_bell() {
+        let dot = Circuit::new(2).h(0).cx(0, 1).to_dot();
+
+        assert!(dot.starts_with("digraph circuit {"));
+        assert!(dot.trim_end().ends_with('}'));
+        assert_eq!(dot.matches("[label=").count(), 2);
+        assert!(dot.contains("n0 [label=\"H q0\"]"));
+        assert!(dot.contains("n1 [label=\"CX q0,q1\"]"));
+        assert!(dot.contains("n0 -> n1;"));
+        assert_eq!(dot.matches("->").count(), 1);
+    }
+
+    #[test]
+    fn test_to_dot_labels_angles() {
+        let dot = Circuit::new(1).rz(crate::PI / 2.0, 0).to_dot();
+        assert!(dot.contains("n0 [label=\"Rz(π/2) q0\"]"));
+    }
+
+    #[test]
+    fn test_to_dot_parallel_gates_have_no_edges() {
+        let dot = Circuit::new(3).h(0).h(1).h(2).to_dot();
+        assert_eq!(dot.matches("[label=").count(), 3);
+        assert!(!dot.contains("->"));
+    }
 }
@@ -0,0 +1,169 @@
+//! Commutation analysis for instructions.
+//!
+//! Knowing when two instructions commute lets optimization passes reorder
+//! gates and look past them for cancellations.
+//!
+//! The analysis is conservative: `true` means the instructions definitely
+//! commute, `false` means they may not.
+
+use crate::{Circuit, GateType, Instruction};
+
+/// How a gate acts on one of its qubits.
+///
+/// A gate has role `Z` on a qubit if it commutes with Pauli-Z there
+/// (diagonal gates, controls), `X` if it commutes with Pauli-X (X, Rx,
+/// CNOT targets), and so on. Two gates sharing a qubit commute when they
+/// have the same Pauli role on every shared qubit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Role {
+    /// Acts trivially on the qubit
+    Identity,
+    /// Commutes with Pauli-X on the qubit
+    X,
+    /// Commutes with Pauli-Y on the qubit
+    Y,
+    /// Commutes with Pauli-Z on the qubit
+    Z,
+    /// No useful structure
+    Other,
+}
+
+/// Role of the instruction on its `pos`-th qubit operand.
+fn role(inst: &Instruction, pos: usize) -> Role {
+    use GateType::*;
+
+    match inst.gate.gate_type {
+        I => Role::Identity,
+        Z | S | Sdg | T | Tdg | Rz | P | CZ | CP | Rzz => Role::Z,
+        X | Rx | Rxx => Role::X,
+        Y | Ry | Ryy => Role::Y,
+        CX | CCX | MCX if pos + 1 == inst.qubits.len() => Role::X,
+        CY if pos == 1 => Role::Y,
+        CX | CY | CH | CU | CCX | CSwap if pos == 0 => Role::Z,
+        // Second Toffoli control, and any MCX control
+        CCX if pos == 1 => Role::Z,
+        MCX => Role::Z,
+        _ => Role::Other,
+    }
+}
+
+/// Check whether two instructions commute.
+///
+/// Rules, in order:
+/// - a gate conditioned on a classical bit never commutes with an
+///   instruction that writes that bit;
+/// - instructions on disjoint qubits commute;
+/// - barriers, measurements and resets never commute with anything they share a qubit with;
+/// - identical instructions commute;
+/// - otherwise they commute if, on every shared qubit, both act with the same
+///   Pauli role (e.g. two diagonal gates, CNOTs sharing a control or a target).
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_core::{commute::instructions_commute, Gate, Instruction};
+///
+/// let cx = Instruction::new(Gate::cx(), vec![0, 1]);
+/// let z = Instruction::new(Gate::z(), vec![0]);
+/// assert!(instructions_commute(&cx, &z));
+/// ```
+pub fn instructions_commute(a: &Instruction, b: &Instruction) -> bool {
+    let shared: Vec<(usize, usize)> = a
+        .qubits
+        .iter()
+        .enumerate()
+        .filter_map(|(i, q)| b.qubits.iter().position(|r| r == q).map(|j| (i, j)))
+        .collect();
+
+    // An empty barrier spans every qubit, and a snapshot observes them all
+    let spans_all = |inst: &Instruction| {
+        inst.qubits.is_empty() && matches!(inst.gate.gate_type, GateType::Barrier | GateType::Snapshot)
+    };
+
+    // A condition must stay after the measurement that sets its bit
+    let reads_write = |x: &Instruction, y: &Instruction| x.condition.is_some_and(|(c, _)| y.clbits.contains(&c));
+    if reads_write(a, b) || reads_write(b, a) {
+        return false;
+    }
+
+    if shared.is_empty() && !spans_all(a) && !spans_all(b) {
+        return true;
+    }
+
+    if !a.gate.is_unitary() || !b.gate.is_unitary() {
+        return false;
+    }
+
+    if a == b {
+        return true;
+    }
+
+    shared.iter().all(|&(i, j)| match (role(a, i), role(b, j)) {
+        (Role::Identity, _) | (_, Role::Identity) => true,
+        (Role::Other, _) | (_, Role::Other) => false,
+        (ra, rb) => ra == rb,
+    })
+}
+
+impl Circuit {
+    /// Check whether the instructions at indices `a` and `b` commute.
+    ///
+    /// See [`instructions_commute`] for the rules used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn commute(&self, a: usize, b: usize) -> bool {
+        instructions_commute(&self.instructions()[a], &self.instructions()[b])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Gate;
+
+    fn inst(gate: Gate, qubits: &[usize]) -> Instruction {
+        Instruction::new(gate, qubits.to_vec())
+    }
+
+    #[test]
+    fn test_rz_rz_same_qubit() {
+        assert!(instructions_commute(&inst(Gate::rz(0.3), &[0]), &inst(Gate::rz(1.2), &[0])));
+    }
+
+    #[test]
+    fn test_cx_with_z_on_control() {
+        let cx = inst(Gate::cx(), &[0, 1]);
+        assert!(instructions_commute(&cx, &inst(Gate::z(), &[0])));
+        assert!(!instructions_commute(&cx, &inst(Gate::z(), &[1])));
+        assert!(instructions_commute(&cx, &inst(Gate::x(), &[1])));
+    }
+
+    #[test]
+    fn test_x_and_z_do_not_commute() {
+        assert!(!instructions_commute(&inst(Gate::x(), &[0]), &inst(Gate::z(), &[0])));
+    }
+
+    #[test]
+    fn test_cx_sharing_control_or_target() {
+        let cx01 = inst(Gate::cx(), &[0, 1]);
+        assert!(instructions_commute(&cx01, &inst(Gate::cx(), &[0, 2])));
+        assert!(instructions_commute(&cx01, &inst(Gate::cx(), &[2, 1])));
+        assert!(!instructions_commute(&cx01, &inst(Gate::cx(), &[1, 2])));
+    }
+
+    #[test]
+    fn test_disjoint_and_non_unitary() {
+        assert!(instructions_commute(&inst(Gate::h(), &[0]), &inst(Gate::x(), &[1])));
+        assert!(!instructions_commute(&inst(Gate::measure(), &[0]), &inst(Gate::z(), &[0])));
+        assert!(!instructions_commute(&inst(Gate::barrier(), &[]), &inst(Gate::z(), &[3])));
+    }
+
+    #[test]
+    fn test_circuit_commute_by_index() {
+        let circuit = Circuit::new(2).rz(0.1, 0).cx(0, 1).h(0);
+        assert!(circuit.commute(0, 1));
+        assert!(!circuit.commute(1, 2));
+    }
+}
@@ -2,7 +2,8 @@
 //!
 //! Optimized for quantum state vector manipulation.
 
-use core::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub};
+use core::fmt;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub};
 
 /// A complex number with 64-bit floating point components.
 ///
@@ -52,6 +53,14 @@ impl Complex {
         }
     }
 
+    /// Convert to polar coordinates `(magnitude, phase)`.
+    ///
+    /// The inverse of [`from_polar`](Self::from_polar); the phase lies in (-π, π].
+    #[inline]
+    pub fn to_polar(self) -> (f64, f64) {
+        (self.abs(), self.arg())
+    }
+
     /// Compute the complex conjugate.
     #[inline(always)]
     pub const fn conj(self) -> Self {
@@ -91,6 +100,66 @@ impl Complex {
         }
     }
 
+    /// Compute the principal natural logarithm.
+    ///
+    /// The imaginary part lies in (-π, π]. `ln(0)` has a real part of
+    /// negative infinity.
+    #[inline]
+    pub fn ln(self) -> Self {
+        Self {
+            re: self.abs().ln(),
+            im: self.arg(),
+        }
+    }
+
+    /// Raise to a complex power, using the principal branch: e^(exp · ln self).
+    #[inline]
+    pub fn powc(self, exp: Self) -> Self {
+        (self.ln() * exp).exp()
+    }
+
+    /// Raise to a real power, using the principal branch.
+    #[inline]
+    pub fn powf(self, n: f64) -> Self {
+        Self::from_polar(self.abs().powf(n), self.arg() * n)
+    }
+
+    /// Compute the principal square root.
+    ///
+    /// The result has a non-negative real part. On the negative real axis
+    /// the sign of the imaginary part follows the sign of `self.im`, so
+    /// `sqrt(-1 + 0i) = i`.
+    #[inline]
+    pub fn sqrt(self) -> Self {
+        if self == Self::ZERO {
+            return Self::ZERO;
+        }
+
+        // Avoids the cancellation in from_polar(√|z|, arg/2) near the real axis
+        let t = ((self.abs() + self.re.abs()) / 2.0).sqrt();
+        if self.re >= 0.0 {
+            Self {
+                re: t,
+                im: self.im / (2.0 * t),
+            }
+        } else {
+            Self {
+                re: self.im.abs() / (2.0 * t),
+                im: t.copysign(self.im),
+            }
+        }
+    }
+
+    /// Compute the multiplicative inverse 1/self.
+    #[inline]
+    pub fn recip(self) -> Self {
+        let denom = self.norm_sqr();
+        Self {
+            re: self.re / denom,
+            im: -self.im / denom,
+        }
+    }
+
     /// Check if this is approximately zero.
     #[inline]
     pub fn is_zero(self, epsilon: f64) -> bool {
@@ -111,6 +180,16 @@ impl Default for Complex {
     }
 }
 
+impl fmt::Display for Complex {
+    /// Formats as `a+bi` or `a-bi`, with 4 decimal places unless a
+    /// precision is given.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(4);
+        let sign = if self.im.is_sign_negative() { '-' } else { '+' };
+        write!(f, "{:.*}{}{:.*}i", precision, self.re, sign, precision, self.im.abs())
+    }
+}
+
 impl From<f64> for Complex {
     #[inline(always)]
     fn from(re: f64) -> Self {
@@ -191,6 +270,15 @@ impl Mul<f64> for Complex {
     }
 }
 
+impl Mul<Complex> for f64 {
+    type Output = Complex;
+
+    #[inline(always)]
+    fn mul(self, rhs: Complex) -> Complex {
+        rhs * self
+    }
+}
+
 impl Div for Complex {
     type Output = Self;
 
@@ -204,6 +292,36 @@ impl Div for Complex {
     }
 }
 
+impl Div<f64> for Complex {
+    type Output = Self;
+
+    #[inline(always)]
+    fn div(self, rhs: f64) -> Self {
+        Self {
+            re: self.re / rhs,
+            im: self.im / rhs,
+        }
+    }
+}
+
+impl DivAssign<f64> for Complex {
+    #[inline(always)]
+    fn div_assign(&mut self, rhs: f64) {
+        *self = *self / rhs;
+    }
+}
+
+impl Div<Complex> for f64 {
+    type Output = Complex;
+
+    // Scaling the reciprocal is the intended definition, not a typo
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    #[inline(always)]
+    fn div(self, rhs: Complex) -> Complex {
+        self * rhs.recip()
+    }
+}
+
 impl Neg for Complex {
     type Output = Self;
 
@@ -216,6 +334,22 @@ impl Neg for Complex {
     }
 }
 
+#[cfg(feature = "num-complex")]
+impl From<num_complex::Complex64> for Complex {
+    #[inline(always)]
+    fn from(c: num_complex::Complex64) -> Self {
+        Self::new(c.re, c.im)
+    }
+}
+
+#[cfg(feature = "num-complex")]
+impl From<Complex> for num_complex::Complex64 {
+    #[inline(always)]
+    fn from(c: Complex) -> Self {
+        Self::new(c.re, c.im)
+    }
+}
+
 #[cfg(feature = "serde")]
 impl serde::Serialize for Complex {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
@@ -245,6 +379,32 @@ mod tests {
         assert_eq!(a.conj(), Complex::new(1.0, -2.0));
     }
 
+    #[test]
+    fn test_display() {
+        assert_eq!(std::format!("{}", Complex::new(0.5, 0.25)), "0.5000+0.2500i");
+        assert_eq!(std::format!("{}", Complex::new(-1.0, -0.125)), "-1.0000-0.1250i");
+        assert_eq!(std::format!("{:.2}", Complex::new(crate::INV_SQRT_2, -crate::INV_SQRT_2)), "0.71-0.71i");
+        // A negative zero imaginary part takes a single minus sign
+        assert_eq!(std::format!("{}", Complex::ONE.conj()), "1.0000-0.0000i");
+    }
+
+    #[test]
+    fn test_divide_by_real() {
+        assert_eq!(Complex::new(4.0, 2.0) / 2.0, Complex::new(2.0, 1.0));
+
+        let mut c = Complex::new(-3.0, 1.5);
+        c /= 3.0;
+        assert_eq!(c, Complex::new(-1.0, 0.5));
+    }
+
+    #[test]
+    fn test_scalar_on_left() {
+        assert_eq!(2.0 * Complex::I, Complex::new(0.0, 2.0));
+        assert_eq!(1.0 / Complex::I, -Complex::I);
+        assert!((2.0 / Complex::new(1.0, 1.0)).approx_eq(Complex::new(1.0, -1.0), 1e-15));
+        assert!((Complex::new(3.0, 4.0).recip() * Complex::new(3.0, 4.0)).approx_eq(Complex::ONE, 1e-15));
+    }
+
     #[test]
     fn test_magnitude() {
         let c = Complex::new(3.0, 4.0);
@@ -252,6 +412,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn test_polar() {
         use crate::PI;
         let c = Complex::from_polar(1.0, PI / 4.0);
@@ -259,6 +420,29 @@ mod tests {
         assert!((c.im - 0.7071067811865476).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_polar_round_trip() {
+        use crate::PI;
+        for r in [0.5, 1.0, 3.0] {
+            for k in -7..=8 {
+                let theta = k as f64 * PI / 8.0;
+                let (r2, theta2) = Complex::from_polar(r, theta).to_polar();
+                assert!((r2 - r).abs() < 1e-12);
+                assert!((theta2 - theta).abs() < 1e-12, "theta {} came back as {}", theta, theta2);
+            }
+        }
+    }
+
+    #[cfg(feature = "num-complex")]
+    #[test]
+    fn test_num_complex_round_trip() {
+        let c = Complex::new(0.25, -1.5);
+        let n: num_complex::Complex64 = c.into();
+        assert_eq!(n, num_complex::Complex64::new(0.25, -1.5));
+        assert_eq!(Complex::from(n * n), c * c);
+        assert_eq!(Complex::from(n), c);
+    }
+
     #[test]
     fn test_exp() {
         use crate::PI;
@@ -268,4 +452,42 @@ mod tests {
         assert!((result.re + 1.0).abs() < 1e-10);
         assert!(result.im.abs() < 1e-10);
     }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(Complex::new(-1.0, 0.0).sqrt(), Complex::I);
+        assert_eq!(Complex::new(4.0, 0.0).sqrt(), Complex::new(2.0, 0.0));
+        assert_eq!(Complex::ZERO.sqrt(), Complex::ZERO);
+
+        for z in [
+            Complex::new(3.0, 4.0),
+            Complex::new(-3.0, 4.0),
+            Complex::new(-2.0, -0.5),
+            Complex::new(0.0, -1.0),
+            Complex::new(1e-8, 1e8),
+        ] {
+            let root = z.sqrt();
+            assert!(root.re >= 0.0);
+            assert!((root * root).approx_eq(z, 1e-10 * z.abs()), "sqrt({:?}) = {:?}", z, root);
+        }
+    }
+
+    #[test]
+    fn test_ln_and_powers() {
+        use crate::PI;
+
+        assert!(Complex::from_real(core::f64::consts::E).ln().approx_eq(Complex::ONE, 1e-15));
+        assert!(Complex::new(-1.0, 0.0).ln().approx_eq(Complex::new(0.0, PI), 1e-15));
+        assert_eq!(Complex::ZERO.ln().re, f64::NEG_INFINITY);
+
+        for z in [Complex::new(3.0, 4.0), Complex::new(-0.5, 2.0), Complex::new(0.0, -1.5)] {
+            assert!(z.ln().exp().approx_eq(z, 1e-12));
+            assert!(z.powf(2.0).approx_eq(z * z, 1e-12));
+            assert!(z.powc(Complex::from_real(3.0)).approx_eq(z * z * z, 1e-10));
+            assert!(z.powf(0.5).approx_eq(z.sqrt(), 1e-12));
+        }
+
+        // i^i = e^(-π/2)
+        assert!(Complex::I.powc(Complex::I).approx_eq(Complex::from_real((-PI / 2.0).exp()), 1e-15));
+    }
 }
@@ -0,0 +1,304 @@
+//! Gate decomposition passes.
+//!
+//! Rewrite gates that hardware rarely supports natively into one- and
+//! two-qubit gates. [`Circuit::decompose`] and [`Circuit::decompose_swaps`]
+//! return exactly equivalent circuits; [`Circuit::transpile_to_basis`] is
+//! exact up to global phase. A classical condition on a decomposed gate is
+//! copied onto each of its parts.
+
+use crate::{Circuit, Gate, GateParams, GateType, HomayaError, Instruction, Result, PI};
+
+/// A gate sequence replacing one instruction, with each gate's qubits.
+type Parts = Vec<(Gate, Vec<usize>)>;
+
+/// SWAP(a, b) as three CNOTs.
+fn swap_gates(a: usize, b: usize) -> Parts {
+    std::vec![
+        (Gate::cx(), std::vec![a, b]),
+        (Gate::cx(), std::vec![b, a]),
+        (Gate::cx(), std::vec![a, b]),
+    ]
+}
+
+/// Toffoli(c1, c2, t) as the standard 15-gate Clifford+T circuit (6 CNOTs).
+fn toffoli_gates(c1: usize, c2: usize, t: usize) -> Parts {
+    std::vec![
+        (Gate::h(), std::vec![t]),
+        (Gate::cx(), std::vec![c2, t]),
+        (Gate::tdg(), std::vec![t]),
+        (Gate::cx(), std::vec![c1, t]),
+        (Gate::t(), std::vec![t]),
+        (Gate::cx(), std::vec![c2, t]),
+        (Gate::tdg(), std::vec![t]),
+        (Gate::cx(), std::vec![c1, t]),
+        (Gate::t(), std::vec![c2]),
+        (Gate::t(), std::vec![t]),
+        (Gate::h(), std::vec![t]),
+        (Gate::cx(), std::vec![c1, c2]),
+        (Gate::tdg(), std::vec![c2]),
+        (Gate::cx(), std::vec![c1, c2]),
+        (Gate::t(), std::vec![c1]),
+    ]
+}
+
+/// Fredkin(c, a, b) as CX(b, a) · Toffoli(c, a, b) · CX(b, a).
+fn fredkin_gates(c: usize, a: usize, b: usize) -> Parts {
+    let mut gates = std::vec![(Gate::cx(), std::vec![b, a])];
+    gates.extend(toffoli_gates(c, a, b));
+    gates.push((Gate::cx(), std::vec![b, a]));
+    gates
+}
+
+/// The single angle of a rotation gate.
+fn angle(gate: &Gate) -> Result<f64> {
+    match gate.params {
+        GateParams::Angle(theta) => Ok(theta),
+        _ => Err(HomayaError::NotSupported {
+            operation: "transpiling a gate with unbound parameters",
+        }),
+    }
+}
+
+/// One rewriting step toward {Rz, Ry, CX}, exact up to global phase.
+///
+/// Returns `None` for gates with no rule, including Rz, Ry and CX
+/// themselves. The parts may need further rewriting.
+fn lower(gate: &Gate, q: &[usize]) -> Result<Option<Parts>> {
+    use GateType::*;
+
+    let one = |gates: &[Gate]| gates.iter().map(|g| (g.clone(), std::vec![q[0]])).collect();
+    let parts = match gate.gate_type {
+        I => Vec::new(),
+        // H = Ry(π/2)·Z and X = Ry(π)·Z, applying Z first
+        H => one(&[Gate::rz(PI), Gate::ry(PI / 2.0)]),
+        X => one(&[Gate::rz(PI), Gate::ry(PI)]),
+        Y => one(&[Gate::ry(PI)]),
+        Z => one(&[Gate::rz(PI)]),
+        S => one(&[Gate::rz(PI / 2.0)]),
+        Sdg => one(&[Gate::rz(-PI / 2.0)]),
+        T => one(&[Gate::rz(PI / 4.0)]),
+        Tdg => one(&[Gate::rz(-PI / 4.0)]),
+        P => one(&[Gate::rz(angle(gate)?)]),
+        // Rx(θ) = Rz(−π/2)·Ry(θ)·Rz(π/2)
+        Rx => one(&[Gate::rz(PI / 2.0), Gate::ry(angle(gate)?), Gate::rz(-PI / 2.0)]),
+        U => match gate.params {
+            GateParams::Angles3(theta, phi, lambda) => one(&[Gate::rz(lambda), Gate::ry(theta), Gate::rz(phi)]),
+            _ => return Err(HomayaError::NotSupported {
+                operation: "transpiling a gate with unbound parameters",
+            }),
+        },
+        CZ => std::vec![(Gate::h(), std::vec![q[1]]), (Gate::cx(), q.to_vec()), (Gate::h(), std::vec![q[1]])],
+        // S·X·S† = Y on the target
+        CY => std::vec![(Gate::sdg(), std::vec![q[1]]), (Gate::cx(), q.to_vec()), (Gate::s(), std::vec![q[1]])],
+        CP => {
+            let theta = angle(gate)?;
+            std::vec![
+                (Gate::p(theta / 2.0), std::vec![q[0]]),
+                (Gate::cx(), q.to_vec()),
+                (Gate::p(-theta / 2.0), std::vec![q[1]]),
+                (Gate::cx(), q.to_vec()),
+                (Gate::p(theta / 2.0), std::vec![q[1]]),
+            ]
+        }
+        Rzz => std::vec![
+            (Gate::cx(), q.to_vec()),
+            (Gate::rz(angle(gate)?), std::vec![q[1]]),
+            (Gate::cx(), q.to_vec()),
+        ],
+        Swap => swap_gates(q[0], q[1]),
+        CCX => toffoli_gates(q[0], q[1], q[2]),
+        CSwap => fredkin_gates(q[0], q[1], q[2]),
+        _ => return Ok(None),
+    };
+    Ok(Some(parts))
+}
+
+/// Rewrite `gate` until every part is in `basis`, appending to `out`.
+fn lower_into(gate: &Gate, qubits: &[usize], basis: &[GateType], out: &mut Parts) -> Result<()> {
+    if basis.contains(&gate.gate_type) || !gate.is_unitary() {
+        out.push((gate.clone(), qubits.to_vec()));
+        return Ok(());
+    }
+
+    let parts = lower(gate, qubits)?.ok_or(HomayaError::NotSupported {
+        operation: "transpiling a gate with no decomposition into the requested basis",
+    })?;
+    for (part, part_qubits) in &parts {
+        lower_into(part, part_qubits, basis, out)?;
+    }
+    Ok(())
+}
+
+impl Circuit {
+    /// Replace every SWAP with three CNOTs.
+    ///
+    /// `SWAP(a, b)` becomes `CX(a, b) · CX(b, a) · CX(a, b)`. All other
+    /// instructions are kept as they are.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::{Circuit, GateType};
+    ///
+    /// let circuit = Circuit::new(2).h(0).swap(0, 1).decompose_swaps();
+    /// assert_eq!(circuit.len(), 4);
+    /// assert_eq!(circuit.count_gates()[&GateType::CX], 3);
+    /// ```
+    pub fn decompose_swaps(self) -> Circuit {
+        self.expand(|inst| match inst.gate.gate_type {
+            GateType::Swap => Some(swap_gates(inst.qubits[0], inst.qubits[1])),
+            _ => None,
+        })
+    }
+
+    /// Expand SWAP, Toffoli and Fredkin gates into one- and two-qubit gates.
+    ///
+    /// SWAP becomes three CNOTs, Toffoli the 15-gate Clifford+T circuit,
+    /// and Fredkin a Toffoli between two CNOTs. Other gates are kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let circuit = Circuit::new(3).ccx(0, 1, 2).decompose();
+    /// assert_eq!(circuit.len(), 15);
+    /// assert!(circuit.instructions().iter().all(|inst| inst.qubits.len() <= 2));
+    /// ```
+    pub fn decompose(self) -> Circuit {
+        self.expand(|inst| {
+            let q = &inst.qubits;
+            match inst.gate.gate_type {
+                GateType::Swap => Some(swap_gates(q[0], q[1])),
+                GateType::CCX => Some(toffoli_gates(q[0], q[1], q[2])),
+                GateType::CSwap => Some(fredkin_gates(q[0], q[1], q[2])),
+                _ => None,
+            }
+        })
+    }
+
+    /// Rewrite the circuit using only the gates in `basis`.
+    ///
+    /// Gates already in the basis, and measurements, resets, barriers and
+    /// snapshots, are kept. Other gates are rewritten step by step toward
+    /// {Rz, Ry, CX}: single-qubit gates become Z-Y-Z rotations, CZ becomes
+    /// H·CX·H on the target, SWAP three CNOTs, and so on. A gate in
+    /// `basis` stops the rewriting early, so `{H, Rz, Ry, CX}` keeps the
+    /// Hadamards from a CZ. The result matches the original up to global
+    /// phase.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotSupported` for gates with no decomposition yet (CH, CU,
+    /// iSWAP, √SWAP, Rxx, Ryy, MCX), for symbolic parameters, and when the
+    /// rewriting needs a gate from {Rz, Ry, CX} that `basis` leaves out.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::{Circuit, GateType};
+    ///
+    /// let basis = [GateType::Rz, GateType::Ry, GateType::CX];
+    /// let circuit = Circuit::new(2).h(0).cz(0, 1).transpile_to_basis(&basis).unwrap();
+    /// assert!(circuit.instructions().iter().all(|inst| basis.contains(&inst.gate.gate_type)));
+    /// ```
+    pub fn transpile_to_basis(self, basis: &[GateType]) -> Result<Circuit> {
+        let mut lowered = Vec::with_capacity(self.len());
+
+        for inst in self.instructions() {
+            if basis.contains(&inst.gate.gate_type) || !inst.gate.is_unitary() {
+                lowered.push(inst.clone());
+                continue;
+            }
+
+            let mut parts = Vec::new();
+            lower_into(&inst.gate, &inst.qubits, basis, &mut parts)?;
+            lowered.extend(parts.into_iter().map(|(gate, qubits)| {
+                let mut part = Instruction::new(gate, qubits);
+                part.condition = inst.condition;
+                part
+            }));
+        }
+
+        Ok(self.with_instructions(lowered))
+    }
+
+    /// Replace each instruction for which `rule` gives a gate sequence.
+    fn expand(self, rule: impl Fn(&Instruction) -> Option<Parts>) -> Circuit {
+        let mut expanded = Vec::with_capacity(self.len());
+
+        for inst in self.instructions() {
+            match rule(inst) {
+                Some(gates) => expanded.extend(gates.into_iter().map(|(gate, qubits)| {
+                    let mut part = Instruction::new(gate, qubits);
+                    part.condition = inst.condition;
+                    part
+                })),
+                None => expanded.push(inst.clone()),
+            }
+        }
+
+        self.with_instructions(expanded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Circuit, Gate, GateType, HomayaError, Instruction};
+
+    #[test]
+    fn test_swaps_become_cnots() {
+        let circuit = Circuit::new(3).h(0).swap(0, 2).ccx(0, 1, 2).decompose_swaps();
+        let types: Vec<GateType> = circuit.instructions().iter().map(|i| i.gate.gate_type).collect();
+        assert_eq!(types, [GateType::H, GateType::CX, GateType::CX, GateType::CX, GateType::CCX]);
+
+        let qubits: Vec<&[usize]> = circuit.instructions()[1..4].iter().map(|i| &i.qubits[..]).collect();
+        assert_eq!(qubits, [&[0, 2][..], &[2, 0], &[0, 2]]);
+    }
+
+    #[test]
+    fn test_conditions_carry_over() {
+        let swap = Instruction::new(Gate::swap(), std::vec![0, 1]).conditioned(0, 1);
+        let circuit = Circuit::with_clbits(2, 1).with_instructions(std::vec![swap]).decompose();
+        assert_eq!(circuit.len(), 3);
+        assert!(circuit.instructions().iter().all(|inst| inst.condition == Some((0, 1))));
+    }
+
+    #[test]
+    fn test_transpile_keeps_basis_and_non_unitary() {
+        let basis = [GateType::Rz, GateType::Ry, GateType::CX];
+        let circuit = Circuit::new(2).rz(0.3, 0).cx(0, 1).measure(1, 0).transpile_to_basis(&basis).unwrap();
+        let types: Vec<GateType> = circuit.instructions().iter().map(|i| i.gate.gate_type).collect();
+        assert_eq!(types, [GateType::Rz, GateType::CX, GateType::Measure]);
+
+        // H in the basis stops CZ at H·CX·H
+        let circuit = Circuit::new(2).cz(0, 1).transpile_to_basis(&[GateType::H, GateType::CX]).unwrap();
+        let types: Vec<GateType> = circuit.instructions().iter().map(|i| i.gate.gate_type).collect();
+        assert_eq!(types, [GateType::H, GateType::CX, GateType::H]);
+    }
+
+    #[test]
+    fn test_transpile_unsupported() {
+        let basis = [GateType::Rz, GateType::Ry, GateType::CX];
+        assert!(matches!(
+            Circuit::new(2).ch(0, 1).transpile_to_basis(&basis),
+            Err(HomayaError::NotSupported { .. })
+        ));
+        assert!(matches!(
+            Circuit::new(1).h(0).transpile_to_basis(&[GateType::Rz, GateType::CX]),
+            Err(HomayaError::NotSupported { .. })
+        ));
+        assert!(matches!(
+            Circuit::new(1).rx_param("theta", 0).transpile_to_basis(&basis),
+            Err(HomayaError::NotSupported { .. })
+        ));
+    }
+
+    #[test]
+    fn test_fredkin_parts() {
+        let circuit = Circuit::new(3).cswap(0, 1, 2).measure(0, 0).decompose();
+        assert_eq!(circuit.len(), 18);
+        assert_eq!(circuit.two_qubit_gate_count(), 8);
+        assert_eq!(circuit.instructions()[17].gate.gate_type, GateType::Measure);
+    }
+}
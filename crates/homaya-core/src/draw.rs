@@ -0,0 +1,294 @@
+//! Text circuit diagrams.
+//!
+//! Each qubit takes three text rows: the wire in the middle, with room
+//! above and below for box borders and the vertical connectors of
+//! multi-qubit gates.
+
+use crate::{fmt::params_to_string, Circuit, Gate, GateType, Instruction};
+
+/// What an instruction draws on one of its qubits.
+enum Cell {
+    /// A boxed label, `┤ H ├`
+    Boxed(std::string::String),
+    /// A single character on the wire (`●`, `×`, `M`, `░`)
+    Glyph(char),
+}
+
+impl Cell {
+    /// Width in characters; always odd, so the cell has a center column.
+    fn width(&self) -> usize {
+        match self {
+            Cell::Boxed(label) => label_width(label) + 4,
+            Cell::Glyph(_) => 1,
+        }
+    }
+}
+
+/// Label length rounded up to odd, so boxes center on a connector.
+fn label_width(label: &str) -> usize {
+    let len = label.chars().count();
+    len + (len + 1) % 2
+}
+
+/// Box label for a gate acting alone, or for the target of a controlled gate.
+fn label(gate: &Gate) -> std::string::String {
+    use GateType::*;
+
+    let name = match gate.gate_type {
+        I => "I",
+        X | CX | CCX | MCX => "X",
+        Y | CY => "Y",
+        Z | CZ => "Z",
+        H | CH => "H",
+        S => "S",
+        Sdg => "S†",
+        T => "T",
+        Tdg => "T†",
+        Rx => "Rx",
+        Ry => "Ry",
+        Rz => "Rz",
+        P | CP => "P",
+        U | CU => "U",
+        ISwap => "iSwap",
+        SqrtSwap => "√Swap",
+        Rxx => "Rxx",
+        Ryy => "Ryy",
+        Rzz => "Rzz",
+        Reset => "|0⟩",
+        Swap | CSwap | Measure | Barrier | Snapshot => "",
+    };
+    std::format!("{}{}", name, params_to_string(&gate.params))
+}
+
+/// The cells an instruction draws, one per qubit it touches.
+///
+/// A classical condition is appended to the last cell's label
+/// (`┤ X c0=1 ├`), boxing it if it was a glyph.
+fn cells(inst: &Instruction, num_qubits: usize) -> Vec<(usize, Cell)> {
+    let mut cells = unconditioned_cells(inst, num_qubits);
+    if let (Some((clbit, value)), Some((_, cell))) = (inst.condition, cells.last_mut()) {
+        let text = match cell {
+            Cell::Boxed(label) => std::mem::take(label),
+            Cell::Glyph(c) => c.to_string(),
+        };
+        *cell = Cell::Boxed(std::format!("{} c{}={}", text, clbit, value));
+    }
+    cells
+}
+
+/// The cells an instruction draws, ignoring any classical condition.
+fn unconditioned_cells(inst: &Instruction, num_qubits: usize) -> Vec<(usize, Cell)> {
+    use GateType::*;
+
+    let qubits = &inst.qubits;
+    let boxed = |q: usize| (q, Cell::Boxed(label(&inst.gate)));
+    match inst.gate.gate_type {
+        Measure => std::vec![(qubits[0], Cell::Glyph('M'))],
+        Barrier if qubits.is_empty() => (0..num_qubits).map(|q| (q, Cell::Glyph('░'))).collect(),
+        Barrier => qubits.iter().map(|&q| (q, Cell::Glyph('░'))).collect(),
+        CZ => qubits.iter().map(|&q| (q, Cell::Glyph('●'))).collect(),
+        Swap => qubits.iter().map(|&q| (q, Cell::Glyph('×'))).collect(),
+        CSwap => std::vec![
+            (qubits[0], Cell::Glyph('●')),
+            (qubits[1], Cell::Glyph('×')),
+            (qubits[2], Cell::Glyph('×')),
+        ],
+        CX | CY | CH | CP | CU | CCX | MCX => {
+            let (&target, controls) = qubits.split_last().expect("controlled gates have a target");
+            let mut cells: Vec<(usize, Cell)> = controls.iter().map(|&c| (c, Cell::Glyph('●'))).collect();
+            cells.push(boxed(target));
+            cells
+        }
+        _ => qubits.iter().map(|&q| boxed(q)).collect(),
+    }
+}
+
+/// An instruction's cells, and the qubit span of its connector, if any.
+type Placed = (Vec<(usize, Cell)>, Option<(usize, usize)>);
+
+impl Circuit {
+    /// Render the circuit as a text diagram.
+    ///
+    /// Gates are laid out left to right in depth order, each column holding
+    /// gates that can run in parallel. Single-qubit gates are boxed
+    /// (`┤ H ├`), controls are `●`, swaps are `×`, measurements are `M` and
+    /// barriers are `░`. Multi-qubit gates are joined by a vertical line,
+    /// which crosses any qubits in between (`┼`); a gate occupies every
+    /// qubit its line spans, so lines never overlap other gates.
+    ///
+    /// Classically conditioned gates carry their condition in the label
+    /// (`┤ X c0=1 ├`) and are placed after the last write to that clbit,
+    /// following the same clbit dependencies as [`Circuit::layers`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let diagram = Circuit::new(2).h(0).cx(0, 1).draw();
+    /// assert!(diagram.contains("q0─┤ H ├───●───"));
+    /// ```
+    pub fn draw(&self) -> std::string::String {
+        let n = self.num_qubits();
+
+        // Assign each instruction to the first column free across its span
+        let mut level = std::vec![0usize; n];
+        let mut written = std::vec![0usize; self.num_clbits()];
+        let mut read = std::vec![0usize; self.num_clbits()];
+        let mut columns: Vec<Vec<Placed>> = Vec::new();
+        for inst in self.instructions() {
+            let cells = cells(inst, n);
+            let lo = cells.iter().map(|&(q, _)| q).min();
+            let hi = cells.iter().map(|&(q, _)| q).max();
+            let (Some(lo), Some(hi)) = (lo, hi) else { continue };
+
+            // Readers wait for the last write; writers also for the last read
+            let reads = inst.condition.iter().map(|&(c, _)| written[c]);
+            let writes = inst.clbits.iter().map(|&c| written[c].max(read[c]));
+            let column = level[lo..=hi].iter().copied().chain(reads).chain(writes).max().unwrap_or(0);
+            level[lo..=hi].iter_mut().for_each(|l| *l = column + 1);
+            if let Some((c, _)) = inst.condition {
+                read[c] = read[c].max(column + 1);
+            }
+            for &c in &inst.clbits {
+                written[c] = column + 1;
+            }
+            if columns.len() <= column {
+                columns.resize_with(column + 1, Vec::new);
+            }
+            let connector = (lo < hi && !inst.is_barrier()).then_some((lo, hi));
+            columns[column].push((cells, connector));
+        }
+
+        // Rows 3q, 3q + 1 and 3q + 2 are above, on and below qubit q's wire
+        let names: Vec<std::string::String> = (0..n).map(|q| std::format!("q{}", q)).collect();
+        let name_width = names.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+        let mut rows: Vec<Vec<char>> = Vec::with_capacity(3 * n);
+        for name in &names {
+            let pad = name_width - name.chars().count();
+            rows.push(std::vec![' '; name_width + 1]);
+            let mut wire: Vec<char> = name.chars().collect();
+            wire.extend(std::iter::repeat('─').take(pad + 1));
+            rows.push(wire);
+            rows.push(std::vec![' '; name_width + 1]);
+        }
+
+        for column in &columns {
+            let width = column
+                .iter()
+                .flat_map(|(cells, _)| cells.iter().map(|(_, cell)| cell.width()))
+                .max()
+                .unwrap_or(1);
+            let center = width / 2;
+
+            let mut block: Vec<Vec<char>> = (0..3 * n)
+                .map(|row| std::vec![if row % 3 == 1 { '─' } else { ' ' }; width])
+                .collect();
+
+            for (cells, connector) in column {
+                for (q, cell) in cells {
+                    let [top, wire, bottom] = &mut block[3 * q..3 * q + 3] else {
+                        unreachable!("three rows per qubit")
+                    };
+                    match cell {
+                        Cell::Glyph(c) => wire[center] = *c,
+                        Cell::Boxed(label) => {
+                            let start = center - cell.width() / 2;
+                            let inner = label_width(label);
+                            let text: Vec<char> = label.chars().chain(std::iter::once(' ')).take(inner).collect();
+                            top[start] = '┌';
+                            bottom[start] = '└';
+                            wire[start] = '┤';
+                            wire[start + 1] = ' ';
+                            for i in 0..inner + 2 {
+                                top[start + 1 + i] = '─';
+                                bottom[start + 1 + i] = '─';
+                            }
+                            wire[start + 2..start + 2 + inner].copy_from_slice(&text);
+                            wire[start + inner + 2] = ' ';
+                            top[start + inner + 3] = '┐';
+                            bottom[start + inner + 3] = '┘';
+                            wire[start + inner + 3] = '├';
+                        }
+                    }
+                }
+
+                let Some((lo, hi)) = *connector else { continue };
+                for q in lo..=hi {
+                    let has_cell = cells.iter().any(|&(c, _)| c == q);
+                    let (top, bottom) = (3 * q, 3 * q + 2);
+                    if q > lo {
+                        block[top][center] = if block[top][center] == '─' { '┴' } else { '│' };
+                    }
+                    if q < hi {
+                        block[bottom][center] = if block[bottom][center] == '─' { '┬' } else { '│' };
+                    }
+                    if !has_cell {
+                        block[3 * q + 1][center] = '┼';
+                    }
+                }
+            }
+
+            for (index, (row, segment)) in rows.iter_mut().zip(block).enumerate() {
+                row.extend(segment);
+                row.push(if index % 3 == 1 { '─' } else { ' ' });
+            }
+        }
+
+        let mut out = std::string::String::new();
+        for row in rows {
+            let line: std::string::String = row.into_iter().collect();
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Circuit, PI};
+
+    #[test]
+    fn test_draw_bell() {
+        let diagram = Circuit::new(2).h(0).cx(0, 1).measure_all().draw();
+        assert_eq!(
+            diagram,
+            "   ┌───┐\n\
+             q0─┤ H ├───●───M─\n\
+             \x20  └───┘   │\n\
+             \x20        ┌─┴─┐\n\
+             q1───────┤ X ├─M─\n\
+             \x20        └───┘\n"
+        );
+    }
+
+    #[test]
+    fn test_draw_connector_crosses_idle_qubits() {
+        let diagram = Circuit::new(3).cx(2, 0).swap(0, 2).draw();
+        let lines: Vec<&str> = diagram.lines().collect();
+        assert_eq!(lines[1], "q0─┤ X ├─×─");
+        assert_eq!(lines[2], "   └─┬─┘ │");
+        assert_eq!(lines[4], "q1───┼───┼─");
+        assert_eq!(lines[7], "q2───●───×─");
+    }
+
+    #[test]
+    fn test_draw_columns_and_params() {
+        let diagram = Circuit::new(3).rx(PI / 4.0, 0).h(2).ccx(0, 2, 1).draw();
+        let lines: Vec<&str> = diagram.lines().collect();
+        // Rx and H share the first column; the Toffoli follows
+        assert_eq!(lines[1], "q0─┤ Rx(π/4) ├───●───");
+        assert_eq!(lines[4], "q1─────────────┤ X ├─");
+        assert_eq!(lines[5], "               └─┬─┘");
+        assert_eq!(lines[7], "q2────┤ H ├──────●───");
+    }
+
+    #[test]
+    fn test_draw_conditioned_gate_follows_measurement() {
+        let diagram = Circuit::with_clbits(2, 1).h(0).measure(0, 0).x_if(1, 0, 1).draw();
+        let lines: Vec<&str> = diagram.lines().collect();
+        assert_eq!(lines[1], "q0─┤ H ├─M─────────────");
+        assert_eq!(lines[4], "q1─────────┤ X c0=1  ├─");
+    }
+}
@@ -37,6 +37,14 @@ pub enum HomayaError {
         message: &'static str,
     },
 
+    /// Instruction index out of range.
+    InstructionOutOfRange {
+        /// The invalid instruction index
+        index: usize,
+        /// Number of instructions
+        len: usize,
+    },
+
     /// Duplicate qubit in instruction.
     DuplicateQubit {
         /// The duplicated qubit index
@@ -77,6 +85,40 @@ pub enum HomayaError {
         operation: &'static str,
     },
 
+    /// Symbolic parameter without a value.
+    UnboundParameter {
+        /// Parameter name
+        name: std::string::String,
+    },
+
+    /// Malformed OpenQASM source.
+    QasmParse {
+        /// 1-based line of the offending statement
+        line: usize,
+        /// What was wrong
+        message: std::string::String,
+    },
+
+    /// Malformed JSON circuit file.
+    JsonParse {
+        /// What was wrong
+        message: std::string::String,
+    },
+
+    /// JSON circuit file written in a format version this build cannot read.
+    UnsupportedFormatVersion {
+        /// Version found in the file
+        version: u64,
+        /// Version this build reads and writes
+        supported: u64,
+    },
+
+    /// Bitstring with characters other than `0` and `1`, or no characters.
+    InvalidBitstring {
+        /// The rejected bitstring
+        bits: std::string::String,
+    },
+
     /// Simulation error.
     SimulationError {
         /// Error message
@@ -107,6 +149,9 @@ impl fmt::Display for HomayaError {
             Self::InvalidGateParams { gate, message } => {
                 write!(f, "invalid parameters for {}: {}", gate, message)
             }
+            Self::InstructionOutOfRange { index, len } => {
+                write!(f, "instruction index {} out of range (circuit has {})", index, len)
+            }
             Self::DuplicateQubit { qubit } => {
                 write!(f, "duplicate qubit {} in instruction", qubit)
             }
@@ -125,6 +170,21 @@ impl fmt::Display for HomayaError {
             Self::NotSupported { operation } => {
                 write!(f, "operation not supported: {}", operation)
             }
+            Self::UnboundParameter { name } => {
+                write!(f, "circuit parameter '{}' has no value", name)
+            }
+            Self::QasmParse { line, message } => {
+                write!(f, "OpenQASM parse error on line {}: {}", line, message)
+            }
+            Self::JsonParse { message } => {
+                write!(f, "JSON circuit parse error: {}", message)
+            }
+            Self::UnsupportedFormatVersion { version, supported } => {
+                write!(f, "unsupported circuit format version {} (this build reads version {})", version, supported)
+            }
+            Self::InvalidBitstring { bits } => {
+                write!(f, "invalid bitstring '{}': expected one or more '0' or '1'", bits)
+            }
             Self::SimulationError { message } => {
                 write!(f, "simulation error: {}", message)
             }
@@ -136,3 +196,100 @@ impl fmt::Display for HomayaError {
 }
 
 impl std::error::Error for HomayaError {}
+
+/// Convert from the shared error type in `homaya-types`.
+///
+/// Matching variants map directly; `types` reports the largest valid qubit
+/// index where this crate reports the register size. Everything else
+/// becomes a [`HomayaError::SimulationError`] carrying the original message.
+impl From<homaya_types::HomayaError> for HomayaError {
+    fn from(err: homaya_types::HomayaError) -> Self {
+        use homaya_types::HomayaError as Types;
+
+        match err {
+            Types::QubitOutOfRange { qubit, max } => Self::QubitOutOfRange { qubit, max: max + 1 },
+            Types::InvalidProbability { value } => Self::InvalidProbability { value },
+            Types::NotSupported { operation } => Self::NotSupported { operation },
+            Types::BackendError { message } => Self::BackendError { backend: "external", message },
+            other => Self::SimulationError {
+                message: std::string::ToString::to_string(&other),
+            },
+        }
+    }
+}
+
+/// Convert to the shared error type in `homaya-types`.
+///
+/// The inverse of the conversion above where a matching variant exists.
+/// Everything else becomes a `BackendError` carrying the original message.
+impl From<HomayaError> for homaya_types::HomayaError {
+    fn from(err: HomayaError) -> Self {
+        match err {
+            HomayaError::QubitOutOfRange { qubit, max } => Self::QubitOutOfRange {
+                qubit,
+                max: max.saturating_sub(1),
+            },
+            HomayaError::InvalidProbability { value } => Self::InvalidProbability { value },
+            HomayaError::NotSupported { operation } => Self::NotSupported { operation },
+            other => Self::BackendError {
+                message: std::string::ToString::to_string(&other),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(err: HomayaError) -> HomayaError {
+        HomayaError::from(homaya_types::HomayaError::from(err))
+    }
+
+    #[test]
+    fn test_matching_variants_round_trip() {
+        for err in [
+            HomayaError::QubitOutOfRange { qubit: 5, max: 3 },
+            HomayaError::InvalidProbability { value: 1.5 },
+            HomayaError::NotSupported { operation: "mid-circuit reset" },
+        ] {
+            assert_eq!(round_trip(err.clone()), err);
+        }
+
+        // Both report the same largest valid index
+        let types = homaya_types::HomayaError::from(HomayaError::QubitOutOfRange { qubit: 5, max: 3 });
+        assert_eq!(types, homaya_types::HomayaError::QubitOutOfRange { qubit: 5, max: 2 });
+    }
+
+    #[test]
+    fn test_other_variants_keep_their_message() {
+        let err = HomayaError::DuplicateQubit { qubit: 2 };
+        match homaya_types::HomayaError::from(err.clone()) {
+            homaya_types::HomayaError::BackendError { message } => assert_eq!(message, err.to_string()),
+            other => panic!("expected a backend error, got {:?}", other),
+        }
+
+        let err = homaya_types::HomayaError::InvalidParameter {
+            name: "shots",
+            message: "must be positive".to_string(),
+        };
+        assert_eq!(
+            HomayaError::from(err),
+            HomayaError::SimulationError {
+                message: "Invalid parameter 'shots': must be positive".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_question_mark_across_crates() {
+        fn core_op() -> crate::Result<()> {
+            Err(homaya_types::HomayaError::NotSupported { operation: "x" })?
+        }
+        fn types_op() -> homaya_types::Result<()> {
+            core_op()?;
+            Ok(())
+        }
+        assert_eq!(types_op(), Err(homaya_types::HomayaError::NotSupported { operation: "x" }));
+    }
+}
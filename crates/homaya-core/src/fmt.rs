@@ -0,0 +1,113 @@
+//! Human-readable formatting helpers.
+//!
+//! Shared by the circuit renderers so that angles print the same way
+//! everywhere.
+
+use crate::{GateParams, PI};
+
+/// Tolerance for recognizing a fraction of π.
+const PI_FRACTION_EPSILON: f64 = 1e-9;
+
+/// Denominators tried when recognizing fractions of π, smallest first.
+const PI_DENOMINATORS: [i64; 7] = [1, 2, 3, 4, 6, 8, 12];
+
+/// Format an angle, writing common fractions of π symbolically.
+///
+/// Recognizes kπ/d for d in {1, 2, 3, 4, 6, 8, 12}, always in lowest
+/// terms. Anything else prints as a decimal with four places.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_core::{fmt::angle_to_string, PI};
+///
+/// assert_eq!(angle_to_string(PI / 4.0), "π/4");
+/// assert_eq!(angle_to_string(-2.0 * PI / 3.0), "-2π/3");
+/// assert_eq!(angle_to_string(0.7), "0.7000");
+/// ```
+pub fn angle_to_string(theta: f64) -> std::string::String {
+    if theta.abs() < PI_FRACTION_EPSILON {
+        return "0".into();
+    }
+
+    for d in PI_DENOMINATORS {
+        let k = (theta * d as f64 / PI).round();
+        if (theta - k * PI / d as f64).abs() < PI_FRACTION_EPSILON {
+            let k = k as i64;
+            let numerator = match k {
+                1 => "π".into(),
+                -1 => "-π".into(),
+                _ => std::format!("{}π", k),
+            };
+            return if d == 1 {
+                numerator
+            } else {
+                std::format!("{}/{}", numerator, d)
+            };
+        }
+    }
+
+    std::format!("{:.4}", theta)
+}
+
+/// Format gate parameters as a parenthesized list, or `""` if there are none.
+pub(crate) fn params_to_string(params: &GateParams) -> std::string::String {
+    match *params {
+        GateParams::None => std::string::String::new(),
+        GateParams::Angle(theta) => std::format!("({})", angle_to_string(theta)),
+        GateParams::Angles3(theta, phi, lambda) => std::format!(
+            "({}, {}, {})",
+            angle_to_string(theta),
+            angle_to_string(phi),
+            angle_to_string(lambda)
+        ),
+        GateParams::Parameter { ref name, scale } => std::format!("({})", symbol_to_string(name, scale)),
+    }
+}
+
+/// Format a symbolic angle `scale × name`, e.g. `θ`, `-θ` or `0.5000*θ`.
+pub(crate) fn symbol_to_string(name: &str, scale: f64) -> std::string::String {
+    if scale == 1.0 {
+        name.into()
+    } else if scale == -1.0 {
+        std::format!("-{}", name)
+    } else {
+        std::format!("{:.4}*{}", scale, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pi_fractions() {
+        assert_eq!(angle_to_string(PI / 4.0), "π/4");
+        assert_eq!(angle_to_string(PI / 2.0), "π/2");
+        assert_eq!(angle_to_string(PI), "π");
+        assert_eq!(angle_to_string(-PI), "-π");
+        assert_eq!(angle_to_string(2.0 * PI / 3.0), "2π/3");
+        assert_eq!(angle_to_string(2.0 * PI / 4.0), "π/2");
+        assert_eq!(angle_to_string(2.0 * PI), "2π");
+        assert_eq!(angle_to_string(-PI / 8.0), "-π/8");
+        assert_eq!(angle_to_string(0.0), "0");
+    }
+
+    #[test]
+    fn test_decimal_fallback() {
+        assert_eq!(angle_to_string(0.7), "0.7000");
+        assert_eq!(angle_to_string(PI / 5.0), "0.6283");
+        assert_eq!(angle_to_string(PI / 4.0 + 1e-6), "0.7854");
+    }
+
+    #[test]
+    fn test_params_to_string() {
+        assert_eq!(params_to_string(&GateParams::None), "");
+        assert_eq!(params_to_string(&GateParams::Angle(PI / 2.0)), "(π/2)");
+        assert_eq!(params_to_string(&GateParams::Angles3(PI, 0.0, 0.25)), "(π, 0, 0.2500)");
+        let symbol = |scale| GateParams::Parameter { name: "θ".into(), scale };
+        assert_eq!(params_to_string(&symbol(1.0)), "(θ)");
+        assert_eq!(params_to_string(&symbol(-1.0)), "(-θ)");
+        assert_eq!(params_to_string(&symbol(0.5)), "(0.5000*θ)");
+    }
+}
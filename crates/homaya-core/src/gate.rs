@@ -2,7 +2,15 @@
 //!
 //! All standard gates optimized for speed.
 
-use crate::{Complex, INV_SQRT_2, PI};
+use homaya_types::StandardGate;
+
+use crate::{Complex, HomayaError, Result, INV_SQRT_2};
+
+/// e^(iπ/4), the T gate phase. Exact components avoid trig calls in hot loops.
+const T_PHASE: Complex = Complex::new(INV_SQRT_2, INV_SQRT_2);
+
+/// e^(-iπ/4), the T-dagger gate phase.
+const TDG_PHASE: Complex = T_PHASE.conj();
 
 /// The type of a quantum gate.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -57,6 +65,12 @@ pub enum GateType {
     ISwap,
     /// √SWAP gate
     SqrtSwap,
+    /// XX Ising coupling, exp(-iθ/2 X⊗X)
+    Rxx,
+    /// YY Ising coupling, exp(-iθ/2 Y⊗Y)
+    Ryy,
+    /// ZZ Ising coupling, exp(-iθ/2 Z⊗Z)
+    Rzz,
 
     // Three-qubit gates
     /// Toffoli (CCX)
@@ -64,6 +78,10 @@ pub enum GateType {
     /// Controlled-SWAP (Fredkin)
     CSwap,
 
+    // Variable-arity gates
+    /// Multi-controlled X; the last operand is the target
+    MCX,
+
     // Measurement
     /// Measure qubit
     Measure,
@@ -71,10 +89,12 @@ pub enum GateType {
     Reset,
     /// Barrier (no-op for timing)
     Barrier,
+    /// Labeled capture of the simulator state (no-op for the physics)
+    Snapshot,
 }
 
 /// A quantum gate with its parameters.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Gate {
     /// The type of gate
@@ -84,7 +104,7 @@ pub struct Gate {
 }
 
 /// Gate parameters.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GateParams {
     /// No parameters
@@ -93,6 +113,81 @@ pub enum GateParams {
     Angle(f64),
     /// Three angles (U gate: theta, phi, lambda)
     Angles3(f64, f64, f64),
+    /// Symbolic single angle `scale × name`, bound later with [`Circuit::bind`](crate::Circuit::bind)
+    Parameter {
+        /// Parameter name
+        name: std::string::String,
+        /// Factor applied to the bound value (−1 after [`Gate::dagger`])
+        scale: f64,
+    },
+}
+
+impl GateParams {
+    /// Check that every angle is finite (not NaN or infinite).
+    #[inline]
+    pub fn is_finite(&self) -> bool {
+        match *self {
+            GateParams::None => true,
+            GateParams::Angle(theta) => theta.is_finite(),
+            GateParams::Angles3(theta, phi, lambda) => {
+                theta.is_finite() && phi.is_finite() && lambda.is_finite()
+            }
+            GateParams::Parameter { scale, .. } => scale.is_finite(),
+        }
+    }
+}
+
+/// An angle's bit pattern, with `-0.0` folded into `0.0`.
+fn angle_bits(x: f64) -> u64 {
+    if x == 0.0 {
+        0
+    } else {
+        x.to_bits()
+    }
+}
+
+/// Angles compare by bit pattern ([`f64::to_bits`]), with `-0.0` equal to
+/// `0.0`. Unlike float `==` this is reflexive for NaN too, so `Eq` holds and
+/// a gate with a NaN angle can be found again in a map.
+impl PartialEq for GateParams {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (GateParams::None, GateParams::None) => true,
+            (GateParams::Angle(a), GateParams::Angle(b)) => angle_bits(*a) == angle_bits(*b),
+            (GateParams::Angles3(a0, a1, a2), GateParams::Angles3(b0, b1, b2)) => {
+                angle_bits(*a0) == angle_bits(*b0)
+                    && angle_bits(*a1) == angle_bits(*b1)
+                    && angle_bits(*a2) == angle_bits(*b2)
+            }
+            (
+                GateParams::Parameter { name: a, scale: sa },
+                GateParams::Parameter { name: b, scale: sb },
+            ) => a == b && angle_bits(*sa) == angle_bits(*sb),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for GateParams {}
+
+/// Hashes angles by bit pattern, consistent with `==`.
+impl core::hash::Hash for GateParams {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match *self {
+            GateParams::None => {}
+            GateParams::Angle(theta) => angle_bits(theta).hash(state),
+            GateParams::Angles3(theta, phi, lambda) => {
+                angle_bits(theta).hash(state);
+                angle_bits(phi).hash(state);
+                angle_bits(lambda).hash(state);
+            }
+            GateParams::Parameter { ref name, scale } => {
+                name.hash(state);
+                angle_bits(scale).hash(state);
+            }
+        }
+    }
 }
 
 impl Gate {
@@ -283,6 +378,15 @@ impl Gate {
         }
     }
 
+    /// Controlled-U gate.
+    #[inline]
+    pub const fn cu(theta: f64, phi: f64, lambda: f64) -> Self {
+        Self {
+            gate_type: GateType::CU,
+            params: GateParams::Angles3(theta, phi, lambda),
+        }
+    }
+
     /// SWAP gate.
     #[inline]
     pub const fn swap() -> Self {
@@ -292,6 +396,51 @@ impl Gate {
         }
     }
 
+    /// iSWAP gate.
+    #[inline]
+    pub const fn iswap() -> Self {
+        Self {
+            gate_type: GateType::ISwap,
+            params: GateParams::None,
+        }
+    }
+
+    /// √SWAP gate.
+    #[inline]
+    pub const fn sqrt_swap() -> Self {
+        Self {
+            gate_type: GateType::SqrtSwap,
+            params: GateParams::None,
+        }
+    }
+
+    /// XX Ising coupling rotation.
+    #[inline]
+    pub const fn rxx(theta: f64) -> Self {
+        Self {
+            gate_type: GateType::Rxx,
+            params: GateParams::Angle(theta),
+        }
+    }
+
+    /// YY Ising coupling rotation.
+    #[inline]
+    pub const fn ryy(theta: f64) -> Self {
+        Self {
+            gate_type: GateType::Ryy,
+            params: GateParams::Angle(theta),
+        }
+    }
+
+    /// ZZ Ising coupling rotation.
+    #[inline]
+    pub const fn rzz(theta: f64) -> Self {
+        Self {
+            gate_type: GateType::Rzz,
+            params: GateParams::Angle(theta),
+        }
+    }
+
     // ========== Three-qubit gates ==========
 
     /// Toffoli (CCX) gate.
@@ -312,6 +461,18 @@ impl Gate {
         }
     }
 
+    /// Multi-controlled X gate.
+    ///
+    /// Takes any number of controls followed by the target, so it has no
+    /// fixed arity (see [`Circuit::mcx`](crate::Circuit::mcx)).
+    #[inline]
+    pub const fn mcx() -> Self {
+        Self {
+            gate_type: GateType::MCX,
+            params: GateParams::None,
+        }
+    }
+
     // ========== Special operations ==========
 
     /// Measure qubit.
@@ -341,6 +502,74 @@ impl Gate {
         }
     }
 
+    /// Snapshot of the simulator state; see [`Circuit::snapshot`](crate::Circuit::snapshot).
+    #[inline]
+    pub const fn snapshot() -> Self {
+        Self {
+            gate_type: GateType::Snapshot,
+            params: GateParams::None,
+        }
+    }
+
+    // ========== Symbolic parameters ==========
+
+    /// Single-angle gate whose angle is the named parameter.
+    pub(crate) fn symbolic(gate_type: GateType, name: impl Into<std::string::String>) -> Self {
+        Self {
+            gate_type,
+            params: GateParams::Parameter {
+                name: name.into(),
+                scale: 1.0,
+            },
+        }
+    }
+
+    // ========== Inversion ==========
+
+    /// Get the adjoint (inverse) gate.
+    ///
+    /// S and T swap with their daggers, rotations and phases negate their
+    /// angle (or the scale of a symbolic angle), and `U(θ, φ, λ)` becomes
+    /// `U(-θ, -λ, -φ)` (likewise `CU`). Self-inverse gates are returned
+    /// unchanged.
+    ///
+    /// `ISwap` and `SqrtSwap` have no single-gate inverse and are returned
+    /// unchanged; [`Circuit::inverse`](crate::Circuit::inverse) expands them.
+    /// Measurement, reset and barriers are not unitary and are also
+    /// returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::{Gate, GateType};
+    ///
+    /// assert_eq!(Gate::t().dagger().gate_type, GateType::Tdg);
+    /// assert_eq!(Gate::rx(0.5).dagger(), Gate::rx(-0.5));
+    /// ```
+    pub fn dagger(&self) -> Gate {
+        use GateType::*;
+
+        let gate_type = match self.gate_type {
+            S => Sdg,
+            Sdg => S,
+            T => Tdg,
+            Tdg => T,
+            other => other,
+        };
+
+        let params = match self.params {
+            GateParams::Angle(theta) => GateParams::Angle(-theta),
+            GateParams::Angles3(theta, phi, lambda) => GateParams::Angles3(-theta, -lambda, -phi),
+            GateParams::Parameter { ref name, scale } => GateParams::Parameter {
+                name: name.clone(),
+                scale: -scale,
+            },
+            GateParams::None => GateParams::None,
+        };
+
+        Gate { gate_type, params }
+    }
+
     // ========== Matrix representation ==========
 
     /// Get the 2x2 matrix for a single-qubit gate.
@@ -375,15 +604,9 @@ impl Gate {
 
             Sdg => Some([[one, zero], [zero, -i]]),
 
-            T => {
-                let t = Complex::from_polar(1.0, PI / 4.0);
-                Some([[one, zero], [zero, t]])
-            }
+            T => Some([[one, zero], [zero, T_PHASE]]),
 
-            Tdg => {
-                let t = Complex::from_polar(1.0, -PI / 4.0);
-                Some([[one, zero], [zero, t]])
-            }
+            Tdg => Some([[one, zero], [zero, TDG_PHASE]]),
 
             Rx => {
                 if let GateParams::Angle(theta) = self.params {
@@ -431,11 +654,11 @@ impl Gate {
                     Some([
                         [
                             Complex::from_real(cos),
-                            -Complex::from_polar(1.0, lambda) * sin,
+                            -sin * Complex::from_polar(1.0, lambda),
                         ],
                         [
-                            Complex::from_polar(1.0, phi) * sin,
-                            Complex::from_polar(1.0, phi + lambda) * cos,
+                            sin * Complex::from_polar(1.0, phi),
+                            cos * Complex::from_polar(1.0, phi + lambda),
                         ],
                     ])
                 } else {
@@ -447,15 +670,210 @@ impl Gate {
         }
     }
 
+    /// Get the 4x4 matrix for a two-qubit gate.
+    ///
+    /// Rows and columns are indexed by `b0 + 2·b1`, where `b0` is the state
+    /// of the first operand (the control, for controlled gates) and `b1`
+    /// that of the second, matching the simulator's little-endian order.
+    /// Returns `None` for single-qubit, three-qubit and non-unitary gates.
+    pub fn matrix_4x4(&self) -> Option<[[Complex; 4]; 4]> {
+        use GateType::*;
+
+        let zero = Complex::ZERO;
+        let one = Complex::ONE;
+        let i = Complex::I;
+
+        match self.gate_type {
+            CX => Some(controlled(Gate::x().matrix_2x2()?)),
+            CY => Some(controlled(Gate::y().matrix_2x2()?)),
+            CZ => Some(controlled(Gate::z().matrix_2x2()?)),
+            CH => Some(controlled(Gate::h().matrix_2x2()?)),
+            CP => match self.params {
+                GateParams::Angle(theta) => Some(controlled(Gate::p(theta).matrix_2x2()?)),
+                _ => None,
+            },
+            CU => match self.params {
+                GateParams::Angles3(theta, phi, lambda) => {
+                    Some(controlled(Gate::u(theta, phi, lambda).matrix_2x2()?))
+                }
+                _ => None,
+            },
+
+            Swap => Some([
+                [one, zero, zero, zero],
+                [zero, zero, one, zero],
+                [zero, one, zero, zero],
+                [zero, zero, zero, one],
+            ]),
+
+            ISwap => Some([
+                [one, zero, zero, zero],
+                [zero, zero, i, zero],
+                [zero, i, zero, zero],
+                [zero, zero, zero, one],
+            ]),
+
+            SqrtSwap => {
+                let a = Complex::new(0.5, 0.5);
+                let b = Complex::new(0.5, -0.5);
+                Some([
+                    [one, zero, zero, zero],
+                    [zero, a, b, zero],
+                    [zero, b, a, zero],
+                    [zero, zero, zero, one],
+                ])
+            }
+
+            Rxx | Ryy | Rzz => {
+                let GateParams::Angle(theta) = self.params else {
+                    return None;
+                };
+                let cos = Complex::from_real((theta / 2.0).cos());
+                let sin = (theta / 2.0).sin();
+                Some(match self.gate_type {
+                    // Couples |00⟩↔|11⟩ and |01⟩↔|10⟩
+                    Rxx => {
+                        let s = Complex::new(0.0, -sin);
+                        [[cos, zero, zero, s], [zero, cos, s, zero], [zero, s, cos, zero], [s, zero, zero, cos]]
+                    }
+                    Ryy => {
+                        let s = Complex::new(0.0, sin);
+                        [[cos, zero, zero, s], [zero, cos, -s, zero], [zero, -s, cos, zero], [s, zero, zero, cos]]
+                    }
+                    _ => {
+                        let even = Complex::from_polar(1.0, -theta / 2.0);
+                        let odd = Complex::from_polar(1.0, theta / 2.0);
+                        [[even, zero, zero, zero], [zero, odd, zero, zero], [zero, zero, odd, zero], [zero, zero, zero, even]]
+                    }
+                })
+            }
+
+            _ => None,
+        }
+    }
+
+    /// Get the 8x8 matrix for a three-qubit gate.
+    ///
+    /// Rows and columns are indexed by `b0 + 2·b1 + 4·b2` for the states of
+    /// the three operands in order, as in [`matrix_4x4`](Self::matrix_4x4).
+    /// Returns `None` for gates on fewer qubits and for `MCX`.
+    pub fn matrix_8x8(&self) -> Option<[[Complex; 8]; 8]> {
+        // Both gates permute basis states
+        let image: fn(usize) -> usize = match self.gate_type {
+            GateType::CCX => |i| if i & 0b011 == 0b011 { i ^ 0b100 } else { i },
+            GateType::CSwap => |i| match i {
+                0b011 => 0b101,
+                0b101 => 0b011,
+                _ => i,
+            },
+            _ => return None,
+        };
+
+        let mut matrix = [[Complex::ZERO; 8]; 8];
+        for col in 0..8 {
+            matrix[image(col)][col] = Complex::ONE;
+        }
+        Some(matrix)
+    }
+
+    /// Get the two eigenvalues of a single-qubit gate.
+    ///
+    /// Named gates use their known spectra; `U` is solved from the 2x2
+    /// characteristic equation λ² - tr(M)λ + det(M) = 0.
+    /// Returns `None` for multi-qubit gates and non-unitary operations.
+    pub fn eigenvalues(&self) -> Option<[Complex; 2]> {
+        use GateType::*;
+
+        let one = Complex::ONE;
+
+        match (self.gate_type, &self.params) {
+            (I, _) => Some([one, one]),
+            (X | Y | Z | H, _) => Some([one, -one]),
+            (S, _) => Some([one, Complex::I]),
+            (Sdg, _) => Some([one, -Complex::I]),
+            (T, _) => Some([one, T_PHASE]),
+            (Tdg, _) => Some([one, TDG_PHASE]),
+            (Rx | Ry | Rz, GateParams::Angle(theta)) => Some([
+                Complex::from_polar(1.0, -theta / 2.0),
+                Complex::from_polar(1.0, theta / 2.0),
+            ]),
+            (P, GateParams::Angle(theta)) => Some([one, Complex::from_polar(1.0, *theta)]),
+            (U, _) => {
+                let m = self.matrix_2x2()?;
+                let half_trace = (m[0][0] + m[1][1]) * 0.5;
+                let det = m[0][0] * m[1][1] - m[0][1] * m[1][0];
+                let disc = half_trace * half_trace - det;
+                // Principal square root of the discriminant
+                let root = Complex::from_polar(disc.abs().sqrt(), disc.arg() / 2.0);
+                Some([half_trace - root, half_trace + root])
+            }
+            _ => None,
+        }
+    }
+
+    /// Canonical lowercase name, following OpenQASM 3 `stdgates.inc`.
+    ///
+    /// The builtin `U` keeps its capital. Gates outside `stdgates.inc`
+    /// use their conventional names (`iswap`, `sqrtswap`, `rxx`, `mcx`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Gate;
+    ///
+    /// assert_eq!(Gate::cx().name(), "cx");
+    /// assert_eq!(Gate::rz(0.5).name(), "rz");
+    /// ```
+    pub const fn name(&self) -> &'static str {
+        use GateType::*;
+
+        match self.gate_type {
+            I => "id",
+            X => "x",
+            Y => "y",
+            Z => "z",
+            H => "h",
+            S => "s",
+            Sdg => "sdg",
+            T => "t",
+            Tdg => "tdg",
+            Rx => "rx",
+            Ry => "ry",
+            Rz => "rz",
+            P => "p",
+            U => "U",
+            CX => "cx",
+            CY => "cy",
+            CZ => "cz",
+            CH => "ch",
+            CP => "cp",
+            CU => "cu",
+            Swap => "swap",
+            ISwap => "iswap",
+            SqrtSwap => "sqrtswap",
+            Rxx => "rxx",
+            Ryy => "ryy",
+            Rzz => "rzz",
+            CCX => "ccx",
+            CSwap => "cswap",
+            MCX => "mcx",
+            Measure => "measure",
+            Reset => "reset",
+            Barrier => "barrier",
+            Snapshot => "snapshot",
+        }
+    }
+
     /// Returns the number of qubits this gate operates on.
     #[inline]
     pub const fn num_qubits(&self) -> usize {
         use GateType::*;
         match self.gate_type {
             I | X | Y | Z | H | S | Sdg | T | Tdg | Rx | Ry | Rz | P | U | Measure | Reset => 1,
-            CX | CY | CZ | CH | CP | CU | Swap | ISwap | SqrtSwap => 2,
+            CX | CY | CZ | CH | CP | CU | Swap | ISwap | SqrtSwap | Rxx | Ryy | Rzz => 2,
             CCX | CSwap => 3,
-            Barrier => 0, // Barrier can span any number
+            Barrier | MCX => 0, // These span any number of qubits
+            Snapshot => 0,
         }
     }
 
@@ -465,20 +883,302 @@ impl Gate {
         use GateType::*;
         matches!(
             self.gate_type,
-            CX | CY | CZ | CH | CP | CU | CCX | CSwap
+            CX | CY | CZ | CH | CP | CU | CCX | CSwap | MCX
         )
     }
 
     /// Returns true if this gate modifies the quantum state.
     #[inline]
     pub const fn is_unitary(&self) -> bool {
-        !matches!(self.gate_type, GateType::Measure | GateType::Reset | GateType::Barrier)
+        !matches!(
+            self.gate_type,
+            GateType::Measure | GateType::Reset | GateType::Barrier | GateType::Snapshot
+        )
+    }
+
+    /// Check that the gate's angles are finite.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidGateParams` if any angle is NaN or infinite.
+    pub fn check_params(&self) -> Result<()> {
+        if self.params.is_finite() {
+            return Ok(());
+        }
+        let gate = match self.gate_type {
+            GateType::Rx => "Rx",
+            GateType::Ry => "Ry",
+            GateType::Rz => "Rz",
+            GateType::P => "P",
+            GateType::U => "U",
+            GateType::CP => "CP",
+            GateType::CU => "CU",
+            GateType::Rxx => "Rxx",
+            GateType::Ryy => "Ryy",
+            GateType::Rzz => "Rzz",
+            _ => "gate",
+        };
+        Err(HomayaError::InvalidGateParams {
+            gate,
+            message: "angles must be finite, got NaN or infinity",
+        })
+    }
+
+    // ========== Validating constructors ==========
+
+    /// Rotation around X-axis, rejecting a non-finite angle.
+    pub fn try_rx(theta: f64) -> Result<Self> {
+        Self::checked(Self::rx(theta))
+    }
+
+    /// Rotation around Y-axis, rejecting a non-finite angle.
+    pub fn try_ry(theta: f64) -> Result<Self> {
+        Self::checked(Self::ry(theta))
+    }
+
+    /// Rotation around Z-axis, rejecting a non-finite angle.
+    pub fn try_rz(theta: f64) -> Result<Self> {
+        Self::checked(Self::rz(theta))
+    }
+
+    /// Phase gate, rejecting a non-finite angle.
+    pub fn try_p(theta: f64) -> Result<Self> {
+        Self::checked(Self::p(theta))
+    }
+
+    /// General single-qubit unitary, rejecting non-finite angles.
+    pub fn try_u(theta: f64, phi: f64, lambda: f64) -> Result<Self> {
+        Self::checked(Self::u(theta, phi, lambda))
+    }
+
+    /// Controlled phase gate, rejecting a non-finite angle.
+    pub fn try_cp(theta: f64) -> Result<Self> {
+        Self::checked(Self::cp(theta))
+    }
+
+    /// Pass `gate` through [`Gate::check_params`].
+    fn checked(gate: Self) -> Result<Self> {
+        gate.check_params().map(|()| gate)
     }
 }
 
+impl TryFrom<GateType> for StandardGate {
+    type Error = HomayaError;
+
+    /// Map to the `homaya-types` vocabulary.
+    ///
+    /// Fails for parameterized gates, gates `StandardGate` lacks (such as
+    /// CU, iSWAP and MCX) and non-unitary operations.
+    fn try_from(gate_type: GateType) -> Result<Self> {
+        use GateType::*;
+
+        Ok(match gate_type {
+            I => StandardGate::I,
+            X => StandardGate::X,
+            Y => StandardGate::Y,
+            Z => StandardGate::Z,
+            H => StandardGate::H,
+            S => StandardGate::S,
+            Sdg => StandardGate::Sdg,
+            T => StandardGate::T,
+            Tdg => StandardGate::Tdg,
+            CX => StandardGate::CX,
+            CY => StandardGate::CY,
+            CZ => StandardGate::CZ,
+            CH => StandardGate::CH,
+            Swap => StandardGate::Swap,
+            CCX => StandardGate::CCX,
+            CSwap => StandardGate::CSwap,
+            _ => {
+                return Err(HomayaError::NotSupported {
+                    operation: "only fixed (non-parameterized) unitary gates map to StandardGate",
+                })
+            }
+        })
+    }
+}
+
+impl From<StandardGate> for GateType {
+    fn from(gate: StandardGate) -> Self {
+        match gate {
+            StandardGate::I => GateType::I,
+            StandardGate::X => GateType::X,
+            StandardGate::Y => GateType::Y,
+            StandardGate::Z => GateType::Z,
+            StandardGate::H => GateType::H,
+            StandardGate::S => GateType::S,
+            StandardGate::Sdg => GateType::Sdg,
+            StandardGate::T => GateType::T,
+            StandardGate::Tdg => GateType::Tdg,
+            StandardGate::CX => GateType::CX,
+            StandardGate::CY => GateType::CY,
+            StandardGate::CZ => GateType::CZ,
+            StandardGate::CH => GateType::CH,
+            StandardGate::Swap => GateType::Swap,
+            StandardGate::CCX => GateType::CCX,
+            StandardGate::CSwap => GateType::CSwap,
+        }
+    }
+}
+
+/// Embed a single-qubit matrix as a two-qubit gate controlled on bit 0.
+fn controlled(u: [[Complex; 2]; 2]) -> [[Complex; 4]; 4] {
+    let zero = Complex::ZERO;
+    let one = Complex::ONE;
+    [
+        [one, zero, zero, zero],
+        [zero, u[0][0], zero, u[0][1]],
+        [zero, zero, one, zero],
+        [zero, u[1][0], zero, u[1][1]],
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::PI;
+
+    fn matmul4(a: &[[Complex; 4]; 4], b: &[[Complex; 4]; 4]) -> [[Complex; 4]; 4] {
+        let mut out = [[Complex::ZERO; 4]; 4];
+        for (r, row) in out.iter_mut().enumerate() {
+            for (c, entry) in row.iter_mut().enumerate() {
+                *entry = (0..4).fold(Complex::ZERO, |acc, k| acc + a[r][k] * b[k][c]);
+            }
+        }
+        out
+    }
+
+    fn is_identity4(m: &[[Complex; 4]; 4]) -> bool {
+        (0..4).all(|r| (0..4).all(|c| m[r][c].approx_eq(if r == c { Complex::ONE } else { Complex::ZERO }, 1e-12)))
+    }
+
+    #[test]
+    fn test_standard_gate_conversion() {
+        assert_eq!(GateType::from(StandardGate::H), GateType::H);
+        assert_eq!(StandardGate::try_from(GateType::CSwap).unwrap(), StandardGate::CSwap);
+        assert!(matches!(
+            StandardGate::try_from(GateType::Rx),
+            Err(HomayaError::NotSupported { .. })
+        ));
+        assert!(StandardGate::try_from(GateType::Measure).is_err());
+
+        // Every StandardGate survives the round trip with the same arity
+        for gate in [StandardGate::I, StandardGate::Tdg, StandardGate::CH, StandardGate::Swap, StandardGate::CCX] {
+            let gate_type = GateType::from(gate);
+            assert_eq!(StandardGate::try_from(gate_type).unwrap(), gate);
+            assert_eq!(Gate { gate_type, params: GateParams::None }.num_qubits(), gate.num_qubits());
+        }
+    }
+
+    #[test]
+    fn test_names() {
+        assert_eq!(Gate::h().name(), "h");
+        assert_eq!(Gate::i().name(), "id");
+        assert_eq!(Gate::cx().name(), "cx");
+        assert_eq!(Gate::rx(0.1).name(), "rx");
+        assert_eq!(Gate::measure().name(), "measure");
+
+        use GateType::*;
+        let all = [
+            I, X, Y, Z, H, S, Sdg, T, Tdg, Rx, Ry, Rz, P, U, CX, CY, CZ, CH, CP, CU, Swap, ISwap, SqrtSwap, Rxx, Ryy,
+            Rzz, CCX, CSwap, MCX, Measure, Reset, Barrier, Snapshot,
+        ];
+        for gate_type in all {
+            let gate = Gate { gate_type, params: GateParams::None };
+            assert!(!gate.name().is_empty(), "{:?} has no name", gate_type);
+        }
+    }
+
+    #[test]
+    fn test_dagger() {
+        assert_eq!(Gate::s().dagger(), Gate::sdg());
+        assert_eq!(Gate::sdg().dagger(), Gate::s());
+        assert_eq!(Gate::tdg().dagger(), Gate::t());
+        assert_eq!(Gate::p(0.3).dagger(), Gate::p(-0.3));
+        assert_eq!(Gate::cp(0.3).dagger(), Gate::cp(-0.3));
+        assert_eq!(Gate::u(0.1, 0.2, 0.3).dagger(), Gate::u(-0.1, -0.3, -0.2));
+        for g in [Gate::h(), Gate::cx(), Gate::cz(), Gate::swap(), Gate::ccx(), Gate::cswap()] {
+            assert_eq!(g.dagger(), g);
+        }
+
+        // U · U† = I
+        let u = Gate::u(0.4, 1.1, -0.7).matrix_2x2().unwrap();
+        let v = Gate::u(0.4, 1.1, -0.7).dagger().matrix_2x2().unwrap();
+        assert!((u[0][0] * v[0][0] + u[0][1] * v[1][0]).approx_eq(Complex::ONE, 1e-12));
+        assert!((u[0][0] * v[0][1] + u[0][1] * v[1][1]).approx_eq(Complex::ZERO, 1e-12));
+        assert!((u[1][0] * v[0][0] + u[1][1] * v[1][0]).approx_eq(Complex::ZERO, 1e-12));
+        assert!((u[1][0] * v[0][1] + u[1][1] * v[1][1]).approx_eq(Complex::ONE, 1e-12));
+    }
+
+    #[test]
+    fn test_swap_matrix_squares_to_identity() {
+        let m = Gate::swap().matrix_4x4().unwrap();
+        assert!(is_identity4(&matmul4(&m, &m)));
+    }
+
+    #[test]
+    fn test_cx_matrix_truth_table() {
+        // Index = control + 2·target: |c=1,t=0⟩ (1) ↔ |c=1,t=1⟩ (3)
+        let m = Gate::cx().matrix_4x4().unwrap();
+        for (input, output) in [(0, 0), (1, 3), (2, 2), (3, 1)] {
+            for (row, entries) in m.iter().enumerate() {
+                let expected = if row == output { Complex::ONE } else { Complex::ZERO };
+                assert_eq!(entries[input], expected, "column {} row {}", input, row);
+            }
+        }
+    }
+
+    #[test]
+    fn test_two_qubit_matrices_are_unitary() {
+        let gate = |gate_type| Gate { gate_type, params: GateParams::None };
+        let sqrt_swap = gate(GateType::SqrtSwap).matrix_4x4().unwrap();
+        assert_eq!(matmul4(&sqrt_swap, &sqrt_swap), Gate::swap().matrix_4x4().unwrap());
+
+        for g in [
+            Gate::cx(),
+            Gate::cy(),
+            Gate::cz(),
+            Gate::ch(),
+            Gate::cp(0.7),
+            Gate::cu(0.3, -1.2, 2.5),
+            Gate::swap(),
+            Gate::rxx(0.9),
+            Gate::ryy(-1.3),
+            Gate::rzz(2.1),
+            gate(GateType::ISwap),
+            gate(GateType::SqrtSwap),
+        ] {
+            let m = g.matrix_4x4().unwrap();
+            let mut dagger = [[Complex::ZERO; 4]; 4];
+            for r in 0..4 {
+                for c in 0..4 {
+                    dagger[r][c] = m[c][r].conj();
+                }
+            }
+            assert!(is_identity4(&matmul4(&m, &dagger)), "{:?} is not unitary", g.gate_type);
+        }
+
+        assert!(Gate::h().matrix_4x4().is_none());
+        assert!(Gate::ccx().matrix_4x4().is_none());
+    }
+
+    #[test]
+    fn test_three_qubit_matrices() {
+        // Toffoli flips b2 only from |b0=1,b1=1⟩; Fredkin swaps b1, b2 when b0=1
+        let ccx = Gate::ccx().matrix_8x8().unwrap();
+        let cswap = Gate::cswap().matrix_8x8().unwrap();
+        for (input, ccx_out, cswap_out) in [(0b011, 0b111, 0b101), (0b111, 0b011, 0b111), (0b101, 0b101, 0b011), (0b110, 0b110, 0b110)] {
+            assert_eq!(ccx[ccx_out][input], Complex::ONE);
+            assert_eq!(cswap[cswap_out][input], Complex::ONE);
+        }
+        for m in [ccx, cswap] {
+            let ones = m.iter().flatten().filter(|&&z| z == Complex::ONE).count();
+            assert_eq!(ones, 8);
+        }
+
+        assert!(Gate::cx().matrix_8x8().is_none());
+        assert!(Gate::mcx().matrix_8x8().is_none());
+    }
 
     #[test]
     fn test_hadamard_matrix() {
@@ -540,4 +1240,89 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_t_phase_constants_match_trig() {
+        let t = Gate::t().matrix_2x2().unwrap();
+        let tdg = Gate::tdg().matrix_2x2().unwrap();
+
+        assert!(t[1][1].approx_eq(Complex::from_polar(1.0, PI / 4.0), 1e-15));
+        assert!(tdg[1][1].approx_eq(Complex::from_polar(1.0, -PI / 4.0), 1e-15));
+        assert_eq!(t[0][0], Complex::ONE);
+        assert_eq!(tdg[0][0], Complex::ONE);
+    }
+
+    #[test]
+    fn test_eigenvalues() {
+        let pm = [Complex::ONE, -Complex::ONE];
+        assert_eq!(Gate::z().eigenvalues(), Some(pm));
+        assert_eq!(Gate::h().eigenvalues(), Some(pm));
+
+        let theta = 0.7;
+        let ev = Gate::rz(theta).eigenvalues().unwrap();
+        assert!(ev[0].approx_eq(Complex::from_polar(1.0, -theta / 2.0), 1e-10));
+        assert!(ev[1].approx_eq(Complex::from_polar(1.0, theta / 2.0), 1e-10));
+
+        assert!(Gate::cx().eigenvalues().is_none());
+    }
+
+    #[test]
+    fn test_eigenvalues_u_satisfy_characteristic_equation() {
+        let g = Gate::u(1.1, 0.4, -0.9);
+        let m = g.matrix_2x2().unwrap();
+        let trace = m[0][0] + m[1][1];
+        let det = m[0][0] * m[1][1] - m[0][1] * m[1][0];
+
+        for l in g.eigenvalues().unwrap() {
+            // Unitary eigenvalues lie on the unit circle
+            assert!((l.abs() - 1.0).abs() < 1e-10);
+            assert!((l * l - trace * l + det).is_zero(1e-10));
+        }
+    }
+
+    #[test]
+    fn test_gate_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let hash = |gate: &Gate| {
+            let mut hasher = DefaultHasher::new();
+            gate.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        assert_eq!(hash(&Gate::rz(0.25)), hash(&Gate::rz(0.25)));
+        assert_ne!(hash(&Gate::rz(0.25)), hash(&Gate::rz(0.5)));
+        assert_ne!(hash(&Gate::rz(0.25)), hash(&Gate::rx(0.25)));
+        // Equal under `==`, so they must hash alike
+        assert_eq!(Gate::rz(0.0), Gate::rz(-0.0));
+        assert_eq!(hash(&Gate::rz(0.0)), hash(&Gate::rz(-0.0)));
+
+        let mut cache = std::collections::HashMap::new();
+        cache.insert(Gate::rz(0.25), 1);
+        assert_eq!(cache.get(&Gate::rz(0.25)), Some(&1));
+    }
+
+    #[test]
+    fn test_nan_gate_equals_itself() {
+        let gate = Gate::rz(f64::NAN);
+        assert_eq!(gate, gate.clone());
+        assert_ne!(gate, Gate::rz(0.0));
+
+        let mut cache = std::collections::HashMap::new();
+        cache.insert(gate.clone(), 1);
+        assert_eq!(cache.get(&gate), Some(&1));
+    }
+
+    #[test]
+    fn test_try_constructors_reject_non_finite() {
+        assert_eq!(Gate::try_rx(0.5), Ok(Gate::rx(0.5)));
+        assert!(Gate::try_rx(f64::NAN).is_err());
+        assert!(Gate::try_rz(f64::INFINITY).is_err());
+        assert!(Gate::try_u(0.1, f64::NEG_INFINITY, 0.2).is_err());
+        assert!(Gate::try_cp(1.0).is_ok());
+
+        let err = Gate::try_ry(f64::NAN).unwrap_err();
+        assert_eq!(err.to_string(), "invalid parameters for Ry: angles must be finite, got NaN or infinity");
+    }
 }
@@ -0,0 +1,121 @@
+//! Versioned JSON circuit format.
+//!
+//! Circuits are stored as `{"homaya_version": 1, "circuit": {...}}`, where
+//! `circuit` is the serde encoding of [`Circuit`]. The version is bumped
+//! whenever that encoding changes incompatibly, so old builds reject new
+//! files instead of misreading them.
+
+use crate::{Circuit, HomayaError, Result};
+
+/// Version of the JSON envelope written by [`Circuit::to_json`].
+const FORMAT_VERSION: u64 = 1;
+
+/// On-disk envelope, as written.
+#[derive(serde::Serialize)]
+struct Envelope<'a> {
+    homaya_version: u64,
+    circuit: &'a Circuit,
+}
+
+/// On-disk envelope, as read: the circuit is decoded only once the
+/// version is known.
+#[derive(serde::Deserialize)]
+struct RawEnvelope {
+    homaya_version: u64,
+    circuit: serde_json::Value,
+}
+
+impl Circuit {
+    /// Serialize the circuit to versioned JSON.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let json = Circuit::new(2).h(0).cx(0, 1).to_json();
+    /// assert!(json.starts_with("{\"homaya_version\":1,"));
+    /// ```
+    pub fn to_json(&self) -> std::string::String {
+        let envelope = Envelope {
+            homaya_version: FORMAT_VERSION,
+            circuit: self,
+        };
+        serde_json::to_string(&envelope).expect("circuits always serialize")
+    }
+
+    /// Parse a circuit written by [`to_json`](Self::to_json).
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnsupportedFormatVersion` if the file was written in another
+    /// format version, `JsonParse` if it is not a valid envelope or
+    /// circuit, and any error from [`validate`](Self::validate).
+    pub fn from_json(s: &str) -> Result<Circuit> {
+        let parse_error = |e: serde_json::Error| HomayaError::JsonParse {
+            message: std::string::ToString::to_string(&e),
+        };
+
+        let raw: RawEnvelope = serde_json::from_str(s).map_err(parse_error)?;
+        if raw.homaya_version != FORMAT_VERSION {
+            return Err(HomayaError::UnsupportedFormatVersion {
+                version: raw.homaya_version,
+                supported: FORMAT_VERSION,
+            });
+        }
+
+        let circuit: Circuit = serde_json::from_value(raw.circuit).map_err(parse_error)?;
+        circuit.validate()?;
+        Ok(circuit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ghz_round_trip() {
+        let circuit = Circuit::new(3)
+            .h(0)
+            .cx(0, 1)
+            .cx(1, 2)
+            .rz(0.25, 2)
+            .barrier(&[])
+            .measure_all()
+            .x_if(0, 2, 1)
+            .snapshot("end");
+
+        let json = circuit.to_json();
+        let parsed = Circuit::from_json(&json).unwrap();
+        assert_eq!(parsed.num_qubits(), circuit.num_qubits());
+        assert_eq!(parsed.num_clbits(), circuit.num_clbits());
+        assert_eq!(parsed.instructions(), circuit.instructions());
+        assert_eq!(parsed.to_json(), json);
+    }
+
+    #[test]
+    fn test_unknown_version_is_rejected() {
+        let json = Circuit::new(1).x(0).to_json().replacen("\"homaya_version\":1", "\"homaya_version\":2", 1);
+        assert_eq!(
+            Circuit::from_json(&json).unwrap_err(),
+            HomayaError::UnsupportedFormatVersion { version: 2, supported: 1 }
+        );
+
+        // The version is checked before the circuit is decoded
+        let json = r#"{"homaya_version": 9, "circuit": {"future": true}}"#;
+        assert!(matches!(
+            Circuit::from_json(json),
+            Err(HomayaError::UnsupportedFormatVersion { version: 9, .. })
+        ));
+    }
+
+    #[test]
+    fn test_malformed_json() {
+        assert!(matches!(Circuit::from_json("{"), Err(HomayaError::JsonParse { .. })));
+        assert!(matches!(
+            Circuit::from_json(r#"{"circuit": {}}"#),
+            Err(HomayaError::JsonParse { .. })
+        ));
+    }
+}
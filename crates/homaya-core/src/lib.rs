@@ -6,6 +6,7 @@
 //! - [`Complex`] - High-performance complex number operations
 //! - [`Gate`] - Quantum gate definitions
 //! - [`Circuit`] - Quantum circuit builder
+//! - [`QuantumRegister`] - Named slices of a circuit's qubits
 //!
 //! ## Philosophy
 //!
@@ -29,11 +30,22 @@ mod complex;
 mod gate;
 mod circuit;
 mod error;
+mod register;
+mod qasm;
+#[cfg(feature = "serde")]
+mod json;
+mod draw;
+pub mod commute;
+pub mod fmt;
+mod optimize;
+mod decompose;
 
 pub use complex::Complex;
 pub use gate::{Gate, GateType, GateParams};
 pub use circuit::{Circuit, Instruction};
 pub use error::HomayaError;
+pub use register::QuantumRegister;
+pub use homaya_types::Optimizable;
 
 /// Result type for HOMAYA operations
 pub type Result<T> = core::result::Result<T, HomayaError>;
@@ -48,7 +60,7 @@ pub const TAU: f64 = core::f64::consts::TAU;
 pub const SQRT_2: f64 = core::f64::consts::SQRT_2;
 
 /// 1/√2, used frequently in quantum gates
-pub const INV_SQRT_2: f64 = 0.7071067811865476;
+pub const INV_SQRT_2: f64 = core::f64::consts::FRAC_1_SQRT_2;
 
 #[cfg(test)]
 mod tests {
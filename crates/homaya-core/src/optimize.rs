@@ -0,0 +1,564 @@
+//! Circuit optimization passes.
+//!
+//! Each pass returns a new circuit that is equivalent to the input (up to
+//! global phase) but uses fewer or cheaper gates. Barriers are respected as
+//! fences: no pass cancels or merges gates across one.
+
+use homaya_types::Optimizable;
+
+use crate::commute::instructions_commute;
+use crate::{Circuit, GateParams, GateType, Instruction};
+
+/// Tolerance for treating a rotation angle as zero.
+const ANGLE_EPSILON: f64 = 1e-12;
+
+/// Check whether `b` undoes `a` (their product is the identity).
+fn is_inverse_pair(a: &Instruction, b: &Instruction) -> bool {
+    use GateType::*;
+
+    if a.condition != b.condition {
+        return false;
+    }
+
+    let same_qubits = a.qubits == b.qubits;
+    // Symmetric two-qubit gates don't care about operand order
+    let swapped_qubits = a.qubits.len() == 2
+        && b.qubits.len() == 2
+        && a.qubits[0] == b.qubits[1]
+        && a.qubits[1] == b.qubits[0];
+
+    match (a.gate.gate_type, b.gate.gate_type) {
+        (CZ, CZ) | (Swap, Swap) => same_qubits || swapped_qubits,
+        (I, I) | (X, X) | (Y, Y) | (Z, Z) | (H, H) | (CX, CX) | (CY, CY) | (CH, CH) | (MCX, MCX) => same_qubits,
+        // Toffoli controls are interchangeable
+        (CCX, CCX) => {
+            same_qubits
+                || (a.qubits[0] == b.qubits[1] && a.qubits[1] == b.qubits[0] && a.qubits[2] == b.qubits[2])
+        }
+        // Fredkin targets are interchangeable
+        (CSwap, CSwap) => {
+            same_qubits
+                || (a.qubits[0] == b.qubits[0] && a.qubits[1] == b.qubits[2] && a.qubits[2] == b.qubits[1])
+        }
+        (S, Sdg) | (Sdg, S) | (T, Tdg) | (Tdg, T) => same_qubits,
+        (Rx, Rx) | (Ry, Ry) | (Rz, Rz) | (P, P) => same_qubits && angles_cancel(&a.gate.params, &b.gate.params),
+        (CP, CP) => (same_qubits || swapped_qubits) && angles_cancel(&a.gate.params, &b.gate.params),
+        _ => false,
+    }
+}
+
+/// Check whether two single-angle parameters sum to zero.
+fn angles_cancel(a: &GateParams, b: &GateParams) -> bool {
+    match (a, b) {
+        (GateParams::Angle(x), GateParams::Angle(y)) => (x + y).abs() < ANGLE_EPSILON,
+        (GateParams::Parameter { name: a, scale: x }, GateParams::Parameter { name: b, scale: y }) => {
+            a == b && (x + y).abs() < ANGLE_EPSILON
+        }
+        _ => false,
+    }
+}
+
+/// Check whether a parameter is a zero angle.
+fn is_zero_angle(params: &GateParams) -> bool {
+    match params {
+        GateParams::Angle(x) => x.abs() < ANGLE_EPSILON,
+        GateParams::Parameter { scale, .. } => scale.abs() < ANGLE_EPSILON,
+        _ => false,
+    }
+}
+
+/// Check whether an instruction is the identity (up to global phase).
+fn is_identity(inst: &Instruction) -> bool {
+    use GateType::*;
+
+    match inst.gate.gate_type {
+        I => true,
+        Rx | Ry | Rz | P | CP | Rxx | Ryy | Rzz => is_zero_angle(&inst.gate.params),
+        _ => false,
+    }
+}
+
+/// Merge two rotations about the same axis on the same qubits, if possible.
+fn merge_rotations(a: &Instruction, b: &Instruction) -> Option<Instruction> {
+    use GateType::*;
+
+    if a.condition != b.condition || a.gate.gate_type != b.gate.gate_type {
+        return None;
+    }
+
+    let same_qubits = a.qubits == b.qubits;
+    let swapped_qubits = a.qubits.len() == 2 && a.qubits[0] == b.qubits[1] && a.qubits[1] == b.qubits[0];
+    let qubits_match = match a.gate.gate_type {
+        Rx | Ry | Rz | P => same_qubits,
+        CP | Rxx | Ryy | Rzz => same_qubits || swapped_qubits,
+        _ => false,
+    };
+    if !qubits_match {
+        return None;
+    }
+
+    let params = match (&a.gate.params, &b.gate.params) {
+        (GateParams::Angle(x), GateParams::Angle(y)) => GateParams::Angle(x + y),
+        (GateParams::Parameter { name: m, scale: x }, GateParams::Parameter { name: n, scale: y }) if m == n => {
+            GateParams::Parameter { name: m.clone(), scale: x + y }
+        }
+        _ => return None,
+    };
+
+    let mut merged = a.clone();
+    merged.gate.params = params;
+    Some(merged)
+}
+
+/// Check whether `barrier` fences off `inst`.
+fn fences(barrier: &Instruction, inst: &Instruction) -> bool {
+    barrier.qubits.is_empty() || barrier.qubits.iter().any(|q| inst.qubits.contains(q))
+}
+
+impl Circuit {
+    /// Cancel inverse gate pairs, looking through gates they commute with.
+    ///
+    /// Unlike adjacent-only cancellation, `X(0)·Rz(θ,1)·X(0)` reduces to
+    /// `Rz(θ,1)` because the rotation commutes with `X(0)`. Any gate that
+    /// does not commute with the candidate, or a barrier on a shared
+    /// qubit, blocks the search.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let circuit = Circuit::new(2).x(0).rz(0.5, 1).x(0).cancel_commuting();
+    /// assert_eq!(circuit.len(), 1);
+    /// ```
+    pub fn cancel_commuting(self) -> Circuit {
+        let mut kept: Vec<Option<Instruction>> = Vec::with_capacity(self.len());
+
+        for inst in self.instructions() {
+            let mut cancelled = false;
+
+            if inst.gate.is_unitary() {
+                for slot in kept.iter_mut().rev() {
+                    let Some(prev) = slot else { continue };
+                    if prev.is_barrier() && fences(prev, inst) {
+                        break;
+                    }
+                    if is_inverse_pair(prev, inst) {
+                        *slot = None;
+                        cancelled = true;
+                        break;
+                    }
+                    if !instructions_commute(prev, inst) {
+                        break;
+                    }
+                }
+            }
+
+            if !cancelled {
+                kept.push(Some(inst.clone()));
+            }
+        }
+
+        self.with_instructions(kept.into_iter().flatten().collect())
+    }
+
+    /// Apply adjacent-gate simplifications.
+    ///
+    /// Working through the circuit once, with cascades (`H·X·X·H` empties
+    /// out):
+    /// - identity gates and zero-angle rotations are dropped;
+    /// - a gate directly followed on its qubits by its inverse cancels
+    ///   (H·H, X·X, CX·CX on the same operands, S·Sdg, ...);
+    /// - consecutive rotations about the same axis on the same qubits merge
+    ///   into one, with the angles summed.
+    ///
+    /// Two gates are adjacent when nothing between them touches their
+    /// qubits, writes a classical bit they are conditioned on, or is a
+    /// barrier or snapshot over every qubit. Unlike
+    /// [`Circuit::cancel_commuting`], this never looks through other gates.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let circuit = Circuit::new(2).h(0).rz(0.25, 1).h(0).rz(0.5, 1).peephole();
+    /// assert_eq!(circuit.len(), 1);
+    /// ```
+    pub fn peephole(self) -> Circuit {
+        self.combine_adjacent(is_identity, |a, b| {
+            if is_inverse_pair(a, b) {
+                Some(None)
+            } else {
+                merge_rotations(a, b).map(Some)
+            }
+        })
+    }
+
+    /// Merge consecutive single-qubit rotations about the same axis.
+    ///
+    /// Runs of Rx, Ry, Rz or P on one qubit, with no other instruction on
+    /// that qubit in between, become a single rotation by the summed angle.
+    /// Rotations that come out (or start) at zero are removed, so
+    /// `Rz(θ)·Rz(−θ)` disappears entirely. Adjacency is as in
+    /// [`Circuit::peephole`]: any gate on the qubit, such as a CX, blocks
+    /// fusion.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let circuit = Circuit::new(2).rx(0.1, 0).rz(0.5, 1).rx(0.2, 0).fuse_rotations();
+    /// assert_eq!(circuit.len(), 2);
+    /// ```
+    pub fn fuse_rotations(self) -> Circuit {
+        use GateType::*;
+
+        let is_rotation = |inst: &Instruction| matches!(inst.gate.gate_type, Rx | Ry | Rz | P);
+        self.combine_adjacent(
+            |inst| is_rotation(inst) && is_zero_angle(&inst.gate.params),
+            |a, b| if is_rotation(a) { merge_rotations(a, b).map(Some) } else { None },
+        )
+    }
+
+    /// Simplify pairs of adjacent instructions in one pass.
+    ///
+    /// Instructions for which `dropped` holds are removed. `combine(a, b)`
+    /// for an instruction `b` and its adjacent predecessor `a` returns
+    /// `None` to keep both, `Some(None)` to remove both, or `Some(Some(c))`
+    /// to replace both with `c`.
+    fn combine_adjacent(
+        self,
+        dropped: impl Fn(&Instruction) -> bool,
+        combine: impl Fn(&Instruction, &Instruction) -> Option<Option<Instruction>>,
+    ) -> Circuit {
+        let mut kept: Vec<Instruction> = Vec::with_capacity(self.len());
+
+        for inst in self.instructions() {
+            if dropped(inst) {
+                continue;
+            }
+
+            if inst.gate.is_unitary() {
+                // The latest kept instruction this one cannot move past
+                let blocker = kept.iter().rposition(|prev| {
+                    prev.qubits.is_empty()
+                        || prev.qubits.iter().any(|q| inst.qubits.contains(q))
+                        || inst.condition.is_some_and(|(c, _)| prev.clbits.contains(&c))
+                });
+
+                if let Some(j) = blocker {
+                    if let Some(combined) = combine(&kept[j], inst) {
+                        match combined {
+                            Some(c) if !dropped(&c) => kept[j] = c,
+                            _ => {
+                                kept.remove(j);
+                            }
+                        }
+                        continue;
+                    }
+                }
+            }
+
+            kept.push(inst.clone());
+        }
+
+        self.with_instructions(kept)
+    }
+
+    /// Find measurements of qubits left untouched since their last measurement.
+    ///
+    /// Returns instruction indices. Re-measuring a collapsed qubit just
+    /// copies the earlier result, which is usually a mistake. Barriers on
+    /// the qubit count as touching it. The measurement must also leave its
+    /// classical bit as it was: the bit is either unwritten so far, or was
+    /// last written by a measurement of the same qubit with no conditioned
+    /// instruction reading it since.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let circuit = Circuit::new(1).h(0).measure(0, 0).measure(0, 1);
+    /// assert_eq!(circuit.redundant_measurements(), vec![2]);
+    /// ```
+    pub fn redundant_measurements(&self) -> Vec<usize> {
+        // Whether each qubit's most recent operation was a measurement
+        let mut measured = std::vec![false; self.num_qubits()];
+        // Whether each clbit has been written, and by a measurement of which
+        // qubit if its value is still that qubit's last result
+        let mut written = std::vec![false; self.num_clbits()];
+        let mut holds: Vec<Option<usize>> = std::vec![None; self.num_clbits()];
+        let mut redundant = Vec::new();
+
+        for (i, inst) in self.instructions().iter().enumerate() {
+            let is_measure = inst.gate.gate_type == GateType::Measure;
+            let keeps_clbits = inst.clbits.iter().all(|&c| {
+                !written.get(c).copied().unwrap_or(true) || inst.qubits.iter().all(|&q| holds.get(c) == Some(&Some(q)))
+            });
+            if is_measure
+                && !inst.qubits.is_empty()
+                && inst.qubits.iter().all(|&q| measured.get(q) == Some(&true))
+                && keeps_clbits
+            {
+                redundant.push(i);
+            }
+
+            if let Some(h) = inst.condition.and_then(|(c, _)| holds.get_mut(c)) {
+                *h = None;
+            }
+            for &c in &inst.clbits {
+                if let (Some(w), Some(h)) = (written.get_mut(c), holds.get_mut(c)) {
+                    *w = true;
+                    *h = if is_measure { inst.qubits.first().copied() } else { None };
+                }
+            }
+
+            if inst.is_barrier() && inst.qubits.is_empty() {
+                measured.fill(false);
+            }
+            for &q in &inst.qubits {
+                if let Some(m) = measured.get_mut(q) {
+                    *m = is_measure;
+                }
+            }
+        }
+
+        redundant
+    }
+
+    /// Remove the measurements reported by [`Circuit::redundant_measurements`].
+    ///
+    /// Classical bits written only by removed measurements are dropped and
+    /// higher clbits are renumbered down to fill the gap. Measurements into
+    /// a bit that a conditioned gate reads are kept.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let circuit = Circuit::new(1).measure(0, 0).measure(0, 1).dedupe_measurements();
+    /// assert_eq!(circuit.len(), 1);
+    /// assert_eq!(circuit.num_clbits(), 1);
+    /// ```
+    pub fn dedupe_measurements(self) -> Circuit {
+        // A later condition may read the copy, so keep measurements into such bits
+        let read: Vec<usize> = self.instructions().iter().filter_map(|inst| inst.condition).map(|(c, _)| c).collect();
+        let mut redundant = self.redundant_measurements();
+        redundant.retain(|&i| !self.instructions()[i].clbits.iter().any(|c| read.contains(c)));
+        if redundant.is_empty() {
+            return self;
+        }
+
+        let mut kept: Vec<Instruction> = self
+            .instructions()
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !redundant.contains(i))
+            .map(|(_, inst)| inst.clone())
+            .collect();
+
+        let mut orphaned: Vec<usize> = redundant
+            .iter()
+            .flat_map(|&i| self.instructions()[i].clbits.iter().copied())
+            .filter(|c| !kept.iter().any(|inst| inst.clbits.contains(c)))
+            .collect();
+        orphaned.sort_unstable();
+        orphaned.dedup();
+
+        let shift = |c: usize| c - orphaned.iter().filter(|&&o| o < c).count();
+        for inst in &mut kept {
+            for c in &mut inst.clbits {
+                *c = shift(*c);
+            }
+            if let Some((c, _)) = &mut inst.condition {
+                *c = shift(*c);
+            }
+        }
+
+        let mut circuit = self.with_instructions(kept);
+        circuit.set_num_clbits(self.num_clbits() - orphaned.len());
+        circuit
+    }
+}
+
+impl Optimizable for Circuit {
+    /// Simplify with [`Circuit::peephole`].
+    fn optimize(&self) -> Self {
+        self.clone().peephole()
+    }
+
+    /// Number of instructions.
+    fn cost(&self) -> usize {
+        self.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use homaya_types::Optimizable;
+
+    use crate::{Circuit, GateParams, GateType};
+
+    #[test]
+    fn test_cancel_through_commuting_gate() {
+        let theta = 0.42;
+        let circuit = Circuit::new(2).x(0).rz(theta, 1).x(0).cancel_commuting();
+
+        assert_eq!(circuit.len(), 1);
+        let inst = &circuit.instructions()[0];
+        assert_eq!(inst.gate.gate_type, GateType::Rz);
+        assert_eq!(inst.qubits, vec![1]);
+    }
+
+    #[test]
+    fn test_non_commuting_gate_blocks_cancellation() {
+        let circuit = Circuit::new(1).x(0).z(0).x(0).cancel_commuting();
+        assert_eq!(circuit.len(), 3);
+    }
+
+    #[test]
+    fn test_barrier_fences_cancellation() {
+        let circuit = Circuit::new(1).h(0).barrier(&[0]).h(0).cancel_commuting();
+        assert_eq!(circuit.len(), 3);
+
+        let circuit = Circuit::new(2).h(0).barrier(&[1]).h(0).cancel_commuting();
+        assert_eq!(circuit.len(), 1);
+    }
+
+    #[test]
+    fn test_snapshot_fences_cancellation() {
+        // The snapshot would otherwise record a different state
+        let circuit = Circuit::new(2).h(0).snapshot("mid").h(0).cancel_commuting();
+        assert_eq!(circuit.len(), 3);
+    }
+
+    #[test]
+    fn test_cancel_through_cx_control() {
+        // Z on the control commutes with CX; S·Sdg cancel across it
+        let circuit = Circuit::new(2).s(0).cx(0, 1).z(0).sdg(0).cancel_commuting();
+        let types: Vec<GateType> = circuit.instructions().iter().map(|i| i.gate.gate_type).collect();
+        assert_eq!(types, [GateType::CX, GateType::Z]);
+    }
+
+    #[test]
+    fn test_optimize_cancels_hadamards() {
+        let circuit = Circuit::new(1).h(0).h(0);
+        assert_eq!(circuit.cost(), 2);
+        assert_eq!(circuit.optimize().cost(), 0);
+
+        // Cascades: the X pair goes, then the H pair
+        let circuit = Circuit::new(2).h(0).x(0).x(0).h(0).cx(0, 1).cx(0, 1);
+        assert!(circuit.optimize().is_empty());
+
+        // Different operands do not cancel
+        assert_eq!(Circuit::new(2).cx(0, 1).cx(1, 0).optimize().cost(), 2);
+    }
+
+    #[test]
+    fn test_optimize_fuses_rotations() {
+        let (a, b) = (0.3, 0.9);
+        let circuit = Circuit::new(1).rz(a, 0).rz(b, 0).optimize();
+        assert_eq!(circuit.len(), 1);
+        let inst = &circuit.instructions()[0];
+        assert_eq!(inst.gate.gate_type, GateType::Rz);
+        assert_eq!(inst.gate.params, GateParams::Angle(a + b));
+
+        // Fusing to zero removes the gate; a different axis blocks fusion
+        assert!(Circuit::new(1).rx(a, 0).rx(-a, 0).optimize().is_empty());
+        assert_eq!(Circuit::new(1).rz(a, 0).rx(b, 0).rz(a, 0).optimize().len(), 3);
+        assert_eq!(Circuit::new(2).cp(a, 0, 1).cp(b, 1, 0).optimize().len(), 1);
+    }
+
+    #[test]
+    fn test_fuse_rotations() {
+        let circuit = Circuit::new(1).rz(0.1, 0).rz(0.2, 0).rz(0.3, 0).fuse_rotations();
+        assert_eq!(circuit.len(), 1);
+        match circuit.instructions()[0].gate.params {
+            GateParams::Angle(theta) => assert!((theta - 0.6).abs() < 1e-12),
+            ref other => panic!("expected an angle, got {:?}", other),
+        }
+
+        // Opposite rotations cancel completely
+        assert!(Circuit::new(1).rz(0.7, 0).rz(-0.7, 0).fuse_rotations().is_empty());
+        assert!(Circuit::new(1).p(0.7, 0).rz(0.0, 0).p(-0.7, 0).fuse_rotations().is_empty());
+
+        // Only rotations merge; H·H is left for other passes
+        assert_eq!(Circuit::new(1).h(0).h(0).fuse_rotations().len(), 2);
+    }
+
+    #[test]
+    fn test_fuse_rotations_blocked_by_cx() {
+        let circuit = Circuit::new(2).rz(0.4, 0).cx(0, 1).rz(0.4, 0).fuse_rotations();
+        assert_eq!(circuit.len(), 3);
+
+        // A CX elsewhere does not block
+        let circuit = Circuit::new(3).ry(0.4, 0).cx(1, 2).ry(0.4, 0).fuse_rotations();
+        let types: Vec<GateType> = circuit.instructions().iter().map(|i| i.gate.gate_type).collect();
+        assert_eq!(types, [GateType::Ry, GateType::CX]);
+    }
+
+    #[test]
+    fn test_optimize_drops_identities() {
+        let circuit = Circuit::new(2).i(0).rz(0.0, 1).h(1).p(0.0, 0).optimize();
+        assert_eq!(circuit.len(), 1);
+        assert_eq!(circuit.instructions()[0].gate.gate_type, GateType::H);
+    }
+
+    #[test]
+    fn test_optimize_respects_fences() {
+        assert_eq!(Circuit::new(1).h(0).barrier(&[]).h(0).optimize().len(), 3);
+        assert_eq!(Circuit::new(1).h(0).measure(0, 0).h(0).optimize().len(), 3);
+        // A measurement on another qubit can change the condition between the pair
+        let circuit = Circuit::new(2).x_if(1, 0, 1).measure(0, 0).x_if(1, 0, 1).optimize();
+        assert_eq!(circuit.len(), 3);
+    }
+
+    #[test]
+    fn test_double_measurement_collapses() {
+        let circuit = Circuit::new(2).h(0).measure(0, 0).measure(0, 1).measure(1, 2);
+        assert_eq!(circuit.redundant_measurements(), vec![2]);
+
+        let circuit = circuit.dedupe_measurements();
+        assert_eq!(circuit.len(), 3);
+        assert_eq!(circuit.num_clbits(), 2);
+        // Qubit 1's measurement moves down into the freed clbit
+        assert_eq!(circuit.instructions()[2].qubits, vec![1]);
+        assert_eq!(circuit.instructions()[2].clbits, vec![1]);
+    }
+
+    #[test]
+    fn test_remeasure_into_overwritten_clbit_is_kept() {
+        // c0 holds qubit 1's result when qubit 0 is measured into it again
+        let circuit = Circuit::with_clbits(2, 2).x(1).measure(1, 0).measure(0, 1).measure(0, 0);
+        assert!(circuit.redundant_measurements().is_empty());
+        assert_eq!(circuit.clone().dedupe_measurements().instructions(), circuit.instructions());
+
+        // Back into the bit it already wrote is still a copy
+        let circuit = Circuit::with_clbits(1, 1).measure(0, 0).measure(0, 0);
+        assert_eq!(circuit.redundant_measurements(), vec![1]);
+
+        // A conditioned read in between keeps it
+        let circuit = Circuit::with_clbits(2, 1).measure(0, 0).x_if(1, 0, 1).measure(0, 0);
+        assert!(circuit.redundant_measurements().is_empty());
+    }
+
+    #[test]
+    fn test_remeasure_after_gate_is_kept() {
+        let circuit = Circuit::new(1).measure(0, 0).x(0).measure(0, 1);
+        assert!(circuit.redundant_measurements().is_empty());
+
+        let circuit = circuit.dedupe_measurements();
+        assert_eq!(circuit.len(), 3);
+        assert_eq!(circuit.num_clbits(), 2);
+    }
+
+    #[test]
+    fn test_measure_is_not_cancelled() {
+        let circuit = Circuit::new(1).x(0).measure(0, 0).x(0).cancel_commuting();
+        assert_eq!(circuit.len(), 3);
+    }
+}
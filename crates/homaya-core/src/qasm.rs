@@ -0,0 +1,1212 @@
+//! OpenQASM export and import.
+//!
+//! Text formats for exchanging circuits with other quantum toolchains.
+//! OpenQASM 2.0 uses the gate names from the original `qelib1.inc`.
+
+use std::fmt::Write;
+
+use crate::{Circuit, Gate, GateParams, GateType, HomayaError, Instruction, Result, PI};
+
+/// Definition emitted when a circuit uses iSWAP, which has no `stdgates.inc` entry.
+///
+/// The two-qubit definitions below are valid in both OpenQASM 2.0 and 3.0.
+const ISWAP_DEF: &str = "gate iswap a, b { s a; s b; h a; cx a, b; cx b, a; h b; }";
+
+/// √SWAP as CX(a,b) · controlled-√X(b→a) · CX(a,b), with √X = H·S·H.
+const SQRT_SWAP_DEF: &str = "gate sqrtswap a, b { cx a, b; h a; cu1(pi/2) b, a; h a; cx a, b; }";
+
+/// XX coupling as a ZZ rotation conjugated by Hadamards.
+const RXX_DEF: &str = "gate rxx(theta) a, b { h a; h b; cx a, b; rz(theta) b; cx a, b; h a; h b; }";
+
+/// YY coupling as a ZZ rotation conjugated by Rx(π/2).
+const RYY_DEF: &str =
+    "gate ryy(theta) a, b { rx(pi/2) a; rx(pi/2) b; cx a, b; rz(theta) b; cx a, b; rx(-pi/2) a; rx(-pi/2) b; }";
+
+/// ZZ coupling, which `qelib1.inc` has but `stdgates.inc` lacks.
+const RZZ_DEF: &str = "gate rzz(theta) a, b { cx a, b; rz(theta) b; cx a, b; }";
+
+/// `gate` definitions for `cNx`, the X gate with `N` controls, for
+/// `from < N ≤ to`.
+///
+/// `qelib1.inc` stops at `ccx`, so wider MCX gates are built without
+/// ancillas: `cNx` is H · `cNu1(pi)` · H on the target, and the controlled
+/// phase `cNu1(λ)` recurses on one fewer control,
+/// `cu1(λ/2)` on the last control, `c(N-1)x` into it, `cu1(-λ/2)`,
+/// `c(N-1)x` again, then `c(N-1)u1(λ/2)` from the rest.
+/// Each `cNu1` is emitted before the `cNx` that uses it.
+fn mcx_defs(from: usize, to: usize) -> Vec<std::string::String> {
+    let mut defs = Vec::new();
+    for n in (from + 1).max(2)..=to {
+        let controls: Vec<std::string::String> = (0..n).map(|i| std::format!("c{}", i)).collect();
+        let (rest, last) = (controls[..n - 1].join(", "), &controls[n - 1]);
+        let all = controls.join(", ");
+        let inner_x = match n - 1 {
+            1 => "cx".into(),
+            2 => "ccx".into(),
+            m => std::format!("c{}x", m),
+        };
+        let inner_u1 = if n == 2 { "cu1".into() } else { std::format!("c{}u1", n - 1) };
+
+        defs.push(std::format!(
+            "gate c{n}u1(lambda) {all}, t {{ cu1(lambda/2) {last}, t; {inner_x} {rest}, {last}; \
+             cu1(-lambda/2) {last}, t; {inner_x} {rest}, {last}; {inner_u1}(lambda/2) {rest}, t; }}"
+        ));
+        if n >= 3 {
+            defs.push(std::format!("gate c{n}x {all}, t {{ h t; c{n}u1(pi) {all}, t; h t; }}"));
+        }
+    }
+    defs
+}
+
+/// Number of controls in an OpenQASM `cNx` name, for `N ≥ 3`.
+fn mcx_controls(name: &str) -> Option<usize> {
+    let n: usize = name.strip_prefix('c')?.strip_suffix('x')?.parse().ok()?;
+    (n >= 3).then_some(n)
+}
+
+impl Circuit {
+    /// Export the circuit as OpenQASM 3.0.
+    ///
+    /// Gates from `stdgates.inc` are emitted by name; controlled-U uses the
+    /// `ctrl @` modifier and √SWAP uses `pow(0.5) @ swap`. iSWAP and the
+    /// `rxx`, `ryy` and `rzz` couplings get a `gate` definition. Free
+    /// symbolic parameters are declared as `input angle` variables.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let qasm = Circuit::new(2).h(0).cx(0, 1).measure_all().to_qasm3();
+    /// assert!(qasm.starts_with("OPENQASM 3.0;"));
+    /// assert!(qasm.contains("c[1] = measure q[1];"));
+    /// ```
+    pub fn to_qasm3(&self) -> std::string::String {
+        let mut out = std::string::String::from("OPENQASM 3.0;\ninclude \"stdgates.inc\";\n");
+
+        let mut defined: Vec<GateType> = Vec::new();
+        for inst in self.instructions() {
+            let gate_type = inst.gate.gate_type;
+            let def = match gate_type {
+                GateType::ISwap => ISWAP_DEF,
+                GateType::Rxx => RXX_DEF,
+                GateType::Ryy => RYY_DEF,
+                GateType::Rzz => RZZ_DEF,
+                _ => continue,
+            };
+            if !defined.contains(&gate_type) {
+                out.push_str(def);
+                out.push('\n');
+                defined.push(gate_type);
+            }
+        }
+
+        for name in self.parameters() {
+            let _ = writeln!(out, "input angle {};", name);
+        }
+        let _ = writeln!(out, "qubit[{}] q;", self.num_qubits());
+        if self.num_clbits() > 0 {
+            let _ = writeln!(out, "bit[{}] c;", self.num_clbits());
+        }
+
+        for inst in self.instructions() {
+            match inst.condition {
+                Some((clbit, value)) => {
+                    let _ = writeln!(out, "if (c[{}] == {}) {{ {} }}", clbit, value, qasm3_line(inst));
+                }
+                None => {
+                    out.push_str(&qasm3_line(inst));
+                    out.push('\n');
+                }
+            }
+        }
+
+        out
+    }
+}
+
+impl Circuit {
+    /// Export the circuit as OpenQASM 2.0.
+    ///
+    /// Emits one statement per instruction using the `qelib1.inc` names
+    /// (`u1`, `u3`, `cu1`, `cu3` for the phase and U gates). Angles are
+    /// written at full precision; symbolic angles are written by name,
+    /// which OpenQASM 2.0 cannot declare, so [`bind`](Self::bind) them
+    /// first. Classical conditions become `if` statements; OpenQASM 2.0
+    /// only tests whole registers, so `if(c[i]==v)` for a circuit with
+    /// more than one classical bit needs a reader that accepts bit tests,
+    /// such as [`from_qasm`](Self::from_qasm).
+    /// Gates missing from `qelib1.inc`, such as
+    /// iSWAP, are written by name without a definition (an X with `N ≥ 3`
+    /// controls becomes `cNx`); use
+    /// [`to_openqasm_with_custom_gates`](Self::to_openqasm_with_custom_gates)
+    /// for self-contained output.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let qasm = Circuit::new(2).h(0).cx(0, 1).measure_all().to_qasm();
+    /// assert!(qasm.starts_with("OPENQASM 2.0;"));
+    /// assert!(qasm.contains("measure q[1] -> c[1];"));
+    /// ```
+    pub fn to_qasm(&self) -> std::string::String {
+        qasm2(self, false)
+    }
+
+    /// Export the circuit as OpenQASM 2.0, defining gates missing from `qelib1.inc`.
+    ///
+    /// Gates such as iSWAP and √SWAP get a `gate` definition built from
+    /// their standard decomposition, and an X with `N ≥ 3` controls gets a
+    /// `cNx` definition built from `cx` and `cu1` without ancillas, so the
+    /// output is self-contained and loads in any OpenQASM 2.0 toolchain.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::{Circuit, Gate};
+    ///
+    /// let mut circuit = Circuit::new(2);
+    /// circuit.insert_at(0, Gate::swap(), vec![0, 1]).unwrap();
+    /// let qasm = circuit.to_openqasm_with_custom_gates();
+    /// assert!(qasm.contains("swap q[0],q[1];"));
+    /// ```
+    pub fn to_openqasm_with_custom_gates(&self) -> std::string::String {
+        qasm2(self, true)
+    }
+}
+
+/// Write a circuit as OpenQASM 2.0, optionally defining non-`qelib1` gates.
+fn qasm2(circuit: &Circuit, define_custom: bool) -> std::string::String {
+    let mut out = std::string::String::from("OPENQASM 2.0;\ninclude \"qelib1.inc\";\n");
+
+    if define_custom {
+        let mut defined: Vec<GateType> = Vec::new();
+        let mut mcx_defined = 1;
+        for inst in circuit.instructions() {
+            let gate_type = inst.gate.gate_type;
+            let controls = inst.qubits.len().saturating_sub(1);
+            if gate_type == GateType::MCX && controls >= 3 && controls > mcx_defined {
+                for def in mcx_defs(mcx_defined, controls) {
+                    out.push_str(&def);
+                    out.push('\n');
+                }
+                mcx_defined = controls;
+            }
+            if defined.contains(&gate_type) {
+                continue;
+            }
+            if let Some(def) = custom_gate_def(gate_type) {
+                out.push_str(def);
+                out.push('\n');
+                defined.push(gate_type);
+            }
+        }
+    }
+
+    let _ = writeln!(out, "qreg q[{}];", circuit.num_qubits());
+    if circuit.num_clbits() > 0 {
+        let _ = writeln!(out, "creg c[{}];", circuit.num_clbits());
+    }
+
+    for inst in circuit.instructions() {
+        match inst.condition {
+            // With a one-bit register the bit test is a register test
+            Some((_, value)) if circuit.num_clbits() == 1 => {
+                let _ = write!(out, "if(c=={}) ", value);
+            }
+            Some((clbit, value)) => {
+                let _ = write!(out, "if(c[{}]=={}) ", clbit, value);
+            }
+            None => {}
+        }
+        out.push_str(&qasm2_line(inst));
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Format a single instruction as an OpenQASM 2.0 statement.
+fn qasm2_line(inst: &Instruction) -> std::string::String {
+    use GateType::*;
+
+    let operands = inst
+        .qubits
+        .iter()
+        .map(|q| std::format!("q[{}]", q))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    match inst.gate.gate_type {
+        Measure => match inst.clbits.first() {
+            Some(c) => std::format!("measure {} -> c[{}];", operands, c),
+            // OpenQASM 2.0 has no discarded measurement
+            None => std::format!("// measure {} (no classical target)", operands),
+        },
+        Barrier if inst.qubits.is_empty() => "barrier q;".into(),
+        // OpenQASM has no snapshot instruction
+        Snapshot => std::format!("// snapshot {}", inst.label.as_deref().unwrap_or_default()),
+        // qelib1.inc only goes up to two controls
+        MCX => match inst.qubits.len() {
+            1 => std::format!("x {};", operands),
+            2 => std::format!("cx {};", operands),
+            3 => std::format!("ccx {};", operands),
+            n => std::format!("c{}x {};", n - 1, operands),
+        },
+        _ => std::format!(
+            "{}{} {};",
+            qelib1_name(&inst.gate),
+            params(&inst.gate.params),
+            operands
+        ),
+    }
+}
+
+/// The OpenQASM 2.0 name for a gate.
+fn qelib1_name(gate: &Gate) -> &'static str {
+    use GateType::*;
+
+    match gate.gate_type {
+        P => "u1",
+        U => "u3",
+        CP => "cu1",
+        CU => "cu3",
+        _ => gate.name(),
+    }
+}
+
+/// `gate` definition for gates that `qelib1.inc` lacks.
+fn custom_gate_def(gate_type: GateType) -> Option<&'static str> {
+    match gate_type {
+        GateType::ISwap => Some(ISWAP_DEF),
+        GateType::SqrtSwap => Some(SQRT_SWAP_DEF),
+        GateType::Rxx => Some(RXX_DEF),
+        GateType::Ryy => Some(RYY_DEF),
+        _ => None,
+    }
+}
+
+/// Format a single instruction as an OpenQASM 3.0 statement.
+fn qasm3_line(inst: &Instruction) -> std::string::String {
+    use GateType::*;
+
+    let operands = inst
+        .qubits
+        .iter()
+        .map(|q| std::format!("q[{}]", q))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    match inst.gate.gate_type {
+        Measure => match inst.clbits.first() {
+            Some(c) => std::format!("c[{}] = measure {};", c, operands),
+            None => std::format!("measure {};", operands),
+        },
+        Barrier if inst.qubits.is_empty() => "barrier;".into(),
+        Snapshot => std::format!("// snapshot {}", inst.label.as_deref().unwrap_or_default()),
+        CU => std::format!("ctrl @ U{} {};", params(&inst.gate.params), operands),
+        SqrtSwap => std::format!("pow(0.5) @ swap {};", operands),
+        MCX => match inst.qubits.len() {
+            1 => std::format!("x {};", operands),
+            n => std::format!("ctrl({}) @ x {};", n - 1, operands),
+        },
+        _ => std::format!(
+            "{}{} {};",
+            inst.gate.name(),
+            params(&inst.gate.params),
+            operands
+        ),
+    }
+}
+
+/// Format gate parameters as a parenthesized list (empty if none).
+fn params(params: &GateParams) -> std::string::String {
+    match params {
+        GateParams::None => std::string::String::new(),
+        GateParams::Angle(theta) => std::format!("({})", theta),
+        GateParams::Angles3(theta, phi, lambda) => std::format!("({}, {}, {})", theta, phi, lambda),
+        GateParams::Parameter { name, scale } if *scale == 1.0 => std::format!("({})", name),
+        GateParams::Parameter { name, scale } => std::format!("({}*{})", scale, name),
+    }
+}
+
+impl Circuit {
+    /// Parse an OpenQASM 2.0 program.
+    ///
+    /// Supports the subset that [`to_qasm`](Self::to_qasm) writes:
+    /// `qreg`/`creg` declarations (several of each are fine, and become
+    /// consecutive qubit and bit ranges), the `qelib1.inc` gates this crate
+    /// implements plus `iswap`, `sqrtswap`, `rxx`, `ryy`, `rzz`, `mcx` and
+    /// `cNx` for any `N ≥ 3`,
+    /// `measure`, `reset` and `barrier`, each optionally guarded by
+    /// `if(c==v)` on a one-bit register or `if(c[i]==v)` on a single bit.
+    /// Operands may be whole registers, which broadcast as in the
+    /// specification. Parameters are expressions over numbers and `pi` with
+    /// `+ - * / ^`, parentheses, and `sin`, `cos`, `tan`, `exp`, `ln` and
+    /// `sqrt`.
+    ///
+    /// `gate` definitions are only accepted when they are exactly one of
+    /// the definitions written by
+    /// [`to_openqasm_with_custom_gates`](Self::to_openqasm_with_custom_gates),
+    /// so its output imports too.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotSupported` for unknown gates, other `gate` or `opaque`
+    /// definitions, `if` tests on registers wider than one bit and includes
+    /// other than `qelib1.inc`;
+    /// `QasmParse` for malformed statements; and any error from
+    /// [`validate`](Self::validate).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    ///
+    /// let circuit = Circuit::from_qasm(
+    ///     "OPENQASM 2.0;
+    ///      include \"qelib1.inc\";
+    ///      qreg q[2];
+    ///      creg c[2];
+    ///      h q[0];
+    ///      cx q[0],q[1];
+    ///      measure q -> c;",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(circuit.len(), 4);
+    /// ```
+    pub fn from_qasm(src: &str) -> Result<Circuit> {
+        let mut circuit = Circuit::new(0);
+        let mut cregs: Vec<(std::string::String, usize, usize)> = Vec::new();
+        let mut num_clbits = 0;
+        let mut instructions = Vec::new();
+
+        for (line, stmt) in qasm2_statements(src) {
+            let parse_error = |message: std::string::String| HomayaError::QasmParse { line, message };
+            let (condition, stmt) = split_condition(&stmt, &cregs)?.map_err(parse_error)?;
+            let first = instructions.len();
+
+            let name_len = stmt
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(stmt.len());
+            let (keyword, rest) = (&stmt[..name_len], stmt[name_len..].trim());
+            let declaration = matches!(keyword, "OPENQASM" | "include" | "qreg" | "creg" | "gate" | "opaque" | "if");
+            if condition.is_some() && declaration {
+                return Err(parse_error(std::format!("'if' cannot guard '{}'", keyword)));
+            }
+
+            match keyword {
+                "OPENQASM" => {
+                    if !rest.starts_with("2.") {
+                        return Err(HomayaError::NotSupported {
+                            operation: "only OpenQASM 2.0 can be imported",
+                        });
+                    }
+                }
+                "include" => {
+                    if rest != "\"qelib1.inc\"" {
+                        return Err(HomayaError::NotSupported {
+                            operation: "OpenQASM import only knows qelib1.inc",
+                        });
+                    }
+                }
+                "qreg" | "creg" => {
+                    let (name, size) = parse_operand(rest).map_err(parse_error)?;
+                    let size = size.ok_or_else(|| parse_error(std::format!("register '{}' needs a size", name)))?;
+                    if keyword == "qreg" {
+                        circuit.add_qreg(name, size);
+                    } else {
+                        cregs.push((name.into(), num_clbits, size));
+                        num_clbits += size;
+                    }
+                }
+                "gate" => {
+                    let normalized: std::string::String = stmt.split_whitespace().collect();
+                    let normalize = |def: &str| def.split_whitespace().collect::<std::string::String>();
+                    // `gate cNu1(...)` or `gate cNx ...` is checked against the definitions up to N
+                    let mcx_arity: Option<usize> = rest
+                        .strip_prefix('c')
+                        .map(|r| r.split(|c: char| !c.is_ascii_digit()).next().unwrap_or_default())
+                        .and_then(|n| n.parse().ok());
+                    let known = [ISWAP_DEF, SQRT_SWAP_DEF, RXX_DEF, RYY_DEF]
+                        .iter()
+                        .any(|def| normalize(def) == normalized)
+                        || mcx_arity.is_some_and(|n| mcx_defs(1, n).iter().any(|def| normalize(def) == normalized));
+                    if !known {
+                        return Err(HomayaError::NotSupported {
+                            operation: "custom gate definitions in OpenQASM import",
+                        });
+                    }
+                }
+                "opaque" => {
+                    return Err(HomayaError::NotSupported {
+                        operation: "opaque gates in OpenQASM import",
+                    })
+                }
+                "measure" => {
+                    let (q, c) = rest
+                        .split_once("->")
+                        .ok_or_else(|| parse_error("measure needs '->'".into()))?;
+                    let qubits = resolve_qubits(&circuit, q).map_err(parse_error)?;
+                    let clbits = resolve(&cregs, c).map_err(parse_error)?;
+                    if qubits.len() != clbits.len() {
+                        return Err(parse_error("measure operands differ in size".into()));
+                    }
+                    for (q, c) in qubits.into_iter().zip(clbits) {
+                        instructions.push(Instruction::with_clbits(Gate::measure(), std::vec![q], std::vec![c]));
+                    }
+                }
+                "barrier" => {
+                    let mut qubits = Vec::new();
+                    for arg in rest.split(',') {
+                        qubits.extend(resolve_qubits(&circuit, arg).map_err(parse_error)?);
+                    }
+                    // `barrier q;` over the whole circuit is the all-qubit barrier
+                    let spans_all = !rest.contains('[') && qubits.len() == circuit.num_qubits();
+                    if spans_all {
+                        qubits.clear();
+                    }
+                    instructions.push(Instruction::new(Gate::barrier(), qubits));
+                }
+                _ => {
+                    let (params, operands) = match rest.strip_prefix('(') {
+                        Some(inner) => {
+                            let close = matching_paren(inner)
+                                .ok_or_else(|| parse_error("unbalanced parentheses".into()))?;
+                            let values = split_top_level(&inner[..close])
+                                .into_iter()
+                                .map(|expr| {
+                                    eval_expr(expr)
+                                        .ok_or_else(|| parse_error(std::format!("invalid parameter '{}'", expr.trim())))
+                                })
+                                .collect::<Result<Vec<f64>>>()?;
+                            (values, inner[close + 1..].trim())
+                        }
+                        None => (Vec::new(), rest),
+                    };
+
+                    let gate = if keyword == "reset" && params.is_empty() {
+                        Gate::reset()
+                    } else if let (Some(_), true) = (mcx_controls(keyword), params.is_empty()) {
+                        Gate::mcx()
+                    } else {
+                        qasm2_gate(keyword, &params)?.map_err(parse_error)?
+                    };
+
+                    let args = operands
+                        .split(',')
+                        .map(|arg| resolve_qubits(&circuit, arg))
+                        .collect::<core::result::Result<Vec<_>, _>>()
+                        .map_err(parse_error)?;
+                    let arity_ok = match (mcx_controls(keyword), gate.num_qubits()) {
+                        (Some(controls), _) => args.len() == controls + 1,
+                        (None, 0) => !args.is_empty(),
+                        (None, n) => args.len() == n,
+                    };
+                    if !arity_ok {
+                        return Err(parse_error(std::format!(
+                            "'{}' takes {} qubit operands, got {}",
+                            keyword,
+                            mcx_controls(keyword).map_or(gate.num_qubits(), |controls| controls + 1),
+                            args.len()
+                        )));
+                    }
+
+                    // Whole-register operands broadcast over their qubits
+                    let width = args.iter().map(Vec::len).max().unwrap_or(1);
+                    if args.iter().any(|a| a.len() != 1 && a.len() != width) {
+                        return Err(parse_error("register operands differ in size".into()));
+                    }
+                    for i in 0..width {
+                        let qubits = args.iter().map(|a| if a.len() == 1 { a[0] } else { a[i] }).collect();
+                        instructions.push(Instruction::new(gate.clone(), qubits));
+                    }
+                }
+            }
+
+            for inst in &mut instructions[first..] {
+                inst.condition = condition;
+            }
+        }
+
+        let mut circuit = circuit.with_instructions(instructions);
+        circuit.set_num_clbits(num_clbits);
+        circuit.validate()?;
+        Ok(circuit)
+    }
+}
+
+/// Split OpenQASM source into statements, paired with the line each starts on.
+///
+/// Strips `//` comments. A statement ends at `;`, or at the `}` closing a
+/// `gate` body.
+fn qasm2_statements(src: &str) -> Vec<(usize, std::string::String)> {
+    let mut statements: Vec<(usize, std::string::String)> = Vec::new();
+    let mut current = std::string::String::new();
+    let mut start = 0;
+    let mut depth = 0usize;
+
+    for (index, line) in src.lines().enumerate() {
+        let code = line.split("//").next().unwrap_or("");
+        for c in code.chars().chain(core::iter::once('\n')) {
+            if current.trim().is_empty() {
+                start = index + 1;
+            }
+            match c {
+                ';' if depth == 0 => {
+                    statements.push((start, current.trim().into()));
+                    current.clear();
+                    continue;
+                }
+                '{' => depth += 1,
+                '}' => {
+                    depth = depth.saturating_sub(1);
+                    if depth == 0 {
+                        current.push(c);
+                        statements.push((start, current.trim().into()));
+                        current.clear();
+                        continue;
+                    }
+                }
+                _ => {}
+            }
+            current.push(c);
+        }
+    }
+
+    if !current.trim().is_empty() {
+        statements.push((start, current.trim().into()));
+    }
+    statements.retain(|(_, stmt)| !stmt.is_empty());
+    statements
+}
+
+/// A statement with its optional `(clbit, value)` condition.
+type Guarded<'a> = (Option<(usize, u8)>, &'a str);
+
+/// Split a leading `if(<creg>==v)` or `if(<creg>[i]==v)` off a statement.
+///
+/// Returns the `(clbit, value)` condition, if any, and the guarded
+/// statement. The outer error is for a test this crate cannot represent;
+/// the inner one for a malformed test.
+fn split_condition<'a>(
+    stmt: &'a str,
+    cregs: &[(std::string::String, usize, usize)],
+) -> Result<core::result::Result<Guarded<'a>, std::string::String>> {
+    let Some(inner) = stmt.strip_prefix("if").map(str::trim_start).and_then(|s| s.strip_prefix('(')) else {
+        return Ok(Ok((None, stmt)));
+    };
+    let Some(close) = matching_paren(inner) else {
+        return Ok(Err("unbalanced parentheses".into()));
+    };
+    let Some((bits, value)) = inner[..close].split_once("==") else {
+        return Ok(Err("'if' needs '=='".into()));
+    };
+    let bits = match resolve(cregs, bits) {
+        Ok(bits) => bits,
+        Err(message) => return Ok(Err(message)),
+    };
+    if bits.len() != 1 {
+        return Err(HomayaError::NotSupported {
+            operation: "OpenQASM import of 'if' on registers wider than one bit",
+        });
+    }
+    let value = match value.trim().parse::<u8>() {
+        Ok(value @ (0 | 1)) => value,
+        _ => return Ok(Err(std::format!("'if' on one bit needs 0 or 1, got '{}'", value.trim()))),
+    };
+    Ok(Ok((Some((bits[0], value)), inner[close + 1..].trim())))
+}
+
+/// Map an OpenQASM 2.0 gate name and its parameters to a gate.
+///
+/// The outer error is for unknown names; the inner one for a known gate
+/// with the wrong number of parameters.
+fn qasm2_gate(name: &str, params: &[f64]) -> Result<core::result::Result<Gate, std::string::String>> {
+    let fixed = match name {
+        "id" => Some(Gate::i()),
+        "x" => Some(Gate::x()),
+        "y" => Some(Gate::y()),
+        "z" => Some(Gate::z()),
+        "h" => Some(Gate::h()),
+        "s" => Some(Gate::s()),
+        "sdg" => Some(Gate::sdg()),
+        "t" => Some(Gate::t()),
+        "tdg" => Some(Gate::tdg()),
+        "cx" | "CX" => Some(Gate::cx()),
+        "cy" => Some(Gate::cy()),
+        "cz" => Some(Gate::cz()),
+        "ch" => Some(Gate::ch()),
+        "swap" => Some(Gate::swap()),
+        "iswap" => Some(Gate::iswap()),
+        "sqrtswap" => Some(Gate::sqrt_swap()),
+        "ccx" => Some(Gate::ccx()),
+        "cswap" => Some(Gate::cswap()),
+        "mcx" => Some(Gate::mcx()),
+        _ => None,
+    };
+
+    let (expected, gate) = match (name, params) {
+        _ if fixed.is_some() => (0, fixed),
+        ("rx", &[theta]) => (1, Some(Gate::rx(theta))),
+        ("ry", &[theta]) => (1, Some(Gate::ry(theta))),
+        ("rz", &[theta]) => (1, Some(Gate::rz(theta))),
+        ("u1" | "p", &[theta]) => (1, Some(Gate::p(theta))),
+        ("cu1" | "cp", &[theta]) => (1, Some(Gate::cp(theta))),
+        ("rxx", &[theta]) => (1, Some(Gate::rxx(theta))),
+        ("ryy", &[theta]) => (1, Some(Gate::ryy(theta))),
+        ("rzz", &[theta]) => (1, Some(Gate::rzz(theta))),
+        ("u2", &[phi, lambda]) => (2, Some(Gate::u(PI / 2.0, phi, lambda))),
+        ("u3" | "u" | "U", &[theta, phi, lambda]) => (3, Some(Gate::u(theta, phi, lambda))),
+        ("cu3", &[theta, phi, lambda]) => (3, Some(Gate::cu(theta, phi, lambda))),
+        ("rx" | "ry" | "rz" | "u1" | "p" | "cu1" | "cp" | "rxx" | "ryy" | "rzz", _) => (1, None),
+        ("u2", _) => (2, None),
+        ("u3" | "u" | "U" | "cu3", _) => (3, None),
+        _ => {
+            return Err(HomayaError::NotSupported {
+                operation: "unknown gate in OpenQASM import",
+            })
+        }
+    };
+
+    Ok(match gate {
+        Some(gate) if params.len() == expected => Ok(gate),
+        _ => Err(std::format!(
+            "'{}' takes {} parameters, got {}",
+            name,
+            expected,
+            params.len()
+        )),
+    })
+}
+
+/// Split `name[index]` or `name` into its parts.
+fn parse_operand(arg: &str) -> core::result::Result<(&str, Option<usize>), std::string::String> {
+    let arg = arg.trim();
+    match arg.split_once('[') {
+        Some((name, index)) => {
+            let index = index
+                .strip_suffix(']')
+                .and_then(|i| i.trim().parse().ok())
+                .ok_or_else(|| std::format!("malformed operand '{}'", arg))?;
+            Ok((name.trim(), Some(index)))
+        }
+        None if !arg.is_empty() => Ok((arg, None)),
+        None => Err("missing operand".into()),
+    }
+}
+
+/// Resolve an operand against `(name, start, size)` registers to global indices.
+fn resolve(
+    registers: &[(std::string::String, usize, usize)],
+    arg: &str,
+) -> core::result::Result<Vec<usize>, std::string::String> {
+    let (name, index) = parse_operand(arg)?;
+    let &(_, start, size) = registers
+        .iter()
+        .find(|(reg, _, _)| reg == name)
+        .ok_or_else(|| std::format!("undeclared register '{}'", name))?;
+    match index {
+        Some(i) if i < size => Ok(std::vec![start + i]),
+        Some(i) => Err(std::format!("index {} out of range for register '{}' of size {}", i, name, size)),
+        None => Ok((start..start + size).collect()),
+    }
+}
+
+/// Resolve a qubit operand against the circuit's registers.
+fn resolve_qubits(circuit: &Circuit, arg: &str) -> core::result::Result<Vec<usize>, std::string::String> {
+    let registers: Vec<(std::string::String, usize, usize)> = circuit
+        .qregs()
+        .iter()
+        .map(|reg| (reg.name().into(), reg.range().start, reg.len()))
+        .collect();
+    resolve(&registers, arg)
+}
+
+/// Index of the `)` closing an already-opened parenthesis.
+fn matching_paren(s: &str) -> Option<usize> {
+    let mut depth = 1;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split on commas that are not nested inside parentheses.
+fn split_top_level(s: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&s[start..]);
+    parts
+}
+
+/// Evaluate an OpenQASM parameter expression.
+fn eval_expr(src: &str) -> Option<f64> {
+    let mut parser = ExprParser { src: src.as_bytes(), pos: 0 };
+    let value = parser.expr()?;
+    parser.skip_whitespace();
+    (parser.pos == parser.src.len()).then_some(value)
+}
+
+/// Recursive-descent evaluator for parameter expressions.
+struct ExprParser<'a> {
+    /// Expression text
+    src: &'a [u8],
+    /// Current byte offset
+    pos: usize,
+}
+
+impl ExprParser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.src.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// Next non-whitespace byte, without consuming it.
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.src.get(self.pos).copied()
+    }
+
+    /// expr := term (('+' | '-') term)*
+    fn expr(&mut self) -> Option<f64> {
+        let mut value = self.term()?;
+        loop {
+            match self.peek() {
+                Some(b'+') => {
+                    self.pos += 1;
+                    value += self.term()?;
+                }
+                Some(b'-') => {
+                    self.pos += 1;
+                    value -= self.term()?;
+                }
+                _ => return Some(value),
+            }
+        }
+    }
+
+    /// term := factor (('*' | '/') factor)*
+    fn term(&mut self) -> Option<f64> {
+        let mut value = self.factor()?;
+        loop {
+            match self.peek() {
+                Some(b'*') => {
+                    self.pos += 1;
+                    value *= self.factor()?;
+                }
+                Some(b'/') => {
+                    self.pos += 1;
+                    value /= self.factor()?;
+                }
+                _ => return Some(value),
+            }
+        }
+    }
+
+    /// factor := ('-' | '+') factor | primary ('^' factor)?
+    fn factor(&mut self) -> Option<f64> {
+        match self.peek()? {
+            b'-' => {
+                self.pos += 1;
+                Some(-self.factor()?)
+            }
+            b'+' => {
+                self.pos += 1;
+                self.factor()
+            }
+            _ => {
+                let base = self.primary()?;
+                if self.peek() == Some(b'^') {
+                    self.pos += 1;
+                    Some(base.powf(self.factor()?))
+                } else {
+                    Some(base)
+                }
+            }
+        }
+    }
+
+    /// primary := number | 'pi' | function '(' expr ')' | '(' expr ')'
+    fn primary(&mut self) -> Option<f64> {
+        let c = self.peek()?;
+        if c == b'(' {
+            self.pos += 1;
+            let value = self.expr()?;
+            return (self.peek() == Some(b')')).then(|| {
+                self.pos += 1;
+                value
+            });
+        }
+
+        let start = self.pos;
+        if c.is_ascii_digit() || c == b'.' {
+            while let Some(&b) = self.src.get(self.pos) {
+                let exponent_sign =
+                    (b == b'+' || b == b'-') && matches!(self.src[self.pos - 1], b'e' | b'E');
+                if b.is_ascii_digit() || b == b'.' || b == b'e' || b == b'E' || exponent_sign {
+                    self.pos += 1;
+                } else {
+                    break;
+                }
+            }
+            return core::str::from_utf8(&self.src[start..self.pos]).ok()?.parse().ok();
+        }
+
+        while self.src.get(self.pos).is_some_and(u8::is_ascii_alphabetic) {
+            self.pos += 1;
+        }
+        let name = core::str::from_utf8(&self.src[start..self.pos]).ok()?;
+        if name == "pi" {
+            return Some(PI);
+        }
+        let function: fn(f64) -> f64 = match name {
+            "sin" => f64::sin,
+            "cos" => f64::cos,
+            "tan" => f64::tan,
+            "exp" => f64::exp,
+            "ln" => f64::ln,
+            "sqrt" => f64::sqrt,
+            _ => return None,
+        };
+        if self.peek() != Some(b'(') {
+            return None;
+        }
+        self.primary().map(function)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Circuit, Gate, GateParams, GateType, HomayaError, Instruction, PI};
+
+    #[test]
+    fn test_qasm3_ghz() {
+        let qasm = Circuit::new(3).h(0).cx(0, 1).cx(1, 2).measure_all().to_qasm3();
+        let lines: Vec<&str> = qasm.lines().collect();
+
+        assert_eq!(lines[0], "OPENQASM 3.0;");
+        assert!(lines.contains(&"qubit[3] q;"));
+        assert!(lines.contains(&"bit[3] c;"));
+        assert!(lines.contains(&"h q[0];"));
+        assert!(lines.contains(&"cx q[0], q[1];"));
+        assert!(lines.contains(&"cx q[1], q[2];"));
+        assert!(lines.contains(&"c[2] = measure q[2];"));
+    }
+
+    #[test]
+    fn test_qasm3_params_and_fences() {
+        let qasm = Circuit::new(2).rz(PI / 2.0, 1).barrier_all().reset(0).to_qasm3();
+
+        assert!(!qasm.lines().any(|l| l.starts_with("bit[")));
+        assert!(qasm.contains(&std::format!("rz({}) q[1];", PI / 2.0)));
+        assert!(qasm.contains("barrier q[0], q[1];"));
+        assert!(qasm.contains("reset q[0];"));
+    }
+
+    #[test]
+    fn test_qasm2_defines_custom_gates_once() {
+        let gate = |gate_type| Gate { gate_type, params: GateParams::None };
+        let mut circuit = Circuit::new(3).h(0).measure(2, 0);
+        circuit.insert_at(1, gate(GateType::ISwap), std::vec![0, 1]).unwrap();
+        circuit.insert_at(2, gate(GateType::ISwap), std::vec![1, 2]).unwrap();
+        circuit.insert_at(3, gate(GateType::SqrtSwap), std::vec![0, 2]).unwrap();
+
+        let qasm = circuit.to_openqasm_with_custom_gates();
+        let lines: Vec<&str> = qasm.lines().collect();
+
+        assert_eq!(lines[0], "OPENQASM 2.0;");
+        assert_eq!(lines[1], "include \"qelib1.inc\";");
+        assert_eq!(qasm.matches("gate iswap a, b").count(), 1);
+        assert_eq!(qasm.matches("gate sqrtswap a, b").count(), 1);
+        assert!(lines.contains(&"qreg q[3];"));
+        assert!(lines.contains(&"creg c[1];"));
+        assert!(lines.contains(&"iswap q[0],q[1];"));
+        assert!(lines.contains(&"iswap q[1],q[2];"));
+        assert!(lines.contains(&"sqrtswap q[0],q[2];"));
+        assert!(lines.contains(&"measure q[2] -> c[0];"));
+    }
+
+    #[test]
+    fn test_qasm2_uses_qelib1_names() {
+        let qasm = Circuit::new(2)
+            .p(0.5, 0)
+            .cp(0.25, 0, 1)
+            .u(0.1, 0.2, 0.3, 1)
+            .to_openqasm_with_custom_gates();
+        assert!(qasm.contains("u1(0.5) q[0];"));
+        assert!(qasm.contains("cu1(0.25) q[0],q[1];"));
+        assert!(qasm.contains("u3(0.1, 0.2, 0.3) q[1];"));
+        assert!(!qasm.contains("gate "));
+    }
+
+    #[test]
+    fn test_qasm3_defines_ising_couplings() {
+        let qasm = Circuit::new(3).rxx(0.5, 0, 1).ryy(0.5, 1, 2).rzz(0.5, 0, 2).rxx(0.1, 2, 0).to_qasm3();
+        assert_eq!(qasm.matches("gate rxx(theta) a, b").count(), 1);
+        assert_eq!(qasm.matches("gate ryy(theta) a, b").count(), 1);
+        assert_eq!(qasm.matches("gate rzz(theta) a, b").count(), 1);
+        assert!(!qasm.contains("gate iswap"));
+        assert!(qasm.contains("rxx(0.5) q[0], q[1];"));
+        assert!(qasm.contains("rzz(0.5) q[0], q[2];"));
+
+        assert!(!Circuit::new(2).cx(0, 1).to_qasm3().contains("gate "));
+    }
+
+    #[test]
+    fn test_qasm2_ising_couplings() {
+        let qasm = Circuit::new(2).rxx(0.5, 0, 1).ryy(0.5, 0, 1).rzz(0.5, 0, 1).to_openqasm_with_custom_gates();
+        assert_eq!(qasm.matches("gate rxx(theta) a, b").count(), 1);
+        assert_eq!(qasm.matches("gate ryy(theta) a, b").count(), 1);
+        // rzz is in qelib1.inc
+        assert!(!qasm.contains("gate rzz"));
+        assert!(qasm.contains("rxx(0.5) q[0],q[1];"));
+        assert!(qasm.contains("rzz(0.5) q[0],q[1];"));
+    }
+
+    #[test]
+    fn test_mcx_export() {
+        let circuit = Circuit::new(5).mcx(&[0, 1, 2], 4).mcx(&[1], 0);
+        let qasm3 = circuit.to_qasm3();
+        assert!(qasm3.contains("ctrl(3) @ x q[0], q[1], q[2], q[4];"));
+        assert!(qasm3.contains("ctrl(1) @ x q[1], q[0];"));
+        let qasm2 = circuit.to_openqasm_with_custom_gates();
+        assert!(qasm2.contains("cx q[1],q[0];"));
+        assert!(qasm2.contains("c3x q[0],q[1],q[2],q[4];"));
+    }
+
+    #[test]
+    fn test_mcx_definitions_are_self_contained() {
+        let circuit = Circuit::new(6).mcx(&[0, 1, 2, 3, 4], 5).mcx(&[0, 1, 2], 3).mcx(&[4, 3, 2, 1], 0);
+        let qasm = circuit.to_openqasm_with_custom_gates();
+
+        // Every gate a definition calls is defined above it, and each once
+        let lines: Vec<&str> = qasm.lines().collect();
+        for n in 2..=5 {
+            assert_eq!(qasm.matches(&std::format!("gate c{}u1(lambda)", n)).count(), 1);
+            let u1 = lines.iter().position(|l| l.starts_with(&std::format!("gate c{}u1(", n))).unwrap();
+            if n >= 3 {
+                assert_eq!(qasm.matches(&std::format!("gate c{}x ", n)).count(), 1);
+                let x = lines.iter().position(|l| l.starts_with(&std::format!("gate c{}x ", n))).unwrap();
+                assert!(u1 < x);
+                let inner = if n == 3 { "ccx ".into() } else { std::format!("c{}x ", n - 1) };
+                assert!(lines[u1].contains(&inner));
+            }
+        }
+        assert!(!qasm.contains("gate c6"));
+        assert!(qasm.contains("c5x q[0],q[1],q[2],q[3],q[4],q[5];"));
+        assert!(!qasm.contains("mcx"));
+
+        // Without definitions only the gate names are written
+        assert!(!circuit.to_qasm().contains("gate "));
+
+        let parsed = Circuit::from_qasm(&qasm).unwrap();
+        assert_eq!(parsed.instructions(), circuit.instructions());
+        assert!(Circuit::from_qasm(&circuit.to_qasm()).is_ok());
+    }
+
+    #[test]
+    fn test_qasm2_bell() {
+        let qasm = Circuit::new(2).h(0).cx(0, 1).measure_all().to_qasm();
+        assert_eq!(
+            qasm,
+            "OPENQASM 2.0;\n\
+             include \"qelib1.inc\";\n\
+             qreg q[2];\n\
+             creg c[2];\n\
+             h q[0];\n\
+             cx q[0],q[1];\n\
+             measure q[0] -> c[0];\n\
+             measure q[1] -> c[1];\n"
+        );
+    }
+
+    #[test]
+    fn test_qasm2_gate_coverage() {
+        let qasm = Circuit::new(3)
+            .rx(PI / 4.0, 0)
+            .sdg(1)
+            .tdg(2)
+            .cy(0, 1)
+            .ch(1, 2)
+            .cu(0.1, 0.2, 0.3, 0, 2)
+            .iswap(0, 1)
+            .ccx(0, 1, 2)
+            .cswap(2, 0, 1)
+            .barrier(&[0, 2])
+            .barrier_all()
+            .reset(1)
+            .to_qasm();
+        assert!(qasm.contains(&std::format!("rx({}) q[0];", PI / 4.0)));
+        assert!(qasm.contains("sdg q[1];"));
+        assert!(qasm.contains("tdg q[2];"));
+        assert!(qasm.contains("cy q[0],q[1];"));
+        assert!(qasm.contains("ch q[1],q[2];"));
+        assert!(qasm.contains("cu3(0.1, 0.2, 0.3) q[0],q[2];"));
+        assert!(qasm.contains("iswap q[0],q[1];"));
+        assert!(qasm.contains("ccx q[0],q[1],q[2];"));
+        assert!(qasm.contains("cswap q[2],q[0],q[1];"));
+        assert!(qasm.contains("barrier q[0],q[2];"));
+        assert!(qasm.contains("reset q[1];"));
+        assert!(!qasm.contains("gate "));
+        assert!(!qasm.contains("creg"));
+    }
+
+    #[test]
+    fn test_conditions_export() {
+        let circuit = Circuit::new(2).h(0).measure(0, 0).x_if(1, 0, 1);
+        assert!(circuit.to_qasm().contains("if(c==1) x q[1];"));
+        assert!(circuit.to_qasm3().contains("if (c[0] == 1) { x q[1]; }"));
+
+        let wide = Circuit::new(2).measure(0, 0).measure(1, 1).z_if(0, 1, 0);
+        assert!(wide.to_qasm().contains("if(c[1]==0) z q[0];"));
+    }
+
+    #[test]
+    fn test_from_qasm_ghz() {
+        let src = "OPENQASM 2.0;
+            include \"qelib1.inc\";
+            // three-qubit GHZ state
+            qreg q[3];
+            creg c[3];
+            h q[0];
+            cx q[0],q[1];
+            cx q[1],q[2];
+            barrier q;
+            measure q -> c;";
+        let circuit = Circuit::from_qasm(src).unwrap();
+
+        assert_eq!(circuit.num_qubits(), 3);
+        assert_eq!(circuit.num_clbits(), 3);
+        let measure = |q: usize| Instruction::with_clbits(Gate::measure(), std::vec![q], std::vec![q]);
+        assert_eq!(
+            circuit.instructions(),
+            &[
+                Instruction::new(Gate::h(), std::vec![0]),
+                Instruction::new(Gate::cx(), std::vec![0, 1]),
+                Instruction::new(Gate::cx(), std::vec![1, 2]),
+                Instruction::new(Gate::barrier(), std::vec![]),
+                measure(0),
+                measure(1),
+                measure(2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_qasm_round_trip() {
+        let circuit = Circuit::with_clbits(3, 2)
+            .h(0)
+            .rx(PI / 4.0, 0)
+            .ry(-0.3, 1)
+            .rz(1e-7, 2)
+            .p(0.25, 1)
+            .u(0.1, 0.2, 0.3, 2)
+            .s(0)
+            .sdg(1)
+            .t(2)
+            .tdg(0)
+            .cy(0, 1)
+            .cz(1, 2)
+            .ch(1, 2)
+            .cp(0.5, 0, 2)
+            .cu(0.1, 0.2, 0.3, 0, 2)
+            .swap(0, 1)
+            .rzz(0.7, 0, 1)
+            .ccx(0, 1, 2)
+            .cswap(2, 0, 1)
+            .barrier(&[0, 2])
+            .barrier_all()
+            .reset(1)
+            .measure(2, 1)
+            .x_if(0, 1, 1)
+            .z_if(2, 0, 0);
+        let parsed = Circuit::from_qasm(&circuit.to_qasm()).unwrap();
+        assert_eq!(parsed.num_qubits(), circuit.num_qubits());
+        assert_eq!(parsed.num_clbits(), circuit.num_clbits());
+        assert_eq!(parsed.instructions(), circuit.instructions());
+
+        // A one-bit register is tested as a whole
+        let fix = Circuit::new(2).h(0).measure(0, 0).x_if(1, 0, 1);
+        let parsed = Circuit::from_qasm(&fix.to_qasm()).unwrap();
+        assert_eq!(parsed.instructions(), fix.instructions());
+
+        // Custom definitions written for non-qelib1 gates are recognized
+        let custom = Circuit::new(2).iswap(0, 1).sqrt_swap(1, 0).rxx(0.2, 0, 1).ryy(0.3, 1, 0);
+        let parsed = Circuit::from_qasm(&custom.to_openqasm_with_custom_gates()).unwrap();
+        assert_eq!(parsed.instructions(), custom.instructions());
+    }
+
+    #[test]
+    fn test_from_qasm_expressions_and_registers() {
+        let src = "OPENQASM 2.0;
+            qreg a[1]; qreg b[2];
+            creg m[2];
+            rz(-pi/2) a[0];
+            u2(0, pi) b[1];
+            rx(2*pi^2 - sin(pi/2) + 1.5e-1) b[0];
+            cx a[0], b;
+            measure b -> m;";
+        let circuit = Circuit::from_qasm(src).unwrap();
+        let insts = circuit.instructions();
+
+        assert_eq!(circuit.num_qubits(), 3);
+        assert_eq!(insts[0].gate.params, GateParams::Angle(-PI / 2.0));
+        assert_eq!(insts[1].gate, Gate::u(PI / 2.0, 0.0, PI));
+        assert_eq!(insts[1].qubits, std::vec![2]);
+        match insts[2].gate.params {
+            GateParams::Angle(theta) => assert!((theta - (2.0 * PI * PI - 1.0 + 0.15)).abs() < 1e-12),
+            ref other => panic!("unexpected params {:?}", other),
+        }
+        assert_eq!(insts[3].qubits, std::vec![0, 1]);
+        assert_eq!(insts[4].qubits, std::vec![0, 2]);
+        assert_eq!(insts[6].clbits, std::vec![1]);
+    }
+
+    #[test]
+    fn test_from_qasm_rejects_unsupported() {
+        let header = "OPENQASM 2.0;\ninclude \"qelib1.inc\";\nqreg q[2];\n";
+        let parse = |body: &str| Circuit::from_qasm(&std::format!("{}{}", header, body));
+
+        assert!(matches!(
+            parse("gate bell a, b { h a; cx a, b; }\nbell q[0], q[1];"),
+            Err(HomayaError::NotSupported { .. })
+        ));
+        assert!(matches!(parse("sx q[0];"), Err(HomayaError::NotSupported { .. })));
+        assert!(matches!(parse("c3x q[0],q[1];"), Err(HomayaError::QasmParse { line: 4, .. })));
+        assert!(matches!(parse("rx q[0];"), Err(HomayaError::QasmParse { line: 4, .. })));
+        assert!(matches!(parse("cx q[0];"), Err(HomayaError::QasmParse { line: 4, .. })));
+        assert!(matches!(parse("h q[5];"), Err(HomayaError::QasmParse { .. })));
+        assert!(matches!(parse("h r[0];"), Err(HomayaError::QasmParse { .. })));
+        assert!(matches!(parse("cx q[0],q[0];"), Err(HomayaError::DuplicateQubit { qubit: 0 })));
+        assert!(matches!(
+            parse("creg c[2];\nif(c==3) x q[0];"),
+            Err(HomayaError::NotSupported { .. })
+        ));
+        assert!(matches!(parse("creg c[1];\nif(c==2) x q[0];"), Err(HomayaError::QasmParse { line: 5, .. })));
+        assert!(matches!(parse("creg c[1];\nif(c==1) qreg r[1];"), Err(HomayaError::QasmParse { line: 5, .. })));
+    }
+}
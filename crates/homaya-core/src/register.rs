@@ -0,0 +1,85 @@
+//! Named qubit registers.
+//!
+//! A register is a named, contiguous slice of a circuit's flat qubit space.
+
+use core::ops::{Index, Range};
+
+/// A named block of qubits within a circuit.
+///
+/// Created with [`Circuit::add_qreg`](crate::Circuit::add_qreg). Indexing
+/// yields the global qubit index, so registers plug straight into the
+/// circuit builder methods.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_core::Circuit;
+///
+/// let mut circuit = Circuit::new(0);
+/// let a = circuit.add_qreg("a", 2);
+/// let b = circuit.add_qreg("b", 3);
+///
+/// let circuit = circuit.h(a[0]).cx(a[0], b[1]);
+/// assert_eq!(circuit.num_qubits(), 5);
+/// assert_eq!(circuit.instructions()[1].qubits, vec![0, 3]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuantumRegister {
+    /// Register name
+    name: std::string::String,
+    /// Global index of the first qubit
+    start: usize,
+    /// Global qubit indices, in register order
+    qubits: Vec<usize>,
+}
+
+impl QuantumRegister {
+    /// Create a register covering `size` qubits starting at global index `start`.
+    pub(crate) fn new(name: std::string::String, start: usize, size: usize) -> Self {
+        Self {
+            name,
+            start,
+            qubits: (start..start + size).collect(),
+        }
+    }
+
+    /// Get the register name.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the number of qubits in the register.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.qubits.len()
+    }
+
+    /// Check if the register is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.qubits.is_empty()
+    }
+
+    /// Get the global qubit index range covered by this register.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.start + self.qubits.len()
+    }
+
+    /// Get the global qubit indices as a slice.
+    #[inline]
+    pub fn qubits(&self) -> &[usize] {
+        &self.qubits
+    }
+}
+
+impl Index<usize> for QuantumRegister {
+    type Output = usize;
+
+    #[inline]
+    fn index(&self, i: usize) -> &usize {
+        &self.qubits[i]
+    }
+}
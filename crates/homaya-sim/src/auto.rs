@@ -0,0 +1,261 @@
+//! Automatic backend selection.
+//!
+//! Picks the cheapest simulation method that is exact for a given circuit:
+//! a stabilizer tableau, a sparse state, or the dense state vector.
+
+use homaya_core::{Circuit, GateType, Instruction, Result};
+use crate::{MeasurementResult, Simulator, SparseState, StabilizerState, StateVector};
+
+/// Sparse simulation is chosen when the support can grow to at most
+/// 2^(n − this) basis states, i.e. 1/16 of the dense vector.
+const SPARSE_MARGIN: usize = 4;
+
+/// Simulation method used by [`Simulator::run_auto`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// Full state vector ([`StateVector`])
+    Dense,
+    /// Nonzero amplitudes only ([`SparseState`])
+    Sparse,
+    /// Clifford tableau ([`StabilizerState`])
+    Stabilizer,
+}
+
+impl Backend {
+    /// Choose a backend for a circuit.
+    ///
+    /// The heuristics, in order:
+    ///
+    /// 1. **Stabilizer** if every gate is Clifford (see
+    ///    [`StabilizerState::supports`]). Cost is O(n²) per gate at any size.
+    /// 2. **Sparse** if every gate is supported by [`SparseState`], the
+    ///    circuit fits in 64 qubits, and it is provably sparse: each
+    ///    superposition-creating gate (H, Rx, Ry, U, CH, CU, √SWAP, Rxx, Ryy)
+    ///    at most doubles the number of nonzero amplitudes, so with b such
+    ///    gates the support never exceeds 2ᵇ. The circuit qualifies when
+    ///    b ≤ n − 4, or when it is too wide for the dense backend at all.
+    /// 3. **Dense** otherwise.
+    pub fn select(circuit: &Circuit) -> Self {
+        let instructions = circuit.instructions();
+
+        if instructions.iter().all(|inst| StabilizerState::supports(&inst.gate)) {
+            return Self::Stabilizer;
+        }
+
+        let n = circuit.num_qubits();
+        let sparse_ok = n <= SparseState::MAX_QUBITS
+            && instructions.iter().all(|inst| SparseState::supports(&inst.gate));
+        let branching = instructions.iter().filter(|inst| is_branching(inst)).count();
+
+        if sparse_ok && (n > StateVector::MAX_QUBITS || branching + SPARSE_MARGIN <= n) {
+            Self::Sparse
+        } else {
+            Self::Dense
+        }
+    }
+}
+
+/// Final quantum state from [`Simulator::run_auto`], in the backend's format.
+#[derive(Clone, Debug)]
+pub enum AutoState {
+    /// Dense state vector
+    Dense(StateVector),
+    /// Sparse state vector
+    Sparse(SparseState),
+    /// Stabilizer tableau
+    Stabilizer(StabilizerState),
+}
+
+/// Result of [`Simulator::run_auto`].
+#[derive(Clone, Debug)]
+pub struct AutoResult {
+    /// Backend that ran the circuit
+    pub backend: Backend,
+    /// Final state
+    pub state: AutoState,
+    /// Classical bits written by measurements
+    pub measurements: MeasurementResult,
+}
+
+impl Simulator {
+    /// Run a circuit on the cheapest exact backend.
+    ///
+    /// See [`Backend::select`] for how the backend is chosen. Random draws
+    /// follow the same contract as [`run_with_measurements`](Self::run_with_measurements):
+    /// one per `Measure` and one per `Reset`.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if [`Circuit::validate`] rejects the circuit, or
+    /// `CircuitTooLarge` if the circuit needs the dense backend and has more
+    /// than [`StateVector::MAX_QUBITS`] qubits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::{Backend, Simulator};
+    ///
+    /// // 100-qubit GHZ state: Clifford-only, so it runs on a tableau
+    /// let mut circuit = Circuit::new(100).h(0);
+    /// for q in 0..99 {
+    ///     circuit = circuit.cx(q, q + 1);
+    /// }
+    /// let result = Simulator::with_seed(1).run_auto(&circuit.measure_all()).unwrap();
+    /// assert_eq!(result.backend, Backend::Stabilizer);
+    /// ```
+    pub fn run_auto(&mut self, circuit: &Circuit) -> Result<AutoResult> {
+        circuit.validate()?;
+
+        let backend = Backend::select(circuit);
+        let n = circuit.num_qubits();
+        let mut measurements = MeasurementResult::new(circuit.num_clbits());
+
+        let state = match backend {
+            Backend::Dense => {
+                let (state, result) = self.run_with_measurements(circuit)?;
+                measurements = result;
+                AutoState::Dense(state)
+            }
+            Backend::Sparse => {
+                let mut state = SparseState::new(n);
+                for inst in circuit.instructions() {
+                    if !condition_holds(&measurements, inst) {
+                        continue;
+                    }
+                    match inst.gate.gate_type {
+                        GateType::Measure => {
+                            let result = state.measure(inst.qubits[0], self.next_random());
+                            record(&mut measurements, inst, result);
+                        }
+                        GateType::Reset => state.reset(inst.qubits[0], self.next_random()),
+                        _ => state.apply_gate(&inst.gate, &inst.qubits)?,
+                    }
+                }
+                AutoState::Sparse(state)
+            }
+            Backend::Stabilizer => {
+                let mut state = StabilizerState::new(n);
+                for inst in circuit.instructions() {
+                    if !condition_holds(&measurements, inst) {
+                        continue;
+                    }
+                    match inst.gate.gate_type {
+                        GateType::Measure => {
+                            let result = state.measure(inst.qubits[0], self.next_random());
+                            record(&mut measurements, inst, result);
+                        }
+                        GateType::Reset => state.reset(inst.qubits[0], self.next_random()),
+                        _ => state.apply_gate(&inst.gate, &inst.qubits)?,
+                    }
+                }
+                AutoState::Stabilizer(state)
+            }
+        };
+
+        Ok(AutoResult {
+            backend,
+            state,
+            measurements,
+        })
+    }
+}
+
+/// Whether a gate can map one basis state to a superposition of two.
+fn is_branching(inst: &Instruction) -> bool {
+    use GateType::*;
+    matches!(inst.gate.gate_type, H | Rx | Ry | U | CH | CU | SqrtSwap | Rxx | Ryy)
+}
+
+/// Whether a conditioned instruction should run, given the bits so far.
+fn condition_holds(measurements: &MeasurementResult, inst: &Instruction) -> bool {
+    inst.condition.map_or(true, |(clbit, value)| measurements.bits[clbit] == value)
+}
+
+/// Store a measurement outcome in the instruction's classical bit, if any.
+fn record(measurements: &mut MeasurementResult, inst: &Instruction, result: u8) {
+    if let Some(&clbit) = inst.clbits.first() {
+        measurements.bits[clbit] = result;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use homaya_core::HomayaError;
+
+    #[test]
+    fn test_clifford_circuit_uses_stabilizer() {
+        let n = 40;
+        let mut circuit = Circuit::new(n).h(0);
+        for q in 0..n - 1 {
+            circuit = circuit.cx(q, q + 1);
+        }
+        let circuit = circuit.s(5).sdg(5).cz(3, 7).measure_all();
+
+        for seed in [1, 2, 3, 4] {
+            let result = Simulator::with_seed(seed).run_auto(&circuit).unwrap();
+            assert_eq!(result.backend, Backend::Stabilizer);
+            assert!(matches!(result.state, AutoState::Stabilizer(_)));
+
+            let bits = &result.measurements.bits;
+            assert_eq!(bits.len(), n);
+            assert!(bits.iter().all(|&b| b == bits[0]), "GHZ bits disagree: {:?}", bits);
+        }
+    }
+
+    #[test]
+    fn test_sparse_x_circuit_uses_sparse() {
+        let n = 40;
+        let mut circuit = Circuit::new(n);
+        for q in (0..n).step_by(3) {
+            circuit = circuit.x(q);
+        }
+        // T makes it non-Clifford without adding branches
+        let circuit = circuit.t(0).ccx(0, 3, 1).measure_all();
+
+        let result = Simulator::with_seed(7).run_auto(&circuit).unwrap();
+        assert_eq!(result.backend, Backend::Sparse);
+
+        let expected = (0..n).step_by(3).fold(1u64 << 1, |acc, q| acc | (1 << q));
+        assert_eq!(result.measurements.as_int(), expected);
+        match result.state {
+            AutoState::Sparse(state) => {
+                assert_eq!(state.num_nonzero(), 1);
+                assert!((state.probability(expected) - 1.0).abs() < 1e-12);
+            }
+            other => panic!("expected sparse state, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_dense_circuit_matches_run() {
+        let n = 10;
+        let mut circuit = Circuit::new(n);
+        for q in 0..n {
+            circuit = circuit.h(q).t(q);
+        }
+        for q in 0..n - 1 {
+            circuit = circuit.cx(q, q + 1).ry(0.3 * q as f64, q);
+        }
+
+        let result = Simulator::new().run_auto(&circuit).unwrap();
+        assert_eq!(result.backend, Backend::Dense);
+
+        let expected = Simulator::new().run(&circuit).unwrap();
+        match result.state {
+            AutoState::Dense(state) => assert!((state.fidelity(&expected) - 1.0).abs() < 1e-12),
+            other => panic!("expected dense state, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_wide_non_sparse_circuit_is_too_large() {
+        // Too wide for a u64 basis index, and not Clifford
+        let circuit = Circuit::new(70).t(0);
+        assert!(matches!(
+            Simulator::new().run_auto(&circuit),
+            Err(HomayaError::CircuitTooLarge { qubits: 70, .. })
+        ));
+    }
+}
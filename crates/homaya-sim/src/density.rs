@@ -0,0 +1,611 @@
+//! Density matrix representation for mixed states.
+//!
+//! A density matrix ρ can describe statistical mixtures that a state vector
+//! cannot, such as a subsystem of an entangled state or the ensemble left
+//! behind by an unrecorded measurement. [`DensitySimulator`] runs whole
+//! circuits on them, averaging over measurement outcomes.
+
+use homaya_core::{Circuit, Complex, GateType, HomayaError, Instruction, Result};
+use crate::StateVector;
+
+/// A quantum density matrix.
+///
+/// Stores the full 2^n × 2^n matrix in row-major order, using the same
+/// little-endian basis ordering as [`StateVector`].
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_sim::{DensityMatrix, StateVector};
+///
+/// let rho = DensityMatrix::from_statevector(&StateVector::uniform(1));
+/// assert!((rho.trace().re - 1.0).abs() < 1e-10);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DensityMatrix {
+    /// Number of qubits
+    num_qubits: usize,
+    /// Matrix entries, row-major (length = 4^num_qubits)
+    data: Vec<Complex>,
+}
+
+impl DensityMatrix {
+    /// Maximum qubits for [`DensitySimulator`]; the matrix has 4^n entries.
+    pub const MAX_QUBITS: usize = 12;
+
+    /// Create a density matrix for the pure state |0...0⟩⟨0...0|.
+    pub fn new(num_qubits: usize) -> Self {
+        let dim = 1 << num_qubits;
+        let mut data = vec![Complex::ZERO; dim * dim];
+        data[0] = Complex::ONE;
+        Self { num_qubits, data }
+    }
+
+    /// Create the pure-state density matrix |ψ⟩⟨ψ| from a state vector.
+    pub fn from_statevector(state: &StateVector) -> Self {
+        let amps = state.amplitudes();
+        let dim = amps.len();
+        let mut data = Vec::with_capacity(dim * dim);
+        for a in amps {
+            for b in amps {
+                data.push(*a * b.conj());
+            }
+        }
+        Self {
+            num_qubits: state.num_qubits(),
+            data,
+        }
+    }
+
+    /// Get the number of qubits.
+    #[inline]
+    pub const fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    /// Get the dimension (2^n) of each side of the matrix.
+    #[inline]
+    pub fn dimension(&self) -> usize {
+        1 << self.num_qubits
+    }
+
+    /// Get the entry ρ[row][col].
+    #[inline]
+    pub fn get(&self, row: usize, col: usize) -> Complex {
+        self.data[row * self.dimension() + col]
+    }
+
+    /// Get the probability of measuring a specific basis state.
+    #[inline]
+    pub fn probability(&self, index: usize) -> f64 {
+        self.get(index, index).re
+    }
+
+    /// Compute the trace Tr(ρ).
+    pub fn trace(&self) -> Complex {
+        (0..self.dimension())
+            .map(|i| self.get(i, i))
+            .fold(Complex::ZERO, |acc, x| acc + x)
+    }
+
+    /// Trace out every qubit not listed in `keep`.
+    ///
+    /// Qubit `keep[j]` becomes qubit `j` of the returned matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keep` contains an out-of-range or duplicate qubit.
+    pub fn partial_trace(&self, keep: &[usize]) -> DensityMatrix {
+        for (j, &q) in keep.iter().enumerate() {
+            assert!(q < self.num_qubits, "qubit {} out of range for {} qubits", q, self.num_qubits);
+            assert!(!keep[..j].contains(&q), "duplicate qubit {} in partial trace", q);
+        }
+
+        let keep_mask = keep.iter().fold(0usize, |acc, &q| acc | (1 << q));
+        let pattern = |i: usize| {
+            keep.iter()
+                .enumerate()
+                .fold(0usize, |acc, (j, &q)| acc | (((i >> q) & 1) << j))
+        };
+
+        let dim = self.dimension();
+        let mut reduced = DensityMatrix {
+            num_qubits: keep.len(),
+            data: vec![Complex::ZERO; 1 << (2 * keep.len())],
+        };
+        let reduced_dim = reduced.dimension();
+
+        // ρ_A[a][a'] = Σ_b ρ[(a,b)][(a',b)]
+        for row in 0..dim {
+            for col in 0..dim {
+                if (row & !keep_mask) == (col & !keep_mask) {
+                    reduced.data[pattern(row) * reduced_dim + pattern(col)] += self.get(row, col);
+                }
+            }
+        }
+
+        reduced
+    }
+
+    /// Apply a single-qubit gate: ρ → UρU†.
+    pub fn apply_single(&mut self, qubit: usize, matrix: [[Complex; 2]; 2]) {
+        self.apply_matrix(&[qubit], &matrix);
+    }
+
+    /// Apply a controlled single-qubit gate: ρ → UρU† with U = |0⟩⟨0| ⊗ I + |1⟩⟨1| ⊗ `matrix`.
+    pub fn apply_controlled(&mut self, control: usize, target: usize, matrix: [[Complex; 2]; 2]) {
+        let (one, zero) = (Complex::ONE, Complex::ZERO);
+        let [[a, b], [c, d]] = matrix;
+        // Indexed by b_control + 2·b_target
+        self.apply_matrix(
+            &[control, target],
+            &[[one, zero, zero, zero], [zero, a, zero, b], [zero, zero, one, zero], [zero, c, zero, d]],
+        );
+    }
+
+    /// Apply a two-qubit gate, indexed as in [`StateVector::apply_two`].
+    pub fn apply_two(&mut self, q0: usize, q1: usize, matrix: [[Complex; 4]; 4]) {
+        self.apply_matrix(&[q0, q1], &matrix);
+    }
+
+    /// Apply a three-qubit gate, indexed as in [`StateVector::apply_three`].
+    pub fn apply_three(&mut self, q0: usize, q1: usize, q2: usize, matrix: [[Complex; 8]; 8]) {
+        self.apply_matrix(&[q0, q1, q2], &matrix);
+    }
+
+    /// Apply a multi-controlled X gate.
+    pub fn apply_mcx(&mut self, controls: &[usize], target: usize) {
+        let control_mask = controls.iter().fold(0usize, |acc, &c| acc | (1 << c));
+        let flip = |i: usize| if i & control_mask == control_mask { i ^ (1 << target) } else { i };
+
+        // A permutation P: (PρPᵀ)[f(r)][f(c)] = ρ[r][c]
+        let dim = self.dimension();
+        let mut data = vec![Complex::ZERO; dim * dim];
+        for row in 0..dim {
+            for col in 0..dim {
+                data[flip(row) * dim + flip(col)] = self.get(row, col);
+            }
+        }
+        self.data = data;
+    }
+
+    /// Apply U to the operands in `qubits`, with `matrix` indexed by
+    /// `Σ bₖ·2ᵏ` over their states: first ρ → Uρ, then ρ → ρU†.
+    fn apply_matrix<const D: usize>(&mut self, qubits: &[usize], matrix: &[[Complex; D]; D]) {
+        let dim = self.dimension();
+        let all = qubits.iter().fold(0usize, |acc, &q| acc | (1 << q));
+        let offsets: [usize; D] = std::array::from_fn(|k| {
+            qubits
+                .iter()
+                .enumerate()
+                .filter(|&(b, _)| k >> b & 1 == 1)
+                .fold(0, |acc, (_, &q)| acc | (1 << q))
+        });
+        let bases: Vec<usize> = (0..dim).filter(|i| i & all == 0).collect();
+
+        // Left multiplication mixes rows within each column
+        for col in 0..dim {
+            for &base in &bases {
+                let old = offsets.map(|o| self.data[(base | o) * dim + col]);
+                for (row, &o) in matrix.iter().zip(&offsets) {
+                    self.data[(base | o) * dim + col] =
+                        row.iter().zip(&old).fold(Complex::ZERO, |acc, (&m, &a)| acc + m * a);
+                }
+            }
+        }
+
+        // Right multiplication by U† mixes columns within each row
+        for row in 0..dim {
+            for &base in &bases {
+                let old = offsets.map(|o| self.data[row * dim + (base | o)]);
+                for (urow, &o) in matrix.iter().zip(&offsets) {
+                    self.data[row * dim + (base | o)] =
+                        urow.iter().zip(&old).fold(Complex::ZERO, |acc, (&m, &a)| acc + m.conj() * a);
+                }
+            }
+        }
+    }
+
+    /// Reset a qubit to |0⟩ without recording the outcome.
+    ///
+    /// Unlike a state vector, a density matrix can hold the average over
+    /// both outcomes, so no random draw is needed:
+    /// ρ → |0⟩⟨0|ρ|0⟩⟨0| + |0⟩⟨1|ρ|1⟩⟨0|.
+    pub fn reset(&mut self, qubit: usize) {
+        let mask = 1 << qubit;
+        let dim = self.dimension();
+        for row in (0..dim).filter(|r| r & mask == 0) {
+            for col in (0..dim).filter(|c| c & mask == 0) {
+                let moved = self.get(row | mask, col | mask);
+                self.data[row * dim + col] += moved;
+            }
+        }
+        for row in 0..dim {
+            for col in 0..dim {
+                if (row | col) & mask != 0 {
+                    self.data[row * dim + col] = Complex::ZERO;
+                }
+            }
+        }
+    }
+
+    /// The unnormalized branch PρP for `qubit` measuring `outcome`.
+    ///
+    /// Its trace is the probability of that outcome.
+    fn project(&self, qubit: usize, outcome: u8) -> DensityMatrix {
+        let mask = 1 << qubit;
+        let keeps = |i: usize| ((i & mask) != 0) == (outcome == 1);
+        let dim = self.dimension();
+        let mut projected = self.clone();
+        for row in 0..dim {
+            for col in 0..dim {
+                if !(keeps(row) && keeps(col)) {
+                    projected.data[row * dim + col] = Complex::ZERO;
+                }
+            }
+        }
+        projected
+    }
+
+    /// Add another matrix of the same size entrywise.
+    fn accumulate(&mut self, other: &DensityMatrix) {
+        for (a, b) in self.data.iter_mut().zip(&other.data) {
+            *a += *b;
+        }
+    }
+
+    /// Measure a single qubit, collapsing the state.
+    ///
+    /// Projects ρ onto the observed outcome and renormalizes:
+    /// ρ → PρP / Tr(PρP). Returns the measurement result (0 or 1).
+    pub fn measure(&mut self, qubit: usize, random: f64) -> u8 {
+        let mask = 1 << qubit;
+        let dim = self.dimension();
+
+        let prob_0: f64 = (0..dim)
+            .filter(|i| i & mask == 0)
+            .map(|i| self.probability(i))
+            .sum();
+
+        let result = if random < prob_0 { 0 } else { 1 };
+        let norm = if result == 0 { prob_0 } else { 1.0 - prob_0 };
+        let inv_norm = 1.0 / norm;
+        let keeps = |i: usize| ((i & mask) != 0) == (result == 1);
+
+        for row in 0..dim {
+            for col in 0..dim {
+                let entry = &mut self.data[row * dim + col];
+                if keeps(row) && keeps(col) {
+                    *entry = *entry * inv_norm;
+                } else {
+                    *entry = Complex::ZERO;
+                }
+            }
+        }
+
+        result
+    }
+}
+
+/// Circuit simulator on density matrices.
+///
+/// Where [`Simulator`](crate::Simulator) follows one random measurement
+/// record, this one tracks every record at once: each measurement splits
+/// the state into one branch per outcome, classically conditioned gates act
+/// only on the branches whose bits satisfy them, and the result is the
+/// ensemble average Σ pᵢρᵢ. No randomness is involved.
+///
+/// Memory grows as 4ⁿ per branch and up to 2^clbits branches, so this is
+/// meant for small circuits.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_core::Circuit;
+/// use homaya_sim::DensitySimulator;
+///
+/// // Measuring |+⟩ without looking leaves a 50/50 mixture
+/// let rho = DensitySimulator::new().run(&Circuit::new(1).h(0).measure(0, 0)).unwrap();
+/// assert!((rho.probability(1) - 0.5).abs() < 1e-12);
+/// assert!(rho.get(0, 1).is_zero(1e-12));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DensitySimulator;
+
+impl DensitySimulator {
+    /// Create a new density-matrix simulator.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Run a circuit from |0...0⟩ and return the averaged final state.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if [`Circuit::validate`] rejects the circuit, or
+    /// `CircuitTooLarge` if it has more than
+    /// [`DensityMatrix::MAX_QUBITS`] qubits.
+    pub fn run(&self, circuit: &Circuit) -> Result<DensityMatrix> {
+        if circuit.num_qubits() > DensityMatrix::MAX_QUBITS {
+            return Err(HomayaError::CircuitTooLarge {
+                qubits: circuit.num_qubits(),
+                max: DensityMatrix::MAX_QUBITS,
+            });
+        }
+        self.run_from(circuit, DensityMatrix::new(circuit.num_qubits()))
+    }
+
+    /// Run a circuit starting from a given density matrix.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the qubit counts differ or [`Circuit::validate`]
+    /// rejects the circuit.
+    pub fn run_from(&self, circuit: &Circuit, rho: DensityMatrix) -> Result<DensityMatrix> {
+        if rho.num_qubits() != circuit.num_qubits() {
+            return Err(HomayaError::QubitMismatch {
+                expected: circuit.num_qubits(),
+                got: rho.num_qubits(),
+            });
+        }
+        circuit.validate()?;
+
+        // One unnormalized branch per classical record, weighted by its trace
+        let mut branches = vec![(vec![0u8; circuit.num_clbits()], rho)];
+        for inst in circuit.instructions() {
+            if inst.gate.gate_type == GateType::Measure {
+                branches = measure_branches(branches, inst);
+                continue;
+            }
+            for (bits, rho) in &mut branches {
+                if inst.condition.map_or(true, |(clbit, value)| bits[clbit] == value) {
+                    apply_instruction(rho, inst)?;
+                }
+            }
+        }
+
+        let (_, mut total) = branches.pop().expect("at least one branch");
+        for (_, rho) in &branches {
+            total.accumulate(rho);
+        }
+        Ok(total)
+    }
+}
+
+/// Split every branch on a measurement, merging branches whose records agree.
+///
+/// A branch whose record fails the measurement's condition is kept as is.
+fn measure_branches(branches: Vec<(Vec<u8>, DensityMatrix)>, inst: &Instruction) -> Vec<(Vec<u8>, DensityMatrix)> {
+    let qubit = inst.qubits[0];
+    let mut split: Vec<(Vec<u8>, DensityMatrix)> = Vec::with_capacity(2 * branches.len());
+
+    for (bits, rho) in branches {
+        if inst.condition.is_some_and(|(clbit, value)| bits[clbit] != value) {
+            merge_branch(&mut split, bits, rho);
+            continue;
+        }
+        let Some(&clbit) = inst.clbits.first() else {
+            // Unrecorded: the branch just loses its coherence on `qubit`
+            let mut dephased = rho.project(qubit, 0);
+            dephased.accumulate(&rho.project(qubit, 1));
+            split.push((bits, dephased));
+            continue;
+        };
+
+        for outcome in [0, 1] {
+            let projected = rho.project(qubit, outcome);
+            if projected.trace().re <= 0.0 {
+                continue;
+            }
+            let mut record = bits.clone();
+            record[clbit] = outcome;
+            merge_branch(&mut split, record, projected);
+        }
+    }
+
+    split
+}
+
+/// Add a branch, summing it into an existing one with the same record.
+fn merge_branch(branches: &mut Vec<(Vec<u8>, DensityMatrix)>, record: Vec<u8>, rho: DensityMatrix) {
+    match branches.iter_mut().find(|(b, _)| *b == record) {
+        Some((_, existing)) => existing.accumulate(&rho),
+        None => branches.push((record, rho)),
+    }
+}
+
+/// Apply a non-measurement instruction to one branch.
+fn apply_instruction(rho: &mut DensityMatrix, inst: &Instruction) -> Result<()> {
+    use GateType::*;
+
+    let gate = &inst.gate;
+    let qubits = &inst.qubits;
+    let missing = |operation| HomayaError::NotSupported { operation };
+
+    match gate.gate_type {
+        I | X | Y | Z | H | S | Sdg | T | Tdg | Rx | Ry | Rz | P | U => {
+            rho.apply_single(qubits[0], gate.matrix_2x2().ok_or(missing("gate has no 2x2 matrix"))?);
+        }
+        CX | CY | CZ | CH | CP | CU | Swap | ISwap | SqrtSwap | Rxx | Ryy | Rzz => {
+            let matrix = gate.matrix_4x4().ok_or(missing("gate has no 4x4 matrix"))?;
+            rho.apply_two(qubits[0], qubits[1], matrix);
+        }
+        CCX | CSwap => {
+            let matrix = gate.matrix_8x8().ok_or(missing("gate has no 8x8 matrix"))?;
+            rho.apply_three(qubits[0], qubits[1], qubits[2], matrix);
+        }
+        MCX => {
+            let (target, controls) = qubits.split_last().ok_or(HomayaError::QubitMismatch {
+                expected: 1,
+                got: 0,
+            })?;
+            rho.apply_mcx(controls, *target);
+        }
+        Reset => rho.reset(qubits[0]),
+        Measure | Barrier | Snapshot => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Simulator;
+
+    fn bell() -> StateVector {
+        let h = Complex::from_real(std::f64::consts::FRAC_1_SQRT_2);
+        StateVector::from_amplitudes(vec![h, Complex::ZERO, Complex::ZERO, h]).unwrap()
+    }
+
+    #[test]
+    fn test_bell_partial_trace_is_maximally_mixed() {
+        let rho = DensityMatrix::from_statevector(&bell());
+
+        for keep in [0, 1] {
+            let reduced = rho.partial_trace(&[keep]);
+            assert_eq!(reduced.num_qubits(), 1);
+            assert!(reduced.get(0, 0).approx_eq(Complex::from_real(0.5), 1e-10));
+            assert!(reduced.get(1, 1).approx_eq(Complex::from_real(0.5), 1e-10));
+            assert!(reduced.get(0, 1).is_zero(1e-10));
+            assert!(reduced.get(1, 0).is_zero(1e-10));
+        }
+    }
+
+    #[test]
+    fn test_measure_collapses_bell() {
+        let mut rho = DensityMatrix::from_statevector(&bell());
+
+        let result = rho.measure(0, 0.9);
+        assert_eq!(result, 1);
+        assert!((rho.probability(0b11) - 1.0).abs() < 1e-10);
+        assert!((rho.trace().re - 1.0).abs() < 1e-10);
+        assert!(rho.get(0, 3).is_zero(1e-10));
+    }
+
+    #[test]
+    fn test_pure_circuit_matches_outer_product() {
+        let circuit = Circuit::new(4)
+            .h(0)
+            .ry(0.7, 1)
+            .cx(0, 2)
+            .cu(0.3, 0.5, -0.2, 1, 3)
+            .iswap(2, 3)
+            .t(3)
+            .ccx(0, 3, 1)
+            .cswap(2, 1, 0)
+            .mcx(&[0, 1, 2], 3)
+            .rzz(0.4, 1, 2);
+
+        let rho = DensitySimulator::new().run(&circuit).unwrap();
+        let state = Simulator::new().run(&circuit).unwrap();
+        let expected = DensityMatrix::from_statevector(&state);
+
+        assert!((rho.trace().re - 1.0).abs() < 1e-10);
+        for row in 0..16 {
+            for col in 0..16 {
+                assert!(rho.get(row, col).approx_eq(expected.get(row, col), 1e-10), "ρ[{}][{}]", row, col);
+            }
+        }
+    }
+
+    #[test]
+    fn test_apply_single_conjugates() {
+        let mut rho = DensityMatrix::new(1);
+        let h = Complex::from_real(std::f64::consts::FRAC_1_SQRT_2);
+        rho.apply_single(0, [[h, h], [h, -h]]);
+        for (row, col) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+            assert!(rho.get(row, col).approx_eq(Complex::from_real(0.5), 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_measurement_ensemble_is_mixed() {
+        // A measured Bell pair is a classical 50/50 mixture of |00⟩ and |11⟩
+        let circuit = Circuit::new(2).h(0).cx(0, 1).measure(0, 0);
+        let rho = DensitySimulator::new().run(&circuit).unwrap();
+
+        assert!((rho.trace().re - 1.0).abs() < 1e-12);
+        assert!((rho.probability(0b00) - 0.5).abs() < 1e-12);
+        assert!((rho.probability(0b11) - 0.5).abs() < 1e-12);
+        assert!(rho.get(0b00, 0b11).is_zero(1e-12));
+
+        // Resetting the measured qubit leaves qubit 1 mixed
+        let rho = DensitySimulator::new().run(&circuit.reset(0)).unwrap();
+        assert!((rho.probability(0b00) - 0.5).abs() < 1e-12);
+        assert!((rho.probability(0b10) - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_teleportation_ensemble() {
+        // Averaged over all four records, the corrections make qubit 2 pure
+        let theta = 1.1;
+        let circuit = Circuit::new(3)
+            .ry(theta, 0)
+            .h(1)
+            .cx(1, 2)
+            .cx(0, 1)
+            .h(0)
+            .measure(0, 0)
+            .measure(1, 1)
+            .x_if(2, 1, 1)
+            .z_if(2, 0, 1);
+        let rho = DensitySimulator::new().run(&circuit).unwrap();
+        let qubit_2 = rho.partial_trace(&[2]);
+
+        let (c, s) = ((theta / 2.0).cos(), (theta / 2.0).sin());
+        assert!(qubit_2.get(0, 0).approx_eq(Complex::from_real(c * c), 1e-10));
+        assert!(qubit_2.get(0, 1).approx_eq(Complex::from_real(c * s), 1e-10));
+        assert!(qubit_2.get(1, 1).approx_eq(Complex::from_real(s * s), 1e-10));
+
+        // Without the corrections it is maximally mixed
+        let uncorrected = Circuit::new(3).ry(theta, 0).h(1).cx(1, 2).cx(0, 1).h(0).measure(0, 0).measure(1, 1);
+        let qubit_2 = DensitySimulator::new().run(&uncorrected).unwrap().partial_trace(&[2]);
+        assert!(qubit_2.get(0, 0).approx_eq(Complex::from_real(0.5), 1e-10));
+        assert!(qubit_2.get(0, 1).is_zero(1e-10));
+    }
+
+    #[test]
+    fn test_conditioned_measurement_follows_record() {
+        let program = |set_c0: &str| {
+            std::format!(
+                "OPENQASM 2.0;\ninclude \"qelib1.inc\";\nqreg q[2];\ncreg c[2];\n{}\
+                 h q[0];\nif(c[0]==1) measure q[0] -> c[1];\nh q[0];\n",
+                set_c0
+            )
+        };
+
+        // c[0] = 0: the measurement is skipped and H·H is the identity
+        let skipped = Circuit::from_qasm(&program("")).unwrap();
+        let rho = DensitySimulator::new().run(&skipped).unwrap();
+        let state = Simulator::new().run(&skipped).unwrap();
+        for i in 0..4 {
+            assert!((rho.probability(i) - state.probability(i)).abs() < 1e-10, "i={}", i);
+        }
+        assert!((rho.probability(0) - 1.0).abs() < 1e-10);
+
+        // c[0] = 1: the measurement runs and leaves qubit 0 mixed
+        let taken = Circuit::from_qasm(&program("x q[1];\nmeasure q[1] -> c[0];\n")).unwrap();
+        let rho = DensitySimulator::new().run(&taken).unwrap();
+        assert!((rho.probability(0b10) - 0.5).abs() < 1e-10);
+        assert!((rho.probability(0b11) - 0.5).abs() < 1e-10);
+        for seed in 0..8 {
+            let state = Simulator::with_seed(seed).run(&taken).unwrap();
+            assert!((state.probability(0b10) - 0.5).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_density_simulator_size_limit() {
+        assert!(matches!(
+            DensitySimulator::new().run(&Circuit::new(DensityMatrix::MAX_QUBITS + 1)),
+            Err(HomayaError::CircuitTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate qubit")]
+    fn test_partial_trace_rejects_duplicates() {
+        DensityMatrix::new(2).partial_trace(&[0, 0]);
+    }
+}
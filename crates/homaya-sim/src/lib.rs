@@ -6,6 +6,11 @@
 //! - **Full gate support** including all standard gates
 //! - **Measurement** with proper state collapse
 //! - **Sampling** for running multiple shots
+//! - **Density matrices** for mixed states, subsystems and measurement
+//!   ensembles
+//! - **SPSA gradients** for variational circuits under shot noise
+//! - **Automatic backends**: stabilizer tableaus for Clifford circuits and
+//!   sparse states for circuits with few superpositions
 //!
 //! # Example
 //!
@@ -46,9 +51,20 @@
 //! ```
 
 #![deny(missing_docs)]
+#![deny(unsafe_code)]
 
 mod statevector;
 mod simulator;
+mod density;
+mod gradient;
+mod simd;
+mod stabilizer;
+mod sparse;
+mod auto;
 
 pub use statevector::StateVector;
+pub use density::{DensityMatrix, DensitySimulator};
 pub use simulator::{Simulator, MeasurementResult};
+pub use stabilizer::StabilizerState;
+pub use sparse::SparseState;
+pub use auto::{AutoResult, AutoState, Backend};
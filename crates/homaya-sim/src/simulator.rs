@@ -2,9 +2,12 @@
 //!
 //! Executes quantum circuits on state vectors.
 
-use homaya_core::{Circuit, Complex, Gate, GateType, GateParams, HomayaError, Result, INV_SQRT_2, PI};
+use homaya_core::{Circuit, Complex, Gate, GateType, GateParams, HomayaError, Instruction, Result, INV_SQRT_2};
 use crate::StateVector;
 
+/// Roughly how many times `run_with_progress` reports before finishing.
+const PROGRESS_UPDATES: usize = 50;
+
 /// Measurement results from circuit execution.
 #[derive(Clone, Debug, Default)]
 pub struct MeasurementResult {
@@ -51,6 +54,18 @@ impl MeasurementResult {
 /// assert!(state.probability(0) > 0.49); // |00⟩
 /// assert!(state.probability(3) > 0.49); // |11⟩
 /// ```
+///
+/// # Reproducibility
+///
+/// With a fixed seed, results are fully determined by the order of random
+/// draws, which is part of the public contract:
+///
+/// - instructions execute in circuit order;
+/// - each `Measure` and each `Reset` draws exactly one number, when it executes;
+/// - unitary gates and barriers never draw;
+/// - [`sample`](Self::sample) rewinds to the seed, then runs shots back to
+///   back; when all measurements are terminal it instead draws exactly one
+///   number per shot from the final state.
 #[derive(Clone, Debug)]
 pub struct Simulator {
     /// Random seed for measurements
@@ -82,19 +97,44 @@ impl Simulator {
         }
     }
 
-    /// Simple xorshift64 PRNG for fast random numbers.
-    fn next_random(&mut self) -> f64 {
-        let mut x = self.rng_state;
-        x ^= x << 13;
-        x ^= x >> 7;
-        x ^= x << 17;
-        self.rng_state = x;
-        (x as f64) / (u64::MAX as f64)
+    /// Draw a uniform number in `[0, 1)` from a SplitMix64 stream.
+    ///
+    /// SplitMix64 steps its state by a fixed odd constant, so every seed
+    /// (including 0) gives a full-period stream, and nearby seeds give
+    /// unrelated ones.
+    pub(crate) fn next_random(&mut self) -> f64 {
+        // Top 53 bits fill an f64 mantissa exactly
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Next raw 64-bit output of the SplitMix64 stream.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.rng_state = self.rng_state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.rng_state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Split off an unseeded simulator with its own stream, advancing this one.
+    fn split(&mut self) -> Simulator {
+        Simulator {
+            seed: None,
+            rng_state: self.next_u64(),
+        }
     }
 
     /// Run a circuit and return the final state.
+    ///
+    /// A zero-qubit circuit yields the trivial dimension-1 state.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if [`Circuit::validate`] rejects the circuit, e.g. for
+    /// `cx(0, 0)` or a qubit index past the end, or `CircuitTooLarge` if it
+    /// has more than [`StateVector::MAX_QUBITS`] qubits.
     pub fn run(&mut self, circuit: &Circuit) -> Result<StateVector> {
-        self.run_from_state(circuit, StateVector::new(circuit.num_qubits()))
+        self.run_from_state(circuit, StateVector::try_new(circuit.num_qubits())?)
     }
 
     /// Run a circuit starting from a given state.
@@ -106,37 +146,268 @@ impl Simulator {
             });
         }
 
+        circuit.validate()?;
+
         let mut state = state;
         let mut measurements = MeasurementResult::new(circuit.num_clbits());
 
-        for inst in circuit.instructions() {
-            self.apply_instruction(&mut state, &inst.gate, &inst.qubits, &inst.clbits, &mut measurements)?;
+        self.execute(&mut state, circuit.instructions(), &mut measurements)?;
+
+        Ok(state)
+    }
+
+    /// Run a circuit, reporting progress as `progress(done, total)`.
+    ///
+    /// The callback fires after roughly every 2% of the instructions and
+    /// always once at the end with `done == total`, so its overhead stays
+    /// negligible even for very long circuits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::Simulator;
+    ///
+    /// let circuit = Circuit::new(2).h(0).cx(0, 1);
+    /// let mut last = 0;
+    /// Simulator::new().run_with_progress(&circuit, |done, _| last = done).unwrap();
+    /// assert_eq!(last, 2);
+    /// ```
+    pub fn run_with_progress(
+        &mut self,
+        circuit: &Circuit,
+        mut progress: impl FnMut(usize, usize),
+    ) -> Result<StateVector> {
+        circuit.validate()?;
+
+        let total = circuit.len();
+        let interval = (total / PROGRESS_UPDATES).max(1);
+        let mut state = StateVector::try_new(circuit.num_qubits())?;
+        let mut measurements = MeasurementResult::new(circuit.num_clbits());
+
+        for (i, inst) in circuit.instructions().iter().enumerate() {
+            self.apply_instruction(&mut state, inst, &mut measurements)?;
+            let done = i + 1;
+            if done % interval == 0 && done != total {
+                progress(done, total);
+            }
         }
+        progress(total, total);
 
         Ok(state)
     }
 
     /// Run a circuit with measurements and return both state and results.
     pub fn run_with_measurements(&mut self, circuit: &Circuit) -> Result<(StateVector, MeasurementResult)> {
-        let mut state = StateVector::new(circuit.num_qubits());
+        circuit.validate()?;
+
+        let mut state = StateVector::try_new(circuit.num_qubits())?;
         let mut measurements = MeasurementResult::new(circuit.num_clbits());
 
-        for inst in circuit.instructions() {
-            self.apply_instruction(&mut state, &inst.gate, &inst.qubits, &inst.clbits, &mut measurements)?;
-        }
+        self.execute(&mut state, circuit.instructions(), &mut measurements)?;
 
         Ok((state, measurements))
     }
 
+    /// Run a circuit, capturing the state at each snapshot.
+    ///
+    /// Returns the final state and measurement results as
+    /// [`run_with_measurements`](Self::run_with_measurements) does, plus a
+    /// copy of the state at every [`Circuit::snapshot`], keyed by label.
+    /// A conditioned snapshot is taken only if its condition holds, and a
+    /// label reached twice keeps the later state.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::Simulator;
+    ///
+    /// let circuit = Circuit::new(1).h(0).snapshot("plus").h(0);
+    /// let (state, _, snapshots) = Simulator::new().run_with_snapshots(&circuit).unwrap();
+    /// assert!((snapshots["plus"].probability(1) - 0.5).abs() < 1e-12);
+    /// assert!((state.probability(0) - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn run_with_snapshots(
+        &mut self,
+        circuit: &Circuit,
+    ) -> Result<(StateVector, MeasurementResult, std::collections::HashMap<String, StateVector>)> {
+        circuit.validate()?;
+
+        let mut state = StateVector::try_new(circuit.num_qubits())?;
+        let mut measurements = MeasurementResult::new(circuit.num_clbits());
+        let mut snapshots = std::collections::HashMap::new();
+
+        let mut rest = circuit.instructions();
+        while let Some(at) = rest.iter().position(|inst| inst.gate.gate_type == GateType::Snapshot) {
+            self.execute(&mut state, &rest[..at], &mut measurements)?;
+            let snapshot = &rest[at];
+            if snapshot.condition.map_or(true, |(clbit, value)| measurements.bits[clbit] == value) {
+                snapshots.insert(snapshot.label.clone().unwrap_or_default(), state.clone());
+            }
+            rest = &rest[at + 1..];
+        }
+        self.execute(&mut state, rest, &mut measurements)?;
+
+        Ok((state, measurements, snapshots))
+    }
+
+    /// Compute the exact expectation of a Pauli string after running `circuit`.
+    ///
+    /// See [`StateVector::expectation_pauli`] for the format of `paulis`.
+    /// Any measurements in the circuit collapse the state as in [`run`](Self::run).
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the circuit cannot be simulated or the Pauli string
+    /// is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::Simulator;
+    ///
+    /// let bell = Circuit::new(2).h(0).cx(0, 1);
+    /// let zz = Simulator::new().expectation(&bell, &[(0, 'Z'), (1, 'Z')]).unwrap();
+    /// assert!((zz - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn expectation(&mut self, circuit: &Circuit, paulis: &[(usize, char)]) -> Result<f64> {
+        self.run(circuit)?.expectation_pauli(paulis)
+    }
+
+    /// Compute the expectation of a weighted sum of Pauli strings.
+    ///
+    /// Each term is a `(coefficient, pauli_string)` pair. The circuit runs
+    /// once and every term is evaluated on the same final state, so this
+    /// gives a full Hamiltonian expectation Σ cₖ⟨Pₖ⟩ for one state
+    /// preparation.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the circuit cannot be simulated or any Pauli string
+    /// is invalid.
+    pub fn expectation_many(&mut self, circuit: &Circuit, terms: &[(f64, Vec<(usize, char)>)]) -> Result<f64> {
+        let state = self.run(circuit)?;
+        terms
+            .iter()
+            .map(|(coefficient, paulis)| Ok(coefficient * state.expectation_pauli(paulis)?))
+            .sum()
+    }
+
     /// Sample the circuit multiple times.
+    ///
+    /// Circuits without classical bits (including zero-qubit circuits)
+    /// report every shot under the empty bitstring `""`.
+    ///
+    /// When every measurement is terminal (the circuit is unitary up to a
+    /// final block of measurements and barriers, with no conditions), the
+    /// circuit runs once and each shot is drawn from the final distribution
+    /// in O(log 2ⁿ). Otherwise each shot re-runs the whole circuit. For a
+    /// 20-qubit, 300-gate circuit at 10,000 shots that is about a second
+    /// instead of over two hours; see the `bench_sampling` example.
     pub fn sample(&mut self, circuit: &Circuit, shots: usize) -> Result<std::collections::HashMap<String, usize>> {
-        let mut counts = std::collections::HashMap::new();
-
         // Reset seed if specified
         if let Some(seed) = self.seed {
             self.rng_state = seed;
         }
 
+        self.sample_shots(circuit, shots)
+    }
+
+    /// Sample the circuit and return the counts as a histogram.
+    ///
+    /// Same as [`sample`](Self::sample), packaged as a
+    /// [`homaya_types::MeasurementResult`] for its `probability`,
+    /// `most_likely` and `shannon_entropy` summaries, and for merging with
+    /// other runs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::Simulator;
+    ///
+    /// let circuit = Circuit::new(1).h(0).measure(0, 0);
+    /// let result = Simulator::with_seed(3).sample_typed(&circuit, 1000).unwrap();
+    /// assert!((result.shannon_entropy() - 1.0).abs() < 0.01);
+    /// ```
+    pub fn sample_typed(&mut self, circuit: &Circuit, shots: usize) -> Result<homaya_types::MeasurementResult> {
+        let mut result = homaya_types::MeasurementResult::new(shots);
+        result.counts = self.sample(circuit, shots)?;
+        Ok(result)
+    }
+
+    /// Run several circuits and return their final states, in order.
+    ///
+    /// Each circuit gets its own random stream, split off this simulator's
+    /// stream one after another, so measurements in different circuits are
+    /// independent. With the `parallel` feature the circuits run
+    /// concurrently; the results are the same either way.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if any circuit fails to run.
+    pub fn run_batch(&mut self, circuits: &[Circuit]) -> Result<Vec<StateVector>> {
+        self.batch(circuits, |sim, circuit| sim.run(circuit))
+    }
+
+    /// Sample several circuits, returning the counts for each, in order.
+    ///
+    /// Like [`sample`](Self::sample), this rewinds to the seed if one is
+    /// set, but only once for the whole batch; each circuit then samples
+    /// from its own stream as in [`run_batch`](Self::run_batch).
+    ///
+    /// # Errors
+    ///
+    /// Returns error if any circuit fails to run.
+    pub fn sample_batch(
+        &mut self,
+        circuits: &[Circuit],
+        shots: usize,
+    ) -> Result<Vec<std::collections::HashMap<String, usize>>> {
+        if let Some(seed) = self.seed {
+            self.rng_state = seed;
+        }
+
+        self.batch(circuits, |sim, circuit| sim.sample_shots(circuit, shots))
+    }
+
+    /// Run `job` on every circuit, each with a stream split off this one.
+    fn batch<T, F>(&mut self, circuits: &[Circuit], job: F) -> Result<Vec<T>>
+    where
+        T: Send,
+        F: Fn(&mut Simulator, &Circuit) -> Result<T> + Sync,
+    {
+        // Split sequentially so the streams don't depend on scheduling
+        let streams: Vec<Simulator> = circuits.iter().map(|_| self.split()).collect();
+
+        #[cfg(feature = "parallel")]
+        let results = {
+            use rayon::prelude::*;
+            streams
+                .into_par_iter()
+                .zip(circuits.par_iter())
+                .map(|(mut sim, circuit)| job(&mut sim, circuit))
+                .collect()
+        };
+        #[cfg(not(feature = "parallel"))]
+        let results = streams
+            .into_iter()
+            .zip(circuits)
+            .map(|(mut sim, circuit)| job(&mut sim, circuit))
+            .collect();
+
+        results
+    }
+
+    /// Sample without rewinding the random stream.
+    fn sample_shots(&mut self, circuit: &Circuit, shots: usize) -> Result<std::collections::HashMap<String, usize>> {
+        if let Some(split) = terminal_measurements(circuit) {
+            return self.sample_final_state(circuit, split, shots);
+        }
+
+        let mut counts = std::collections::HashMap::new();
         for _ in 0..shots {
             let (_, result) = self.run_with_measurements(circuit)?;
             *counts.entry(result.bitstring()).or_insert(0) += 1;
@@ -145,17 +416,164 @@ impl Simulator {
         Ok(counts)
     }
 
+    /// Sample a circuit whose instructions from `split` on are all terminal
+    /// measurements, simulating the unitary part only once.
+    fn sample_final_state(
+        &mut self,
+        circuit: &Circuit,
+        split: usize,
+        shots: usize,
+    ) -> Result<std::collections::HashMap<String, usize>> {
+        circuit.validate()?;
+
+        let (unitary, terminal) = circuit.instructions().split_at(split);
+        let mut state = StateVector::try_new(circuit.num_qubits())?;
+        self.execute(&mut state, unitary, &mut MeasurementResult::new(0))?;
+
+        // Same outcome rule as `StateVector::sample`, by binary search
+        let mut cumulative = Vec::with_capacity(state.dimension());
+        let mut total = 0.0;
+        for p in state.probabilities() {
+            total += p;
+            cumulative.push(total);
+        }
+        let last_nonzero = state.probabilities().iter().rposition(|&p| p > 0.0).unwrap_or(0);
+
+        let mut by_index = std::collections::HashMap::new();
+        for _ in 0..shots {
+            let random = self.next_random();
+            let index = match cumulative.partition_point(|&c| c <= random) {
+                i if i < cumulative.len() => i,
+                _ => last_nonzero,
+            };
+            *by_index.entry(index).or_insert(0) += 1;
+        }
+
+        let counts = by_index
+            .into_iter()
+            .map(|(index, count)| {
+                let mut result = MeasurementResult::new(circuit.num_clbits());
+                for inst in terminal.iter().filter(|inst| inst.gate.gate_type == GateType::Measure) {
+                    if let Some(&clbit) = inst.clbits.first() {
+                        result.bits[clbit] = (index >> inst.qubits[0] & 1) as u8;
+                    }
+                }
+                (result.bitstring(), count)
+            })
+            .fold(std::collections::HashMap::new(), |mut counts, (bits, count)| {
+                *counts.entry(bits).or_insert(0) += count;
+                counts
+            });
+
+        Ok(counts)
+    }
+
+    /// Run the circuit forward and then backward, and report the drift.
+    ///
+    /// Starting from a fixed, non-trivial probe state |ψ⟩, applies the
+    /// circuit U and then U† from [`Circuit::inverse`], and returns the infidelity
+    /// 1 - |⟨ψ|U†U|ψ⟩|². A correct simulator gives ~0; anything larger points
+    /// at a gate implementation that is not the unitary it claims to be.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the circuit contains measurements or resets, or a
+    /// gate the simulator does not implement.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_core::Circuit;
+    /// use homaya_sim::Simulator;
+    ///
+    /// let circuit = Circuit::new(3).h(0).t(1).ccx(0, 1, 2);
+    /// let drift = Simulator::new().self_inverse_infidelity(&circuit).unwrap();
+    /// assert!(drift < 1e-10);
+    /// ```
+    pub fn self_inverse_infidelity(&mut self, circuit: &Circuit) -> Result<f64> {
+        let non_unitary = |inst: &Instruction| matches!(inst.gate.gate_type, GateType::Measure | GateType::Reset);
+        if circuit.instructions().iter().any(non_unitary) {
+            return Err(HomayaError::NotSupported {
+                operation: "reversible check needs a unitary circuit",
+            });
+        }
+
+        let inverse = circuit.clone().inverse();
+        self.round_trip_infidelity(circuit, inverse.instructions())
+    }
+
+    /// Apply `circuit` then `inverse` to the probe state and measure the drift.
+    fn round_trip_infidelity(&mut self, circuit: &Circuit, inverse: &[Instruction]) -> Result<f64> {
+        let probe = probe_state(circuit.num_qubits());
+        let mut state = probe.clone();
+        let mut measurements = MeasurementResult::new(circuit.num_clbits());
+
+        for inst in circuit.instructions().iter().chain(inverse) {
+            self.apply_instruction(&mut state, inst, &mut measurements)?;
+        }
+
+        Ok(1.0 - probe.fidelity(&state))
+    }
+
+    /// Apply a sequence of instructions to the state.
+    ///
+    /// Consecutive SWAPs are composed into one qubit permutation and applied
+    /// in a single pass over the amplitudes.
+    fn execute(
+        &mut self,
+        state: &mut StateVector,
+        instructions: &[Instruction],
+        measurements: &mut MeasurementResult,
+    ) -> Result<()> {
+        let is_swap = |inst: &Instruction| inst.gate.gate_type == GateType::Swap && inst.condition.is_none();
+
+        let mut i = 0;
+        while i < instructions.len() {
+            let run = instructions[i..].iter().take_while(|inst| is_swap(inst)).count();
+            if run >= 2 {
+                // dest[q]: where the data that started on qubit q ends up
+                let mut dest: Vec<usize> = (0..state.num_qubits()).collect();
+                for inst in &instructions[i..i + run] {
+                    let (a, b) = (inst.qubits[0], inst.qubits[1]);
+                    for d in dest.iter_mut() {
+                        if *d == a {
+                            *d = b;
+                        } else if *d == b {
+                            *d = a;
+                        }
+                    }
+                }
+                state.apply_qubit_permutation(&dest);
+                i += run;
+            } else {
+                let inst = &instructions[i];
+                self.apply_instruction(state, inst, measurements)?;
+                i += 1;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Apply a single instruction to the state.
+    ///
+    /// A conditioned instruction is skipped unless its classical bit in
+    /// `measurements` holds the required value.
     fn apply_instruction(
         &mut self,
         state: &mut StateVector,
-        gate: &Gate,
-        qubits: &[usize],
-        clbits: &[usize],
+        inst: &Instruction,
         measurements: &mut MeasurementResult,
     ) -> Result<()> {
         use GateType::*;
 
+        let Instruction { gate, qubits, clbits, condition, .. } = inst;
+        if let Some((clbit, value)) = *condition {
+            if measurements.bits[clbit] != value {
+                return Ok(());
+            }
+        }
+
         match gate.gate_type {
             // Single-qubit gates
             I | X | Y | Z | H | S | Sdg | T | Tdg | Rx | Ry | Rz | P | U => {
@@ -193,6 +611,13 @@ impl Simulator {
                 }
             }
 
+            CU => {
+                if let GateParams::Angles3(theta, phi, lambda) = gate.params {
+                    let matrix = self.get_single_qubit_matrix(&Gate::u(theta, phi, lambda))?;
+                    state.apply_controlled(qubits[0], qubits[1], matrix);
+                }
+            }
+
             Swap => {
                 let swap_matrix = [
                     [Complex::ONE, Complex::ZERO, Complex::ZERO, Complex::ZERO],
@@ -203,15 +628,26 @@ impl Simulator {
                 state.apply_two(qubits[0], qubits[1], swap_matrix);
             }
 
-            // Three-qubit gates (decomposed)
-            CCX => {
-                // Toffoli decomposition using 6 CNOTs and single-qubit gates
-                self.apply_ccx(state, qubits[0], qubits[1], qubits[2]);
+            ISwap | SqrtSwap | Rxx | Ryy | Rzz => {
+                let matrix = gate.matrix_4x4().ok_or(HomayaError::NotSupported {
+                    operation: "gate has no 4x4 matrix",
+                })?;
+                state.apply_two(qubits[0], qubits[1], matrix);
             }
 
-            CSwap => {
-                // Fredkin = CNOT + Toffoli + CNOT
-                self.apply_cswap(state, qubits[0], qubits[1], qubits[2]);
+            CCX | CSwap => {
+                let matrix = gate.matrix_8x8().ok_or(HomayaError::NotSupported {
+                    operation: "gate has no 8x8 matrix",
+                })?;
+                state.apply_three(qubits[0], qubits[1], qubits[2], matrix);
+            }
+
+            MCX => {
+                let (target, controls) = qubits.split_last().ok_or(HomayaError::QubitMismatch {
+                    expected: 1,
+                    got: 0,
+                })?;
+                state.apply_mcx(controls, *target);
             }
 
             // Measurement
@@ -228,15 +664,9 @@ impl Simulator {
                 state.reset(qubits[0], random);
             }
 
-            Barrier => {
+            Barrier | Snapshot => {
                 // No-op for simulation
             }
-
-            _ => {
-                return Err(HomayaError::NotSupported {
-                    operation: "gate type not implemented",
-                });
-            }
         }
 
         Ok(())
@@ -248,51 +678,42 @@ impl Simulator {
             operation: "gate has no 2x2 matrix",
         })
     }
+}
 
-    /// Apply Toffoli (CCX) gate using decomposition.
-    fn apply_ccx(&mut self, state: &mut StateVector, c1: usize, c2: usize, target: usize) {
-        // Standard Toffoli decomposition
-        let h = Complex::from_real(INV_SQRT_2);
-        let h_matrix = [[h, h], [h, -h]];
-        let t = Complex::from_polar(1.0, PI / 4.0);
-        let tdg = Complex::from_polar(1.0, -PI / 4.0);
-        let t_matrix = [[Complex::ONE, Complex::ZERO], [Complex::ZERO, t]];
-        let tdg_matrix = [[Complex::ONE, Complex::ZERO], [Complex::ZERO, tdg]];
-        let x_matrix = [[Complex::ZERO, Complex::ONE], [Complex::ONE, Complex::ZERO]];
-
-        state.apply_single(target, h_matrix);
-        state.apply_controlled(c2, target, x_matrix);
-        state.apply_single(target, tdg_matrix);
-        state.apply_controlled(c1, target, x_matrix);
-        state.apply_single(target, t_matrix);
-        state.apply_controlled(c2, target, x_matrix);
-        state.apply_single(target, tdg_matrix);
-        state.apply_controlled(c1, target, x_matrix);
-        state.apply_single(c2, t_matrix);
-        state.apply_single(target, t_matrix);
-        state.apply_single(target, h_matrix);
-        state.apply_controlled(c1, c2, x_matrix);
-        state.apply_single(c2, tdg_matrix);
-        state.apply_controlled(c1, c2, x_matrix);
-        state.apply_single(c1, t_matrix);
-        state.apply_single(c2, [[Complex::ONE, Complex::ZERO], [Complex::ZERO, Complex::I]]);
-    }
-
-    /// Apply Fredkin (CSWAP) gate.
-    fn apply_cswap(&mut self, state: &mut StateVector, control: usize, t1: usize, t2: usize) {
-        let x_matrix = [[Complex::ZERO, Complex::ONE], [Complex::ONE, Complex::ZERO]];
-
-        // CSWAP = CNOT(t2, t1) + Toffoli(control, t1, t2) + CNOT(t2, t1)
-        state.apply_controlled(t2, t1, x_matrix);
-        self.apply_ccx(state, control, t1, t2);
-        state.apply_controlled(t2, t1, x_matrix);
-    }
+/// Where the terminal measurements of a circuit begin, if all of them are.
+///
+/// Returns the index after which only measurements and barriers remain,
+/// provided nothing before it measures or resets and nothing is classically
+/// conditioned. Such circuits can be sampled from a single final state.
+fn terminal_measurements(circuit: &Circuit) -> Option<usize> {
+    let instructions = circuit.instructions();
+    let split = instructions
+        .iter()
+        .rposition(|inst| !matches!(inst.gate.gate_type, GateType::Measure | GateType::Barrier))
+        .map_or(0, |i| i + 1);
+
+    let unitary = instructions[..split]
+        .iter()
+        .all(|inst| !matches!(inst.gate.gate_type, GateType::Measure | GateType::Reset));
+    let unconditioned = instructions.iter().all(|inst| inst.condition.is_none());
+    (unitary && unconditioned).then_some(split)
+}
+
+/// A fixed probe state with distinct magnitudes and phases on every basis state.
+fn probe_state(num_qubits: usize) -> StateVector {
+    let dim = 1usize << num_qubits;
+    let raw: Vec<Complex> = (0..dim)
+        .map(|k| Complex::from_polar(1.0 + (k % 3) as f64, 2.4 * k as f64))
+        .collect();
+    let norm = raw.iter().map(|c| c.norm_sqr()).sum::<f64>().sqrt();
+    StateVector::from_amplitudes(raw.into_iter().map(|c| c * (1.0 / norm)).collect())
+        .expect("probe state is normalized")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use homaya_core::Circuit;
+    use homaya_core::{Circuit, PI};
 
     #[test]
     fn test_simple_circuit() {
@@ -373,6 +794,60 @@ mod tests {
         assert!(state.probability(0b111) > 0.99);
     }
 
+    #[test]
+    fn test_zero_qubit_circuit() {
+        let circuit = Circuit::new(0).measure_all();
+        assert_eq!(circuit.depth(), 0);
+        assert!(circuit.is_empty());
+
+        let mut sim = Simulator::with_seed(7);
+        let state = sim.run(&circuit).unwrap();
+        assert_eq!(state.num_qubits(), 0);
+        assert_eq!(state.dimension(), 1);
+        assert!((state.probability(0) - 1.0).abs() < 1e-10);
+
+        let counts = sim.sample(&circuit, 10).unwrap();
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts.get(""), Some(&10));
+    }
+
+    #[test]
+    fn test_empty_circuit_on_qubits() {
+        let circuit = Circuit::new(3);
+        assert_eq!(circuit.depth(), 0);
+
+        let mut sim = Simulator::new();
+        let state = sim.run(&circuit).unwrap();
+        assert_eq!(state, StateVector::new(3));
+
+        let counts = sim.sample(&circuit.measure_all(), 5).unwrap();
+        assert_eq!(counts.get("000"), Some(&5));
+    }
+
+    #[test]
+    fn test_mid_circuit_rng_order_is_pinned() {
+        let circuit = Circuit::new(2)
+            .h(0)
+            .measure(0, 0)
+            .h(1)
+            .cx(0, 1)
+            .measure(1, 1)
+            .h(0)
+            .measure(0, 2);
+
+        let mut sim = Simulator::with_seed(1234);
+        let outcomes: Vec<String> = (0..8)
+            .map(|_| sim.run_with_measurements(&circuit).unwrap().1.bitstring())
+            .collect();
+
+        // Changing this sequence breaks every user's seeded results
+        assert_eq!(outcomes, ["110", "011", "000", "100", "111", "111", "010", "000"]);
+
+        // `sample` rewinds to the seed, so repeated calls agree
+        let first = sim.sample(&circuit, 50).unwrap();
+        assert_eq!(sim.sample(&circuit, 50).unwrap(), first);
+    }
+
     #[test]
     fn test_swap() {
         let circuit = Circuit::new(2).x(0).swap(0, 1);
@@ -382,4 +857,672 @@ mod tests {
         // |01⟩ → |10⟩
         assert!(state.probability(0b10) > 0.99);
     }
+
+    #[test]
+    fn test_self_inverse_infidelity_of_correct_circuit() {
+        let circuit = Circuit::new(3)
+            .h(0)
+            .s(1)
+            .t(2)
+            .rx(0.3, 0)
+            .ry(1.1, 1)
+            .u(0.4, 0.9, -0.2, 2)
+            .cp(0.7, 0, 2)
+            .ccx(0, 1, 2)
+            .cswap(2, 0, 1);
+
+        let drift = Simulator::new().self_inverse_infidelity(&circuit).unwrap();
+        assert!(drift.abs() < 1e-10, "drift {}", drift);
+    }
+
+    #[test]
+    fn test_circuit_then_inverse_returns_to_zero() {
+        let circuit = Circuit::new(3)
+            .h(0)
+            .s(0)
+            .t(1)
+            .rx(0.3, 1)
+            .rz(-1.2, 2)
+            .u(0.4, 0.9, -0.2, 2)
+            .cp(0.7, 0, 2)
+            .cx(2, 1)
+            .sdg(1);
+
+        let round_trip = circuit.clone().compose(&circuit.inverse()).unwrap();
+        let state = Simulator::new().run(&round_trip).unwrap();
+        assert!((state.probability(0) - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_cu_reproduces_u_when_control_set() {
+        let (theta, phi, lambda) = (0.9, -0.4, 1.7);
+        let controlled = Simulator::new().run(&Circuit::new(2).x(0).cu(theta, phi, lambda, 0, 1)).unwrap();
+        let direct = Simulator::new().run(&Circuit::new(2).x(0).u(theta, phi, lambda, 1)).unwrap();
+        for (a, b) in controlled.amplitudes().iter().zip(direct.amplitudes()) {
+            assert!(a.approx_eq(*b, 1e-12));
+        }
+
+        // Control clear: target untouched
+        let idle = Simulator::new().run(&Circuit::new(2).cu(theta, phi, lambda, 0, 1)).unwrap();
+        assert!((idle.probability(0) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cry_matches_ry() {
+        let controlled = Simulator::new().run(&Circuit::new(2).x(1).cry(0.6, 1, 0)).unwrap();
+        let direct = Simulator::new().run(&Circuit::new(2).x(1).ry(0.6, 0)).unwrap();
+        assert!((controlled.fidelity(&direct) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_run_rejects_duplicate_qubits() {
+        for circuit in [
+            Circuit::new(2).h(0).cx(0, 0),
+            Circuit::new(2).swap(1, 1),
+            Circuit::new(3).ccx(0, 1, 0),
+        ] {
+            assert!(matches!(
+                Simulator::new().run(&circuit),
+                Err(HomayaError::DuplicateQubit { .. })
+            ));
+        }
+        assert_eq!(
+            Simulator::new().run(&Circuit::new(3).ccx(0, 1, 0)).unwrap_err(),
+            HomayaError::DuplicateQubit { qubit: 0 }
+        );
+    }
+
+    #[test]
+    fn test_tensor_of_hadamards_is_uniform() {
+        let h = Circuit::new(1).h(0);
+        let circuit = h.tensor(&h);
+        assert_eq!(circuit.num_qubits(), 2);
+
+        let state = Simulator::new().run(&circuit).unwrap();
+        for i in 0..4 {
+            assert!((state.probability(i) - 0.25).abs() < 1e-12);
+            assert!((state.amplitudes()[i].re - 0.5).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_remapped_bell_pair_moves_entanglement() {
+        // Bell pair on qubits 0 and 1 of three; qubit 2 idle
+        let bell = Circuit::new(3).h(0).cx(0, 1);
+        let moved = bell.remap_qubits(&[1, 2, 0]).unwrap();
+        assert_eq!(moved.instructions()[1].qubits, vec![1, 2]);
+
+        let state = Simulator::new().run(&moved).unwrap();
+        assert!((state.probability(0b000) - 0.5).abs() < 1e-12);
+        assert!((state.probability(0b110) - 0.5).abs() < 1e-12);
+
+        // [1, 0] swaps control and target of the two-qubit Bell circuit
+        let swapped = Circuit::new(2).h(0).cx(0, 1).remap_qubits(&[1, 0]).unwrap();
+        assert_eq!(swapped.instructions()[0].qubits, vec![1]);
+        assert_eq!(swapped.instructions()[1].qubits, vec![1, 0]);
+        let expected = Simulator::new().run(&Circuit::new(2).h(1).cx(1, 0)).unwrap();
+        assert!((Simulator::new().run(&swapped).unwrap().fidelity(&expected) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_run_rejects_out_of_range_qubit() {
+        let circuit = Circuit::new(2).h(5);
+        assert!(Simulator::new().run(&circuit).is_err());
+        assert!(Simulator::new().run_with_measurements(&circuit).is_err());
+        assert!(Simulator::new().sample(&circuit, 10).is_err());
+    }
+
+    #[test]
+    fn test_bound_ansatz_runs() {
+        let ansatz = Circuit::new(2).ry_param("theta", 0).ry_param("phi", 1).cx(0, 1);
+        assert!(matches!(
+            Simulator::new().run(&ansatz),
+            Err(HomayaError::UnboundParameter { .. })
+        ));
+
+        let values = std::collections::HashMap::from([("theta".to_string(), PI / 2.0), ("phi".to_string(), PI)]);
+        let state = Simulator::new().run(&ansatz.bind(&values).unwrap()).unwrap();
+        let expected = Simulator::new()
+            .run(&Circuit::new(2).ry(PI / 2.0, 0).ry(PI, 1).cx(0, 1))
+            .unwrap();
+        assert!((state.fidelity(&expected) - 1.0).abs() < 1e-12);
+        // Ry(π/2) puts qubit 0 in |+⟩ and Ry(π) flips qubit 1; the CX then entangles them
+        assert!((state.probability(0b10) - 0.5).abs() < 1e-12);
+        assert!((state.probability(0b01) - 0.5).abs() < 1e-12);
+    }
+
+    /// Teleport qubit 0 onto qubit 2, prepared by `prep` and checked by `undo`.
+    fn teleport(prep: Circuit, undo: Circuit) -> Circuit {
+        prep.h(1)
+            .cx(1, 2)
+            .cx(0, 1)
+            .h(0)
+            .measure(0, 0)
+            .measure(1, 1)
+            .x_if(2, 1, 1)
+            .z_if(2, 0, 1)
+            .compose(&undo)
+            .unwrap()
+            .measure(2, 2)
+    }
+
+    #[test]
+    fn test_sample_typed_bell() {
+        let circuit = Circuit::new(2).h(0).cx(0, 1).measure_all();
+        let result = Simulator::with_seed(4).sample_typed(&circuit, 1000).unwrap();
+
+        assert_eq!(result.shots, 1000);
+        assert_eq!(result.counts.len(), 2);
+        assert_eq!(result.counts["00"] + result.counts["11"], 1000);
+        let (outcome, p) = result.most_likely().unwrap();
+        assert!(outcome == "00" || outcome == "11");
+        assert!((0.4..0.6).contains(&p), "most likely has probability {}", p);
+
+        // Same counts as the plain sample with the same seed
+        assert_eq!(result.counts, Simulator::with_seed(4).sample(&circuit, 1000).unwrap());
+    }
+
+    #[test]
+    fn test_teleportation_with_conditional_corrections() {
+        let theta = 1.1;
+        let circuit = teleport(Circuit::new(3).ry(theta, 0), Circuit::new(3).ry(-theta, 2));
+
+        let mut sim = Simulator::with_seed(7);
+        let mut outcomes = std::collections::HashSet::new();
+        for _ in 0..40 {
+            let (_, result) = sim.run_with_measurements(&circuit).unwrap();
+            // Undoing the preparation on qubit 2 must always give |0⟩
+            assert_eq!(result.bits[2], 0, "bits {:?}", result.bits);
+            outcomes.insert((result.bits[0], result.bits[1]));
+        }
+        assert_eq!(outcomes.len(), 4, "every correction branch is exercised");
+
+        // Without the corrections the state usually arrives wrong
+        let uncorrected = Circuit::new(3)
+            .ry(theta, 0)
+            .h(1)
+            .cx(1, 2)
+            .cx(0, 1)
+            .h(0)
+            .measure(0, 0)
+            .measure(1, 1)
+            .ry(-theta, 2)
+            .measure(2, 2);
+        let counts = Simulator::with_seed(3).sample(&uncorrected, 200).unwrap();
+        assert!(counts.keys().any(|bits| bits.ends_with('1')));
+    }
+
+    #[test]
+    fn test_conditions_on_auto_backends() {
+        // Clifford-only, so run_auto uses the tableau
+        let circuit = teleport(Circuit::new(3).x(0), Circuit::new(3).x(2));
+        let mut sim = Simulator::with_seed(5);
+        for _ in 0..20 {
+            let result = sim.run_auto(&circuit).unwrap();
+            assert_eq!(result.backend, crate::Backend::Stabilizer);
+            assert_eq!(result.measurements.bits[2], 0);
+        }
+
+        // A non-Clifford gate on a wide circuit goes to the sparse backend
+        let circuit = Circuit::new(40).x(0).t(0).measure(0, 0).x_if(39, 0, 1).x_if(38, 0, 0).measure(39, 1).measure(38, 2);
+        let result = Simulator::new().run_auto(&circuit).unwrap();
+        assert_eq!(result.backend, crate::Backend::Sparse);
+        assert_eq!(result.measurements.bits, vec![1, 1, 0]);
+    }
+
+    #[test]
+    fn test_mcx_truth_table() {
+        // Three controls (0, 1, 3) and target 2
+        for input in 0..16usize {
+            let mut circuit = Circuit::new(4);
+            for q in (0..4).filter(|q| input >> q & 1 == 1) {
+                circuit = circuit.x(q);
+            }
+            let state = Simulator::new().run(&circuit.mcx(&[0, 1, 3], 2)).unwrap();
+
+            let all_set = input & 0b1011 == 0b1011;
+            let expected = if all_set { input ^ 0b0100 } else { input };
+            assert!((state.probability(expected) - 1.0).abs() < 1e-12, "input {:04b}", input);
+        }
+    }
+
+    #[test]
+    fn test_mcx_with_few_controls() {
+        let x = Simulator::new().run(&Circuit::new(2).mcx(&[], 1)).unwrap();
+        assert!((x.probability(0b10) - 1.0).abs() < 1e-12);
+
+        let bell = Simulator::new().run(&Circuit::new(2).h(0).mcx(&[0], 1)).unwrap();
+        let expected = Simulator::new().run(&Circuit::new(2).h(0).cx(0, 1)).unwrap();
+        assert!((bell.fidelity(&expected) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_iswap_on_01() {
+        // Qubit 0 set: |q1 q0⟩ = |01⟩ → i|10⟩
+        let state = Simulator::new().run(&Circuit::new(2).x(0).iswap(0, 1)).unwrap();
+        assert!(state.amplitudes()[0b10].approx_eq(Complex::I, 1e-12));
+        assert!(state.probability(0b01) < 1e-12);
+    }
+
+    #[test]
+    fn test_sqrt_swap_twice_is_swap() {
+        let prep = Circuit::new(3).h(0).t(0).ry(0.7, 1).cx(1, 2).s(2);
+        let twice = Simulator::new().run(&prep.clone().sqrt_swap(0, 2).sqrt_swap(0, 2)).unwrap();
+        let swapped = Simulator::new().run(&prep.swap(0, 2)).unwrap();
+        for (a, b) in twice.amplitudes().iter().zip(swapped.amplitudes()) {
+            assert!(a.approx_eq(*b, 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_rzz_phase_on_11() {
+        let state = Simulator::new().run(&Circuit::new(2).x(0).x(1).rzz(PI, 0, 1)).unwrap();
+        // exp(-iπ/2 Z⊗Z) gives |11⟩ the phase e^(-iπ/2) = -i
+        assert!(state.amplitudes()[0b11].approx_eq(-Complex::I, 1e-12));
+
+        let state = Simulator::new().run(&Circuit::new(2).x(1).rzz(PI, 0, 1)).unwrap();
+        assert!(state.amplitudes()[0b10].approx_eq(Complex::I, 1e-12));
+    }
+
+    #[test]
+    fn test_ising_couplings_match_decompositions() {
+        let theta = 0.83;
+        let prep = Circuit::new(2).h(0).t(0).ry(0.4, 1).cx(0, 1).s(1);
+        let rxx = prep.clone().h(0).h(1).cx(0, 1).rz(theta, 1).cx(0, 1).h(0).h(1);
+        let ryy = prep.clone().rx(PI / 2.0, 0).rx(PI / 2.0, 1).cx(0, 1).rz(theta, 1).cx(0, 1).rx(-PI / 2.0, 0).rx(-PI / 2.0, 1);
+
+        for (gate, decomposed) in [(prep.clone().rxx(theta, 0, 1), rxx), (prep.clone().ryy(theta, 0, 1), ryy)] {
+            let a = Simulator::new().run(&gate).unwrap();
+            let b = Simulator::new().run(&decomposed).unwrap();
+            assert!((a.fidelity(&b) - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_toffoli_preserves_phases() {
+        // The truth table alone misses stray phases on the controls
+        let state = Simulator::new().run(&Circuit::new(3).h(0).h(1).ccx(0, 1, 2)).unwrap();
+        for index in [0b000, 0b001, 0b010, 0b111] {
+            assert!(state.amplitudes()[index].approx_eq(Complex::from_real(0.5), 1e-10), "|{:03b}⟩", index);
+        }
+    }
+
+    #[test]
+    fn test_wrong_inverse_raises_infidelity() {
+        // Undoing S with S instead of Sdg leaves a Z behind
+        let circuit = Circuit::new(1).h(0).s(0);
+        let wrong = [
+            Instruction::new(Gate::s(), vec![0]),
+            Instruction::new(Gate::h(), vec![0]),
+        ];
+
+        let drift = Simulator::new().round_trip_infidelity(&circuit, &wrong).unwrap();
+        assert!(drift > 0.1, "drift {}", drift);
+    }
+
+    #[test]
+    fn test_self_inverse_rejects_measurement() {
+        let circuit = Circuit::new(1).h(0).measure(0, 0);
+        assert!(Simulator::new().self_inverse_infidelity(&circuit).is_err());
+    }
+
+    #[test]
+    fn test_run_with_progress() {
+        let mut circuit = Circuit::new(3);
+        for _ in 0..100 {
+            circuit = circuit.h(0).cx(0, 1).t(2);
+        }
+
+        let mut calls = Vec::new();
+        let state = Simulator::new()
+            .run_with_progress(&circuit, |done, total| calls.push((done, total)))
+            .unwrap();
+
+        assert!(calls.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(calls.iter().all(|&(_, total)| total == 300));
+        assert_eq!(calls.last(), Some(&(300, 300)));
+        assert!(calls.len() <= 51);
+
+        let expected = Simulator::new().run(&circuit).unwrap();
+        assert!((state.fidelity(&expected) - 1.0).abs() < 1e-10);
+
+        let mut calls = Vec::new();
+        Simulator::new().run_with_progress(&Circuit::new(1), |d, t| calls.push((d, t))).unwrap();
+        assert_eq!(calls, [(0, 0)]);
+    }
+
+    #[test]
+    fn test_swap_register_matches_bit_reversal() {
+        for input in [0b0001usize, 0b0011, 0b0110, 0b1011] {
+            let mut circuit = Circuit::new(4);
+            for q in 0..4 {
+                if input >> q & 1 == 1 {
+                    circuit = circuit.x(q);
+                }
+            }
+            let circuit = circuit.swap_register(&[0, 1], &[3, 2]).unwrap();
+            let state = Simulator::new().run(&circuit).unwrap();
+
+            let reversed = (0..4).fold(0, |acc, q| acc | ((input >> q & 1) << (3 - q)));
+            assert!((state.probability(reversed) - 1.0).abs() < 1e-12, "{:04b}", input);
+        }
+    }
+
+    #[test]
+    fn test_batched_swaps_match_individual_swaps() {
+        // Overlapping SWAPs compose into a cycle; compare against one pass per SWAP
+        let circuit = Circuit::new(3).swap(0, 1).swap(1, 2).swap(0, 2);
+        let probe = probe_state(3);
+
+        let batched = Simulator::new().run_from_state(&circuit, probe.clone()).unwrap();
+
+        let mut expected = probe;
+        let mut sim = Simulator::new();
+        let mut measurements = MeasurementResult::new(0);
+        for inst in circuit.instructions() {
+            sim.apply_instruction(&mut expected, inst, &mut measurements)
+                .unwrap();
+        }
+
+        assert!((batched.fidelity(&expected) - 1.0).abs() < 1e-12);
+        for (a, b) in batched.amplitudes().iter().zip(expected.amplitudes()) {
+            assert!(a.approx_eq(*b, 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_direct_gates_match_decompositions() {
+        let probe = probe_state(4);
+        let mut sim = Simulator::new();
+
+        for (a, b, c) in [(0, 1, 2), (3, 0, 1), (2, 3, 0)] {
+            for (name, circuit) in [
+                ("swap", Circuit::new(4).swap(a, b)),
+                ("ccx", Circuit::new(4).ccx(a, b, c)),
+                ("cswap", Circuit::new(4).cswap(a, b, c)),
+            ] {
+                let direct = sim.run_from_state(&circuit, probe.clone()).unwrap();
+                let decomposed = circuit.decompose();
+                assert!(decomposed.instructions().iter().all(|inst| inst.qubits.len() <= 2));
+                let decomposed = sim.run_from_state(&decomposed, probe.clone()).unwrap();
+                for (x, y) in direct.amplitudes().iter().zip(decomposed.amplitudes()) {
+                    assert!(x.approx_eq(*y, 1e-12), "{}({}, {}, {})", name, a, b, c);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_decompose_swaps_preserves_state() {
+        let circuit = Circuit::new(3).h(0).ry(0.7, 1).swap(0, 2).cx(2, 1).swap(1, 0);
+        let direct = Simulator::new().run(&circuit).unwrap();
+        let decomposed = circuit.decompose_swaps();
+        assert!(decomposed.instructions().iter().all(|inst| inst.gate.gate_type != GateType::Swap));
+
+        let decomposed = Simulator::new().run(&decomposed).unwrap();
+        for (a, b) in direct.amplitudes().iter().zip(decomposed.amplitudes()) {
+            assert!(a.approx_eq(*b, 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_transpiled_bell_matches_bell_state() {
+        let basis = [GateType::Rz, GateType::Ry, GateType::CX];
+        let bell = Circuit::new(2).h(0).cx(0, 1);
+        let transpiled = bell.clone().transpile_to_basis(&basis).unwrap();
+        assert!(transpiled.instructions().iter().all(|inst| basis.contains(&inst.gate.gate_type)));
+
+        let expected = Simulator::new().run(&bell).unwrap();
+        let state = Simulator::new().run(&transpiled).unwrap();
+        assert!(state.phase_aligned_eq(&expected, 1e-10));
+        assert!((state.probability(0) - 0.5).abs() < 1e-10);
+        assert!((state.probability(3) - 0.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_transpile_preserves_each_gate() {
+        let basis = [GateType::Rz, GateType::Ry, GateType::CX];
+        let circuits = [
+            Circuit::new(3).h(1),
+            Circuit::new(3).x(0),
+            Circuit::new(3).y(2),
+            Circuit::new(3).z(1).s(0).sdg(2).t(1).tdg(0),
+            Circuit::new(3).p(0.4, 1).rx(1.1, 2),
+            Circuit::new(3).u(0.3, 1.2, -0.8, 0),
+            Circuit::new(3).cz(0, 2).cy(2, 1),
+            Circuit::new(3).cp(0.9, 1, 0),
+            Circuit::new(3).rzz(0.6, 0, 2),
+            Circuit::new(3).swap(0, 1).ccx(2, 0, 1).cswap(1, 2, 0),
+        ];
+
+        let mut sim = Simulator::new();
+        for circuit in circuits {
+            let direct = sim.run_from_state(&circuit, probe_state(3)).unwrap();
+            let transpiled = circuit.transpile_to_basis(&basis).unwrap();
+            assert!(transpiled.instructions().iter().all(|inst| basis.contains(&inst.gate.gate_type)));
+
+            let lowered = sim.run_from_state(&transpiled, probe_state(3)).unwrap();
+            assert!(lowered.phase_aligned_eq(&direct, 1e-10));
+        }
+    }
+
+    #[test]
+    fn test_rx_two_pi_is_identity_up_to_phase() {
+        // Rx(2π) = −I: equal to doing nothing only up to global phase
+        let probe = probe_state(2);
+        let mut sim = Simulator::new();
+        let rotated = sim.run_from_state(&Circuit::new(2).rx(2.0 * PI, 1), probe.clone()).unwrap();
+
+        assert_ne!(rotated, probe);
+        assert!(rotated.phase_aligned_eq(&probe, 1e-10));
+    }
+
+    #[test]
+    fn test_terminal_sampling_matches_per_shot_runs() {
+        let unitary = Circuit::new(3).ry(0.7, 0).ry(1.9, 1).cx(1, 2).t(2).h(2);
+        let circuit = unitary
+            .clone()
+            .barrier(&[])
+            .measure(2, 0)
+            .measure(0, 1)
+            .measure(1, 2);
+        assert_eq!(terminal_measurements(&circuit), Some(5));
+
+        let shots = 4000;
+        let mut sim = Simulator::with_seed(2024);
+        let fast = sim.sample(&circuit, shots).unwrap();
+        let mut slow = std::collections::HashMap::new();
+        for _ in 0..shots {
+            *slow.entry(sim.run_with_measurements(&circuit).unwrap().1.bitstring()).or_insert(0) += 1;
+        }
+
+        // Outcome frequencies agree with each other and with the exact state
+        let state = sim.run(&unitary).unwrap();
+        for index in 0..8usize {
+            let bits: String = [2, 0, 1].iter().map(|&q| if index >> q & 1 == 1 { '1' } else { '0' }).collect();
+            let expected = state.probability(index) * shots as f64;
+            for counts in [&fast, &slow] {
+                let got = counts.get(&bits).copied().unwrap_or(0) as f64;
+                assert!((got - expected).abs() < 4.0 * expected.sqrt() + 1.0, "{}: {} vs {:.0}", bits, got, expected);
+            }
+        }
+        assert_eq!(fast.values().sum::<usize>(), shots);
+    }
+
+    #[test]
+    fn test_sampling_falls_back_for_non_terminal_measurements() {
+        let terminal = Circuit::new(2).h(0).cx(0, 1).measure_all();
+        assert_eq!(terminal_measurements(&terminal), Some(2));
+        assert_eq!(terminal_measurements(&Circuit::new(2).h(0)), Some(1));
+
+        for circuit in [
+            Circuit::new(2).h(0).measure(0, 0).h(1).measure(1, 1),
+            Circuit::new(2).h(0).reset(0).measure_all(),
+            Circuit::new(2).h(0).measure(0, 0).x_if(1, 0, 1).measure(1, 1),
+        ] {
+            assert_eq!(terminal_measurements(&circuit), None);
+        }
+
+        // Measuring one qubit leaves the other clbit at 0
+        let partial = Circuit::new(2).x(1).measure(1, 0).measure(0, 1);
+        let counts = Simulator::with_seed(5).sample(&partial, 20).unwrap();
+        assert_eq!(counts.get("10"), Some(&20));
+    }
+
+    #[test]
+    fn test_bell_expectations() {
+        let bell = Circuit::new(2).h(0).cx(0, 1);
+        let mut sim = Simulator::new();
+
+        for (paulis, expected) in [
+            (vec![(0, 'Z'), (1, 'Z')], 1.0),
+            (vec![(0, 'X'), (1, 'X')], 1.0),
+            (vec![(0, 'Y'), (1, 'Y')], -1.0),
+            (vec![(0, 'Z')], 0.0),
+            (vec![(1, 'X')], 0.0),
+            (vec![(0, 'X'), (1, 'Z')], 0.0),
+        ] {
+            let value = sim.expectation(&bell, &paulis).unwrap();
+            assert!((value - expected).abs() < 1e-12, "{:?}: {}", paulis, value);
+        }
+
+        // H = ZZ + XX − YY − 0.5 Z₀ has ⟨H⟩ = 3 on the Bell state
+        let hamiltonian = [
+            (1.0, vec![(0, 'Z'), (1, 'Z')]),
+            (1.0, vec![(0, 'X'), (1, 'X')]),
+            (-1.0, vec![(0, 'Y'), (1, 'Y')]),
+            (-0.5, vec![(0, 'Z')]),
+        ];
+        assert!((sim.expectation_many(&bell, &hamiltonian).unwrap() - 3.0).abs() < 1e-12);
+        assert!(sim.expectation_many(&bell, &[(1.0, vec![(0, 'W')])]).is_err());
+    }
+
+    #[test]
+    fn test_ghz_expectations() {
+        // (|000⟩ + |111⟩)/√2: every ZZ pair is 1, X⊗X⊗X is 1, and an even
+        // number of Ys flips the sign once per pair
+        let ghz = Circuit::new(3).h(0).cx(0, 1).cx(1, 2);
+        let mut sim = Simulator::new();
+
+        for (paulis, expected) in [
+            (vec![(0, 'Z'), (2, 'Z')], 1.0),
+            (vec![(0, 'X'), (1, 'X'), (2, 'X')], 1.0),
+            (vec![(0, 'X'), (1, 'Y'), (2, 'Y')], -1.0),
+            (vec![(0, 'Y'), (1, 'X'), (2, 'Y')], -1.0),
+            (vec![(0, 'Z'), (1, 'Z'), (2, 'Z')], 0.0),
+            (vec![(0, 'X'), (1, 'X')], 0.0),
+        ] {
+            let value = sim.expectation(&ghz, &paulis).unwrap();
+            assert!((value - expected).abs() < 1e-12, "{:?}: {}", paulis, value);
+        }
+    }
+
+    #[test]
+    fn test_oversized_circuit_fails_gracefully() {
+        let circuit = Circuit::new(40).h(0).measure_all();
+        let mut sim = Simulator::new();
+
+        assert!(matches!(
+            sim.run(&circuit),
+            Err(HomayaError::CircuitTooLarge { qubits: 40, max: StateVector::MAX_QUBITS })
+        ));
+        assert!(matches!(sim.run_with_measurements(&circuit), Err(HomayaError::CircuitTooLarge { .. })));
+        assert!(matches!(sim.sample(&circuit, 10), Err(HomayaError::CircuitTooLarge { .. })));
+    }
+
+    #[test]
+    fn test_zero_seed_is_not_degenerate() {
+        let mut sim = Simulator::with_seed(0);
+        let draws: Vec<f64> = (0..1000).map(|_| sim.next_random()).collect();
+
+        assert!(draws.iter().all(|r| (0.0..1.0).contains(r)));
+        let mut distinct = draws.clone();
+        distinct.sort_by(f64::total_cmp);
+        distinct.dedup();
+        assert_eq!(distinct.len(), draws.len());
+        let mean = draws.iter().sum::<f64>() / draws.len() as f64;
+        assert!((mean - 0.5).abs() < 0.05, "mean {}", mean);
+
+        // Adjacent seeds start unrelated streams
+        let first = |seed| Simulator::with_seed(seed).next_random();
+        assert!((first(1) - first(2)).abs() > 1e-3);
+    }
+
+    #[test]
+    fn test_zero_seed_bell_sampling_is_balanced() {
+        let circuit = Circuit::new(2).h(0).cx(0, 1).measure_all();
+        let counts = Simulator::with_seed(0).sample(&circuit, 2000).unwrap();
+
+        let count_00 = counts.get("00").copied().unwrap_or(0);
+        let count_11 = counts.get("11").copied().unwrap_or(0);
+        assert_eq!(count_00 + count_11, 2000);
+        assert!((900..=1100).contains(&count_00), "{} / 2000", count_00);
+    }
+
+    #[test]
+    fn test_batch_runs_each_circuit() {
+        let circuits = [
+            Circuit::new(1).x(0).measure_all(),
+            Circuit::new(2).h(0).cx(0, 1).measure_all(),
+            Circuit::new(3).x(2).swap(0, 2).measure_all(),
+        ];
+        let mut sim = Simulator::with_seed(11);
+
+        let states = sim.run_batch(&circuits).unwrap();
+        assert_eq!(states.len(), 3);
+        assert!((states[0].probability(1) - 1.0).abs() < 1e-12);
+        assert!((states[1].probability(0b00) + states[1].probability(0b11) - 1.0).abs() < 1e-12);
+        assert!((states[2].probability(0b001) - 1.0).abs() < 1e-12);
+
+        let counts = sim.sample_batch(&circuits, 400).unwrap();
+        assert_eq!(counts[0].get("1"), Some(&400));
+        let bell_00 = counts[1].get("00").copied().unwrap_or(0);
+        assert_eq!(bell_00 + counts[1].get("11").copied().unwrap_or(0), 400);
+        assert!((150..=250).contains(&bell_00), "{} / 400", bell_00);
+        assert_eq!(counts[2].get("100"), Some(&400));
+
+        // Seeded batches rewind once, so they repeat
+        assert_eq!(sim.sample_batch(&circuits, 400).unwrap(), counts);
+
+        assert!(sim.run_batch(&[Circuit::new(1), Circuit::new(40)]).is_err());
+    }
+
+    #[test]
+    fn test_batch_streams_are_independent() {
+        // Identical coin flips must not all land the same way
+        let coin = Circuit::new(1).h(0).measure(0, 0);
+        let states = Simulator::with_seed(3).run_batch(&vec![coin; 32]).unwrap();
+        let heads = states.iter().filter(|state| state.probability(1) > 0.5).count();
+        assert!(heads > 0 && heads < 32, "{} heads", heads);
+    }
+
+    #[test]
+    fn test_snapshot_between_h_and_cx() {
+        let circuit = Circuit::new(2).h(0).snapshot("after_h").cx(0, 1).snapshot("bell").measure_all();
+        let (_, result, snapshots) = Simulator::with_seed(4).run_with_snapshots(&circuit).unwrap();
+        assert_eq!(snapshots.len(), 2);
+
+        // |+⟩ on qubit 0, |0⟩ on qubit 1
+        let h = Complex::from_real(INV_SQRT_2);
+        let plus_zero = StateVector::from_amplitudes(vec![h, h, Complex::ZERO, Complex::ZERO]).unwrap();
+        assert_eq!(snapshots["after_h"], plus_zero);
+
+        let bell = &snapshots["bell"];
+        assert!((bell.probability(0b00) - 0.5).abs() < 1e-12);
+        assert!((bell.probability(0b11) - 0.5).abs() < 1e-12);
+        assert_eq!(result.bits[0], result.bits[1]);
+
+        // Snapshots leave the physics alone
+        let plain = Circuit::new(2).h(0).cx(0, 1);
+        let with_snapshot = Circuit::new(2).h(0).snapshot("x").cx(0, 1);
+        assert_eq!(Simulator::new().run(&with_snapshot).unwrap(), Simulator::new().run(&plain).unwrap());
+    }
+
+    #[test]
+    fn test_repeated_snapshot_label_keeps_latest() {
+        let circuit = Circuit::new(1).snapshot("s").x(0).snapshot("s");
+        let (_, _, snapshots) = Simulator::new().run_with_snapshots(&circuit).unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert!((snapshots["s"].probability(1) - 1.0).abs() < 1e-12);
+    }
 }
@@ -0,0 +1,317 @@
+//! Sparse state vector simulation.
+//!
+//! Stores only the nonzero amplitudes in a hash map keyed by basis index.
+//! Circuits that keep the state concentrated on few basis states (classical
+//! reversible logic, few superposition-creating gates) then run in time
+//! and memory proportional to that support rather than to 2ⁿ, so they can
+//! use up to 64 qubits.
+
+use std::collections::HashMap;
+
+use homaya_core::{Complex, Gate, GateType, GateParams, HomayaError, Result, INV_SQRT_2};
+
+/// Amplitudes with squared magnitude at or below this are dropped.
+const PRUNE_EPSILON: f64 = 1e-30;
+
+/// A state vector that stores only its nonzero amplitudes.
+///
+/// Uses the same little-endian basis ordering as [`StateVector`](crate::StateVector).
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_sim::SparseState;
+///
+/// let state = SparseState::new(50);
+/// assert_eq!(state.num_nonzero(), 1);
+/// assert_eq!(state.probability(0), 1.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SparseState {
+    /// Number of qubits
+    num_qubits: usize,
+    /// Nonzero amplitudes by basis index
+    amplitudes: HashMap<u64, Complex>,
+}
+
+impl SparseState {
+    /// Maximum number of qubits (basis indices are `u64`).
+    pub const MAX_QUBITS: usize = 64;
+
+    /// Create the state |0...0⟩.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_qubits` exceeds [`Self::MAX_QUBITS`].
+    pub fn new(num_qubits: usize) -> Self {
+        assert!(
+            num_qubits <= Self::MAX_QUBITS,
+            "sparse state supports at most {} qubits",
+            Self::MAX_QUBITS
+        );
+        let mut amplitudes = HashMap::new();
+        amplitudes.insert(0, Complex::ONE);
+        Self { num_qubits, amplitudes }
+    }
+
+    /// Get the number of qubits.
+    #[inline]
+    pub const fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    /// Get the number of stored (nonzero) amplitudes.
+    #[inline]
+    pub fn num_nonzero(&self) -> usize {
+        self.amplitudes.len()
+    }
+
+    /// Get the amplitude of a basis state.
+    pub fn amplitude(&self, index: u64) -> Complex {
+        self.amplitudes.get(&index).copied().unwrap_or(Complex::ZERO)
+    }
+
+    /// Get the probability of measuring a basis state.
+    pub fn probability(&self, index: u64) -> f64 {
+        self.amplitude(index).norm_sqr()
+    }
+
+    /// Check whether a gate can be simulated on a sparse state.
+    pub fn supports(gate: &Gate) -> bool {
+        gate.gate_type != GateType::CU || matches!(gate.params, GateParams::Angles3(..))
+    }
+
+    /// Apply a 2×2 matrix to `target` on the basis states where every
+    /// qubit in `control_mask` is 1.
+    pub fn apply_controlled(&mut self, control_mask: u64, target: usize, matrix: [[Complex; 2]; 2]) {
+        let bit = 1u64 << target;
+        let mut next: HashMap<u64, Complex> = HashMap::with_capacity(self.amplitudes.len());
+
+        for (&i, &amp) in &self.amplitudes {
+            if i & control_mask != control_mask {
+                *next.entry(i).or_insert(Complex::ZERO) += amp;
+                continue;
+            }
+            let col = ((i & bit) != 0) as usize;
+            for (row, index) in [(0, i & !bit), (1, i | bit)] {
+                let m = matrix[row][col];
+                if m != Complex::ZERO {
+                    *next.entry(index).or_insert(Complex::ZERO) += m * amp;
+                }
+            }
+        }
+
+        next.retain(|_, amp| amp.norm_sqr() > PRUNE_EPSILON);
+        self.amplitudes = next;
+    }
+
+    /// Apply a single-qubit gate matrix.
+    pub fn apply_single(&mut self, target: usize, matrix: [[Complex; 2]; 2]) {
+        self.apply_controlled(0, target, matrix);
+    }
+
+    /// Apply a 4×4 matrix to qubits `q0` and `q1`, indexed like
+    /// [`Gate::matrix_4x4`].
+    pub fn apply_two(&mut self, q0: usize, q1: usize, matrix: [[Complex; 4]; 4]) {
+        let (m0, m1) = (1u64 << q0, 1u64 << q1);
+        let mut next: HashMap<u64, Complex> = HashMap::with_capacity(self.amplitudes.len());
+
+        for (&i, &amp) in &self.amplitudes {
+            let col = ((i & m0) != 0) as usize | ((((i & m1) != 0) as usize) << 1);
+            let base = i & !m0 & !m1;
+            for (row, entries) in matrix.iter().enumerate() {
+                let m = entries[col];
+                if m != Complex::ZERO {
+                    let index = base | if row & 1 != 0 { m0 } else { 0 } | if row & 2 != 0 { m1 } else { 0 };
+                    *next.entry(index).or_insert(Complex::ZERO) += m * amp;
+                }
+            }
+        }
+
+        next.retain(|_, amp| amp.norm_sqr() > PRUNE_EPSILON);
+        self.amplitudes = next;
+    }
+
+    /// Swap qubits `a` and `b` on the basis states where every qubit in
+    /// `control_mask` is 1.
+    pub fn apply_swap(&mut self, control_mask: u64, a: usize, b: usize) {
+        let (ma, mb) = (1u64 << a, 1u64 << b);
+        self.amplitudes = self
+            .amplitudes
+            .drain()
+            .map(|(i, amp)| {
+                let differ = ((i & ma) != 0) != ((i & mb) != 0);
+                if i & control_mask == control_mask && differ {
+                    (i ^ ma ^ mb, amp)
+                } else {
+                    (i, amp)
+                }
+            })
+            .collect();
+    }
+
+    /// Measure a qubit, collapsing the state. Returns 0 or 1.
+    pub fn measure(&mut self, qubit: usize, random: f64) -> u8 {
+        let bit = 1u64 << qubit;
+        let prob_1: f64 = self
+            .amplitudes
+            .iter()
+            .filter(|(&i, _)| i & bit != 0)
+            .map(|(_, amp)| amp.norm_sqr())
+            .sum();
+
+        let result = if random < 1.0 - prob_1 { 0 } else { 1 };
+        let norm = if result == 1 { prob_1 } else { 1.0 - prob_1 };
+        let scale = 1.0 / norm.sqrt();
+
+        self.amplitudes.retain(|&i, _| ((i & bit) != 0) == (result == 1));
+        for amp in self.amplitudes.values_mut() {
+            *amp = *amp * scale;
+        }
+        result
+    }
+
+    /// Reset a qubit to |0⟩.
+    pub fn reset(&mut self, qubit: usize, random: f64) {
+        if self.measure(qubit, random) == 1 {
+            let x = [[Complex::ZERO, Complex::ONE], [Complex::ONE, Complex::ZERO]];
+            self.apply_single(qubit, x);
+        }
+    }
+
+    /// Apply a unitary gate.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotSupported` for gates listed as unsupported by
+    /// [`Self::supports`] and for measurement and reset, which need a random
+    /// number (use [`Self::measure`] and [`Self::reset`]).
+    pub fn apply_gate(&mut self, gate: &Gate, qubits: &[usize]) -> Result<()> {
+        use GateType::*;
+
+        let x = [[Complex::ZERO, Complex::ONE], [Complex::ONE, Complex::ZERO]];
+        let mask = |q: usize| 1u64 << q;
+
+        match gate.gate_type {
+            Barrier | Snapshot => {}
+            I | X | Y | Z | H | S | Sdg | T | Tdg | Rx | Ry | Rz | P | U => {
+                let matrix = gate.matrix_2x2().ok_or(HomayaError::NotSupported {
+                    operation: "gate has no 2x2 matrix",
+                })?;
+                self.apply_single(qubits[0], matrix);
+            }
+            CX => self.apply_controlled(mask(qubits[0]), qubits[1], x),
+            CY => {
+                let y = [[Complex::ZERO, -Complex::I], [Complex::I, Complex::ZERO]];
+                self.apply_controlled(mask(qubits[0]), qubits[1], y);
+            }
+            CZ => {
+                let z = [[Complex::ONE, Complex::ZERO], [Complex::ZERO, -Complex::ONE]];
+                self.apply_controlled(mask(qubits[0]), qubits[1], z);
+            }
+            CH => {
+                let h = Complex::from_real(INV_SQRT_2);
+                self.apply_controlled(mask(qubits[0]), qubits[1], [[h, h], [h, -h]]);
+            }
+            CU => {
+                if let GateParams::Angles3(theta, phi, lambda) = gate.params {
+                    let matrix = Gate::u(theta, phi, lambda).matrix_2x2().ok_or(HomayaError::NotSupported {
+                        operation: "gate has no 2x2 matrix",
+                    })?;
+                    self.apply_controlled(mask(qubits[0]), qubits[1], matrix);
+                }
+            }
+            CP => {
+                if let GateParams::Angle(theta) = gate.params {
+                    let phase = Complex::from_polar(1.0, theta);
+                    let p = [[Complex::ONE, Complex::ZERO], [Complex::ZERO, phase]];
+                    self.apply_controlled(mask(qubits[0]), qubits[1], p);
+                }
+            }
+            Swap => self.apply_swap(0, qubits[0], qubits[1]),
+            ISwap | SqrtSwap | Rxx | Ryy | Rzz => {
+                let matrix = gate.matrix_4x4().ok_or(HomayaError::NotSupported {
+                    operation: "gate has no 4x4 matrix",
+                })?;
+                self.apply_two(qubits[0], qubits[1], matrix);
+            }
+            CCX => self.apply_controlled(mask(qubits[0]) | mask(qubits[1]), qubits[2], x),
+            CSwap => self.apply_swap(mask(qubits[0]), qubits[1], qubits[2]),
+            MCX => {
+                let (&target, controls) = qubits.split_last().ok_or(HomayaError::QubitMismatch {
+                    expected: 1,
+                    got: 0,
+                })?;
+                let control_mask = controls.iter().fold(0, |m, &c| m | mask(c));
+                self.apply_controlled(control_mask, target, x);
+            }
+            _ => {
+                return Err(HomayaError::NotSupported {
+                    operation: "gate type not implemented for sparse simulation",
+                })
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_x_chain_stays_sparse() {
+        let mut state = SparseState::new(60);
+        for q in [0, 17, 59] {
+            state.apply_gate(&Gate::x(), &[q]).unwrap();
+        }
+        state.apply_gate(&Gate::ccx(), &[0, 59, 30]).unwrap();
+
+        state.apply_gate(&Gate::mcx(), &[0, 17, 30, 59, 63]).unwrap();
+
+        let expected = (1u64 << 0) | (1 << 17) | (1 << 59) | (1 << 30) | (1 << 63);
+        assert_eq!(state.num_nonzero(), 1);
+        assert!((state.probability(expected) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_bell_pair_and_measurement() {
+        let mut state = SparseState::new(40);
+        state.apply_gate(&Gate::h(), &[3]).unwrap();
+        state.apply_gate(&Gate::cx(), &[3, 39]).unwrap();
+        assert_eq!(state.num_nonzero(), 2);
+        assert!((state.probability((1 << 3) | (1 << 39)) - 0.5).abs() < 1e-12);
+
+        assert_eq!(state.measure(3, 0.9), 1);
+        assert_eq!(state.measure(39, 0.0), 1);
+        assert_eq!(state.num_nonzero(), 1);
+    }
+
+    #[test]
+    fn test_interference_prunes_zeros() {
+        // H·H = I: the |1⟩ branch cancels exactly
+        let mut state = SparseState::new(1);
+        state.apply_gate(&Gate::h(), &[0]).unwrap();
+        state.apply_gate(&Gate::h(), &[0]).unwrap();
+        assert_eq!(state.num_nonzero(), 1);
+        assert!((state.probability(0) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_ising_coupling() {
+        // Rxx(π) maps |00⟩ to -i|11⟩
+        let mut state = SparseState::new(45);
+        state.apply_gate(&Gate::rxx(homaya_core::PI), &[2, 44]).unwrap();
+        assert_eq!(state.num_nonzero(), 1);
+        assert!(state.amplitude((1 << 2) | (1 << 44)).approx_eq(-Complex::I, 1e-12));
+    }
+
+    #[test]
+    fn test_controlled_swap() {
+        let mut state = SparseState::new(3);
+        state.apply_gate(&Gate::x(), &[0]).unwrap();
+        state.apply_gate(&Gate::x(), &[1]).unwrap();
+        state.apply_gate(&Gate::cswap(), &[0, 1, 2]).unwrap();
+        assert!((state.probability(0b101) - 1.0).abs() < 1e-12);
+    }
+}
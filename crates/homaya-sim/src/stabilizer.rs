@@ -0,0 +1,322 @@
+//! Stabilizer (Clifford) simulation.
+//!
+//! Circuits built only from Clifford gates (H, S, CNOT and friends) plus
+//! measurement keep the state a stabilizer state, which an n-qubit
+//! tableau of O(n²) bits describes exactly. This makes circuits on
+//! hundreds of qubits cheap to simulate, where a state vector would need
+//! 2ⁿ amplitudes.
+//!
+//! The tableau follows Aaronson & Gottesman, "Improved simulation of
+//! stabilizer circuits" (2004): rows 0..n are destabilizers, rows n..2n are
+//! stabilizers, and row 2n is scratch space for deterministic measurements.
+
+use homaya_core::{Gate, GateType, HomayaError, Result};
+
+/// A stabilizer state on `n` qubits, stored as a binary tableau.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_sim::StabilizerState;
+///
+/// // Bell pair: the two measurements always agree
+/// let mut state = StabilizerState::new(2);
+/// state.h(0);
+/// state.cx(0, 1);
+/// let a = state.measure(0, 0.3);
+/// let b = state.measure(1, 0.9);
+/// assert_eq!(a, b);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StabilizerState {
+    /// Number of qubits
+    num_qubits: usize,
+    /// X bits, (2n + 1) rows of n entries, row-major
+    x: Vec<bool>,
+    /// Z bits, same layout as `x`
+    z: Vec<bool>,
+    /// Phase bit of each row (true means a −1 sign)
+    r: Vec<bool>,
+}
+
+impl StabilizerState {
+    /// Create the state |0...0⟩.
+    pub fn new(num_qubits: usize) -> Self {
+        let n = num_qubits;
+        let rows = 2 * n + 1;
+        let mut state = Self {
+            num_qubits: n,
+            x: vec![false; rows * n],
+            z: vec![false; rows * n],
+            r: vec![false; rows],
+        };
+        for i in 0..n {
+            state.x[i * n + i] = true;
+            state.z[(n + i) * n + i] = true;
+        }
+        state
+    }
+
+    /// Get the number of qubits.
+    #[inline]
+    pub const fn num_qubits(&self) -> usize {
+        self.num_qubits
+    }
+
+    /// Check whether a gate can be simulated on a tableau.
+    pub fn supports(gate: &Gate) -> bool {
+        use GateType::*;
+        matches!(
+            gate.gate_type,
+            I | X | Y | Z | H | S | Sdg | CX | CY | CZ | Swap | Measure | Reset | Barrier | Snapshot
+        )
+    }
+
+    #[inline]
+    fn idx(&self, row: usize, qubit: usize) -> usize {
+        row * self.num_qubits + qubit
+    }
+
+    /// Apply a Hadamard gate.
+    pub fn h(&mut self, a: usize) {
+        for row in 0..2 * self.num_qubits {
+            let k = self.idx(row, a);
+            self.r[row] ^= self.x[k] & self.z[k];
+            std::mem::swap(&mut self.x[k], &mut self.z[k]);
+        }
+    }
+
+    /// Apply an S (phase) gate.
+    pub fn s(&mut self, a: usize) {
+        for row in 0..2 * self.num_qubits {
+            let k = self.idx(row, a);
+            self.r[row] ^= self.x[k] & self.z[k];
+            self.z[k] ^= self.x[k];
+        }
+    }
+
+    /// Apply a Pauli-X gate.
+    pub fn x(&mut self, a: usize) {
+        for row in 0..2 * self.num_qubits {
+            self.r[row] ^= self.z[self.idx(row, a)];
+        }
+    }
+
+    /// Apply a Pauli-Z gate.
+    pub fn z(&mut self, a: usize) {
+        for row in 0..2 * self.num_qubits {
+            self.r[row] ^= self.x[self.idx(row, a)];
+        }
+    }
+
+    /// Apply a Pauli-Y gate.
+    pub fn y(&mut self, a: usize) {
+        for row in 0..2 * self.num_qubits {
+            let k = self.idx(row, a);
+            self.r[row] ^= self.x[k] ^ self.z[k];
+        }
+    }
+
+    /// Apply a CNOT gate.
+    pub fn cx(&mut self, control: usize, target: usize) {
+        for row in 0..2 * self.num_qubits {
+            let (c, t) = (self.idx(row, control), self.idx(row, target));
+            self.r[row] ^= self.x[c] & self.z[t] & !(self.x[t] ^ self.z[c]);
+            self.x[t] ^= self.x[c];
+            self.z[c] ^= self.z[t];
+        }
+    }
+
+    /// Measure a qubit in the computational basis, collapsing the state.
+    ///
+    /// `random` is consumed only when the outcome is not already
+    /// determined; outcome 1 is chosen when `random >= 0.5`.
+    pub fn measure(&mut self, a: usize, random: f64) -> u8 {
+        let n = self.num_qubits;
+
+        // A stabilizer anticommuting with Z_a makes the outcome random
+        if let Some(p) = (n..2 * n).find(|&p| self.x[self.idx(p, a)]) {
+            for row in 0..2 * n {
+                if row != p && self.x[self.idx(row, a)] {
+                    self.rowsum(row, p);
+                }
+            }
+
+            // The old stabilizer becomes a destabilizer; Z_a replaces it
+            self.copy_row(p - n, p);
+            for q in 0..n {
+                let k = self.idx(p, q);
+                self.x[k] = false;
+                self.z[k] = false;
+            }
+            let k = self.idx(p, a);
+            self.z[k] = true;
+
+            let outcome = random >= 0.5;
+            self.r[p] = outcome;
+            return outcome as u8;
+        }
+
+        // Deterministic: accumulate the product of stabilizers in scratch row 2n
+        let scratch = 2 * n;
+        for q in 0..n {
+            let k = self.idx(scratch, q);
+            self.x[k] = false;
+            self.z[k] = false;
+        }
+        self.r[scratch] = false;
+        for i in 0..n {
+            if self.x[self.idx(i, a)] {
+                self.rowsum(scratch, i + n);
+            }
+        }
+        self.r[scratch] as u8
+    }
+
+    /// Reset a qubit to |0⟩.
+    pub fn reset(&mut self, a: usize, random: f64) {
+        if self.measure(a, random) == 1 {
+            self.x(a);
+        }
+    }
+
+    /// Apply a supported gate.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotSupported` for non-Clifford gates, and for measurement
+    /// and reset, which need a random number (use [`Self::measure`] and
+    /// [`Self::reset`]).
+    pub fn apply_gate(&mut self, gate: &Gate, qubits: &[usize]) -> Result<()> {
+        use GateType::*;
+
+        match gate.gate_type {
+            I | Barrier | Snapshot => {}
+            X => self.x(qubits[0]),
+            Y => self.y(qubits[0]),
+            Z => self.z(qubits[0]),
+            H => self.h(qubits[0]),
+            S => self.s(qubits[0]),
+            Sdg => {
+                // S† = S³
+                self.s(qubits[0]);
+                self.s(qubits[0]);
+                self.s(qubits[0]);
+            }
+            CX => self.cx(qubits[0], qubits[1]),
+            CZ => {
+                self.h(qubits[1]);
+                self.cx(qubits[0], qubits[1]);
+                self.h(qubits[1]);
+            }
+            CY => {
+                // CY = (I ⊗ S) CX (I ⊗ S†)
+                self.s(qubits[1]);
+                self.s(qubits[1]);
+                self.s(qubits[1]);
+                self.cx(qubits[0], qubits[1]);
+                self.s(qubits[1]);
+            }
+            Swap => {
+                self.cx(qubits[0], qubits[1]);
+                self.cx(qubits[1], qubits[0]);
+                self.cx(qubits[0], qubits[1]);
+            }
+            _ => {
+                return Err(HomayaError::NotSupported {
+                    operation: "stabilizer simulation supports Clifford gates only",
+                })
+            }
+        }
+        Ok(())
+    }
+
+    /// Overwrite row `dst` with row `src`.
+    fn copy_row(&mut self, dst: usize, src: usize) {
+        let n = self.num_qubits;
+        self.x.copy_within(src * n..(src + 1) * n, dst * n);
+        self.z.copy_within(src * n..(src + 1) * n, dst * n);
+        self.r[dst] = self.r[src];
+    }
+
+    /// Multiply row `h` by row `i` (as Pauli operators), tracking the sign.
+    fn rowsum(&mut self, h: usize, i: usize) {
+        // Exponent of i picked up by each single-qubit Pauli product
+        let g = |x1: bool, z1: bool, x2: bool, z2: bool| -> i32 {
+            match (x1, z1) {
+                (false, false) => 0,
+                (true, true) => z2 as i32 - x2 as i32,
+                (true, false) => z2 as i32 * (2 * x2 as i32 - 1),
+                (false, true) => x2 as i32 * (1 - 2 * z2 as i32),
+            }
+        };
+
+        let mut phase = 2 * self.r[h] as i32 + 2 * self.r[i] as i32;
+        for q in 0..self.num_qubits {
+            let (ki, kh) = (self.idx(i, q), self.idx(h, q));
+            phase += g(self.x[ki], self.z[ki], self.x[kh], self.z[kh]);
+        }
+        self.r[h] = phase.rem_euclid(4) == 2;
+
+        for q in 0..self.num_qubits {
+            let (ki, kh) = (self.idx(i, q), self.idx(h, q));
+            self.x[kh] ^= self.x[ki];
+            self.z[kh] ^= self.z[ki];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic_measurements() {
+        let mut state = StabilizerState::new(3);
+        state.x(1);
+        assert_eq!(state.measure(0, 0.9), 0);
+        assert_eq!(state.measure(1, 0.1), 1);
+
+        // H S S H = X
+        state.h(2);
+        state.s(2);
+        state.s(2);
+        state.h(2);
+        assert_eq!(state.measure(2, 0.1), 1);
+    }
+
+    #[test]
+    fn test_ghz_outcomes_agree() {
+        for random in [0.2, 0.7] {
+            let mut state = StabilizerState::new(5);
+            state.h(0);
+            for q in 0..4 {
+                state.cx(q, q + 1);
+            }
+            let first = state.measure(0, random);
+            assert_eq!(first, (random >= 0.5) as u8);
+            for q in 1..5 {
+                assert_eq!(state.measure(q, 1.0 - random), first);
+            }
+        }
+    }
+
+    #[test]
+    fn test_phase_kickback() {
+        // H Z H = X and H S S H = X on |0⟩; CZ between |+⟩|1⟩ flips the control to |−⟩
+        let mut state = StabilizerState::new(2);
+        state.h(0);
+        state.x(1);
+        state.apply_gate(&Gate::cz(), &[0, 1]).unwrap();
+        state.h(0);
+        assert_eq!(state.measure(0, 0.1), 1);
+    }
+
+    #[test]
+    fn test_rejects_non_clifford() {
+        let mut state = StabilizerState::new(1);
+        assert!(state.apply_gate(&Gate::t(), &[0]).is_err());
+        assert!(!StabilizerState::supports(&Gate::rx(0.1)));
+        assert!(StabilizerState::supports(&Gate::sdg()));
+    }
+}
@@ -3,6 +3,7 @@
 //! Optimized for cache-friendly access patterns.
 
 use homaya_core::{Complex, HomayaError, Result};
+use crate::simd;
 
 /// A quantum state vector.
 ///
@@ -40,6 +41,9 @@ impl StateVector {
     pub const MAX_QUBITS: usize = 30;
 
     /// Create a new state vector initialized to |0...0⟩.
+    ///
+    /// Does not check the size; use [`try_new`](Self::try_new) when
+    /// `num_qubits` may exceed [`MAX_QUBITS`](Self::MAX_QUBITS).
     pub fn new(num_qubits: usize) -> Self {
         let dim = 1 << num_qubits;
         let mut amplitudes = vec![Complex::ZERO; dim];
@@ -50,6 +54,65 @@ impl StateVector {
         }
     }
 
+    /// Create a new state vector initialized to |0...0⟩, checking its size.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CircuitTooLarge` if `num_qubits` exceeds
+    /// [`MAX_QUBITS`](Self::MAX_QUBITS), instead of overflowing or
+    /// exhausting memory.
+    pub fn try_new(num_qubits: usize) -> Result<Self> {
+        if num_qubits > Self::MAX_QUBITS {
+            return Err(HomayaError::CircuitTooLarge {
+                qubits: num_qubits,
+                max: Self::MAX_QUBITS,
+            });
+        }
+        Ok(Self::new(num_qubits))
+    }
+
+    /// Create the computational basis state |index⟩.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` does not fit in `num_qubits` bits.
+    pub fn basis(num_qubits: usize, index: usize) -> Self {
+        let dim = 1 << num_qubits;
+        assert!(index < dim, "basis state {} out of range for {} qubits", index, num_qubits);
+        let mut amplitudes = vec![Complex::ZERO; dim];
+        amplitudes[index] = Complex::ONE;
+        Self {
+            num_qubits,
+            amplitudes,
+        }
+    }
+
+    /// Create the basis state written as a bitstring.
+    ///
+    /// The string is read like a binary number, highest qubit first, so
+    /// `"110"` is index `0b110` with qubits 1 and 2 set. Note that this is
+    /// the reverse of [`MeasurementResult::bitstring`](crate::MeasurementResult::bitstring),
+    /// which lists bit 0 first.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the string is empty or contains anything other than
+    /// `0` and `1`, or if it is longer than [`MAX_QUBITS`](Self::MAX_QUBITS).
+    pub fn from_bitstring(bits: &str) -> Result<Self> {
+        if bits.is_empty() || !bits.chars().all(|c| c == '0' || c == '1') {
+            return Err(HomayaError::InvalidBitstring { bits: bits.to_string() });
+        }
+        if bits.len() > Self::MAX_QUBITS {
+            return Err(HomayaError::CircuitTooLarge {
+                qubits: bits.len(),
+                max: Self::MAX_QUBITS,
+            });
+        }
+
+        let index = usize::from_str_radix(bits, 2).expect("bitstring is binary");
+        Ok(Self::basis(bits.len(), index))
+    }
+
     /// Create a state vector from amplitudes.
     ///
     /// # Errors
@@ -77,6 +140,22 @@ impl StateVector {
         })
     }
 
+    /// Create a real, phase-free state with the given measurement probabilities.
+    ///
+    /// Amplitude k is √pₖ. Useful for turning a measured distribution back
+    /// into a state for visualization; the original phases are lost.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if any probability is outside [0, 1], the length is
+    /// not a power of two, or the probabilities don't sum to 1.
+    pub fn from_probabilities(probs: &[f64]) -> Result<Self> {
+        if let Some(&p) = probs.iter().find(|p| !(0.0..=1.0).contains(*p)) {
+            return Err(HomayaError::InvalidProbability { value: p });
+        }
+        Self::from_amplitudes(probs.iter().map(|p| Complex::from_real(p.sqrt())).collect())
+    }
+
     /// Create a uniform superposition over all basis states.
     pub fn uniform(num_qubits: usize) -> Self {
         let dim = 1 << num_qubits;
@@ -134,6 +213,22 @@ impl StateVector {
         self.amplitudes.iter().map(|c| c.norm_sqr()).collect()
     }
 
+    /// Get the probability of measuring `qubit` as 1.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `qubit` is out of range.
+    pub fn qubit_probability(&self, qubit: usize) -> f64 {
+        assert!(qubit < self.num_qubits, "qubit {} out of range for {} qubits", qubit, self.num_qubits);
+        let mask = 1 << qubit;
+        self.amplitudes
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| i & mask != 0)
+            .map(|(_, c)| c.norm_sqr())
+            .sum()
+    }
+
     /// Normalize the state vector in-place.
     pub fn normalize(&mut self) {
         let norm_sqr: f64 = self.amplitudes.iter().map(|c| c.norm_sqr()).sum();
@@ -145,27 +240,61 @@ impl StateVector {
         }
     }
 
-    /// Apply a single-qubit gate.
+    /// Multiply every amplitude by the global phase e^{iθ}.
+    pub fn apply_global_phase(&mut self, theta: f64) {
+        let phase = Complex::from_polar(1.0, theta);
+        for amp in &mut self.amplitudes {
+            *amp *= phase;
+        }
+    }
+
+    /// Append a qubit in |0⟩ (or |1⟩ if `in_state_one`), growing the register.
     ///
-    /// Uses an optimized algorithm that minimizes cache misses.
-    pub fn apply_single(&mut self, qubit: usize, matrix: [[Complex; 2]; 2]) {
+    /// The new qubit becomes the highest-index qubit, so the state becomes
+    /// |new⟩ ⊗ |ψ⟩ in this crate's little-endian ordering.
+    pub fn add_qubit(&mut self, in_state_one: bool) {
         let dim = self.dimension();
-        let mask = 1 << qubit;
+        self.amplitudes.resize(2 * dim, Complex::ZERO);
+        if in_state_one {
+            let (low, high) = self.amplitudes.split_at_mut(dim);
+            low.swap_with_slice(high);
+        }
+        self.num_qubits += 1;
+    }
 
-        // Process pairs of amplitudes where they differ only in the qubit bit
-        for i in 0..dim {
-            // Only process when the qubit bit is 0
-            if (i & mask) == 0 {
-                let i0 = i;          // qubit bit = 0
-                let i1 = i | mask;   // qubit bit = 1
+    /// Apply a single-qubit gate.
+    ///
+    /// Uses an optimized algorithm that minimizes cache misses, vectorized
+    /// with SSE2 on x86_64 and falling back to scalar arithmetic elsewhere.
+    pub fn apply_single(&mut self, qubit: usize, matrix: [[Complex; 2]; 2]) {
+        simd::apply_single(&mut self.amplitudes, 1 << qubit, &matrix);
+    }
 
-                let a0 = self.amplitudes[i0];
-                let a1 = self.amplitudes[i1];
+    /// Move qubit `q` to position `dest[q]`, for every qubit, in one pass.
+    ///
+    /// Equivalent to the SWAP network that realizes the permutation, but
+    /// touches each amplitude once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dest` is not a permutation of `0..num_qubits`.
+    pub fn apply_qubit_permutation(&mut self, dest: &[usize]) {
+        assert_eq!(dest.len(), self.num_qubits, "permutation must cover every qubit");
+        let mut seen = 0usize;
+        for &d in dest {
+            assert!(d < self.num_qubits && seen & (1 << d) == 0, "not a qubit permutation: {:?}", dest);
+            seen |= 1 << d;
+        }
 
-                self.amplitudes[i0] = matrix[0][0] * a0 + matrix[0][1] * a1;
-                self.amplitudes[i1] = matrix[1][0] * a0 + matrix[1][1] * a1;
-            }
+        let mut permuted = vec![Complex::ZERO; self.dimension()];
+        for (i, &amp) in self.amplitudes.iter().enumerate() {
+            let j = dest
+                .iter()
+                .enumerate()
+                .fold(0usize, |acc, (q, &d)| acc | (((i >> q) & 1) << d));
+            permuted[j] = amp;
         }
+        self.amplitudes = permuted;
     }
 
     /// Apply a two-qubit gate.
@@ -198,6 +327,33 @@ impl StateVector {
         }
     }
 
+    /// Apply a three-qubit gate.
+    ///
+    /// Rows and columns of `matrix` are indexed by `b0 + 2·b1 + 4·b2`, where
+    /// `bk` is the bit of qubit `qk`.
+    pub fn apply_three(&mut self, q0: usize, q1: usize, q2: usize, matrix: [[Complex; 8]; 8]) {
+        let dim = self.dimension();
+        let masks = [1 << q0, 1 << q1, 1 << q2];
+        let all = masks[0] | masks[1] | masks[2];
+
+        // Process groups of 8 amplitudes where all three qubits are 0
+        for i in 0..dim {
+            if i & all == 0 {
+                let indices: [usize; 8] = std::array::from_fn(|k| {
+                    (0..3).filter(|b| k >> b & 1 == 1).fold(i, |acc, b| acc | masks[b])
+                });
+                let amps = indices.map(|index| self.amplitudes[index]);
+
+                for (row, &index) in matrix.iter().zip(&indices) {
+                    self.amplitudes[index] = row
+                        .iter()
+                        .zip(&amps)
+                        .fold(Complex::ZERO, |acc, (&m, &a)| acc + m * a);
+                }
+            }
+        }
+    }
+
     /// Apply a controlled single-qubit gate (optimized).
     ///
     /// This is more efficient than the general two-qubit gate for CX, CZ, etc.
@@ -223,6 +379,20 @@ impl StateVector {
         }
     }
 
+    /// Flip `target` on the basis states where every control qubit is 1.
+    ///
+    /// With no controls this is a plain X.
+    pub fn apply_mcx(&mut self, controls: &[usize], target: usize) {
+        let control_mask = controls.iter().fold(0usize, |mask, &c| mask | (1 << c));
+        let target_mask = 1 << target;
+
+        for i in 0..self.dimension() {
+            if i & control_mask == control_mask && i & target_mask == 0 {
+                self.amplitudes.swap(i, i | target_mask);
+            }
+        }
+    }
+
     /// Measure a single qubit, collapsing the state.
     ///
     /// Returns the measurement result (0 or 1).
@@ -255,6 +425,72 @@ impl StateVector {
         result
     }
 
+    /// Jointly measure several qubits, collapsing the state.
+    ///
+    /// Returns the combined outcome with bit `j` holding the result for
+    /// `qubits[j]`. The state collapses onto the measured subspace and is
+    /// renormalized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a qubit index is out of range.
+    pub fn measure_joint(&mut self, qubits: &[usize], random: f64) -> usize {
+        let outcome_probs = self.joint_probabilities(qubits);
+        let outcome = pick_outcome(&outcome_probs, random);
+
+        let inv_sqrt_norm = 1.0 / outcome_probs[outcome].sqrt();
+        for (i, amp) in self.amplitudes.iter_mut().enumerate() {
+            if extract_bits(i, qubits) == outcome {
+                *amp = *amp * inv_sqrt_norm;
+            } else {
+                *amp = Complex::ZERO;
+            }
+        }
+
+        outcome
+    }
+
+    /// Measure several qubits one after another, collapsing the state.
+    ///
+    /// Draws one value from `random` per qubit and returns the outcomes in
+    /// the order of `qubits`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a qubit index is out of range.
+    pub fn measure_qubits(&mut self, qubits: &[usize], random: &mut impl FnMut() -> f64) -> Vec<u8> {
+        qubits.iter().map(|&q| self.measure(q, random())).collect()
+    }
+
+    /// Sample a joint outcome of several qubits without collapsing the state.
+    ///
+    /// Returns one bit per entry of `qubits`, in order, drawn from their
+    /// joint distribution with a single random value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a qubit index is out of range.
+    pub fn sample_qubits(&self, qubits: &[usize], random: f64) -> Vec<u8> {
+        let outcome = pick_outcome(&self.joint_probabilities(qubits), random);
+        (0..qubits.len()).map(|j| (outcome >> j & 1) as u8).collect()
+    }
+
+    /// Probability of each joint outcome of `qubits`, indexed as in
+    /// [`measure_joint`](Self::measure_joint).
+    fn joint_probabilities(&self, qubits: &[usize]) -> Vec<f64> {
+        assert!(
+            qubits.iter().all(|&q| q < self.num_qubits),
+            "qubit index out of range for {} qubits",
+            self.num_qubits
+        );
+
+        let mut outcome_probs = vec![0.0; 1 << qubits.len()];
+        for (i, amp) in self.amplitudes.iter().enumerate() {
+            outcome_probs[extract_bits(i, qubits)] += amp.norm_sqr();
+        }
+        outcome_probs
+    }
+
     /// Reset a qubit to |0⟩.
     pub fn reset(&mut self, qubit: usize, random: f64) {
         let result = self.measure(qubit, random);
@@ -266,6 +502,11 @@ impl StateVector {
     }
 
     /// Sample a measurement outcome without collapsing.
+    ///
+    /// `random` should lie in `[0, 1)`. When rounding leaves the total
+    /// probability just short of `random` (always the case for
+    /// `random == 1.0`), the last basis state with nonzero probability is
+    /// returned, so a zero-probability outcome is never reported.
     pub fn sample(&self, random: f64) -> usize {
         let mut cumulative = 0.0;
         for (i, amp) in self.amplitudes.iter().enumerate() {
@@ -274,11 +515,19 @@ impl StateVector {
                 return i;
             }
         }
-        self.dimension() - 1
+        self.amplitudes
+            .iter()
+            .rposition(|amp| amp.norm_sqr() > 0.0)
+            .unwrap_or(self.dimension() - 1)
     }
 
-    /// Get the inner product with another state.
+    /// Get the inner product ⟨self|other⟩ with another state.
+    ///
+    /// Both states must have the same number of qubits; this is checked
+    /// only in debug builds. Use [`try_inner_product`](Self::try_inner_product)
+    /// when the sizes may differ.
     pub fn inner_product(&self, other: &StateVector) -> Complex {
+        debug_assert_eq!(self.num_qubits, other.num_qubits, "inner product of states with different qubit counts");
         self.amplitudes
             .iter()
             .zip(other.amplitudes.iter())
@@ -286,10 +535,252 @@ impl StateVector {
             .fold(Complex::ZERO, |acc, x| acc + x)
     }
 
-    /// Calculate the fidelity with another state.
+    /// Get the inner product ⟨self|other⟩, checking the sizes match.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StateDimensionMismatch` if the states have different
+    /// numbers of qubits.
+    pub fn try_inner_product(&self, other: &StateVector) -> Result<Complex> {
+        if self.num_qubits != other.num_qubits {
+            return Err(HomayaError::StateDimensionMismatch {
+                expected: self.dimension(),
+                got: other.dimension(),
+            });
+        }
+        Ok(self.inner_product(other))
+    }
+
+    /// Calculate the fidelity |⟨self|other⟩|² with another state.
+    ///
+    /// Has the same precondition as [`inner_product`](Self::inner_product).
     pub fn fidelity(&self, other: &StateVector) -> f64 {
         self.inner_product(other).norm_sqr()
     }
+
+    /// Calculate the fidelity, checking the sizes match.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StateDimensionMismatch` if the states have different
+    /// numbers of qubits.
+    pub fn try_fidelity(&self, other: &StateVector) -> Result<f64> {
+        Ok(self.try_inner_product(other)?.norm_sqr())
+    }
+
+    /// Compare with another state up to a global phase.
+    ///
+    /// Each state is rotated so its first amplitude larger than `eps` is
+    /// real and positive, then the amplitudes are compared within `eps`.
+    /// States with different qubit counts are never equal.
+    pub fn phase_aligned_eq(&self, other: &StateVector, eps: f64) -> bool {
+        if self.num_qubits != other.num_qubits {
+            return false;
+        }
+
+        let unphase = |state: &StateVector| {
+            state
+                .amplitudes
+                .iter()
+                .find(|a| !a.is_zero(eps))
+                .map_or(Complex::ONE, |a| Complex::from_polar(1.0, -a.arg()))
+        };
+        let (p, q) = (unphase(self), unphase(other));
+        self.amplitudes
+            .iter()
+            .zip(&other.amplitudes)
+            .all(|(a, b)| (*a * p).approx_eq(*b * q, eps))
+    }
+
+    /// Compute the expectation ⟨ψ|P|ψ⟩ of a Pauli string.
+    ///
+    /// `paulis` lists `(qubit, 'I' | 'X' | 'Y' | 'Z')` pairs; unlisted
+    /// qubits get the identity. The value is exact, with no sampling noise.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a character is not a Pauli, or a qubit is out of
+    /// range or listed twice.
+    pub fn expectation_pauli(&self, paulis: &[(usize, char)]) -> Result<f64> {
+        // P = i^{#Y} X^{x_mask} Z^{z_mask}, since Y = iXZ
+        let (mut x_mask, mut z_mask, mut num_y) = (0usize, 0usize, 0);
+        let mut seen = 0usize;
+        for &(qubit, pauli) in paulis {
+            if qubit >= self.num_qubits {
+                return Err(HomayaError::QubitOutOfRange {
+                    qubit,
+                    max: self.num_qubits,
+                });
+            }
+            if seen & (1 << qubit) != 0 {
+                return Err(HomayaError::DuplicateQubit { qubit });
+            }
+            seen |= 1 << qubit;
+
+            match pauli.to_ascii_uppercase() {
+                'I' => {}
+                'X' => x_mask |= 1 << qubit,
+                'Z' => z_mask |= 1 << qubit,
+                'Y' => {
+                    x_mask |= 1 << qubit;
+                    z_mask |= 1 << qubit;
+                    num_y += 1;
+                }
+                _ => {
+                    return Err(HomayaError::NotSupported {
+                        operation: "Pauli observable must use I, X, Y or Z",
+                    })
+                }
+            }
+        }
+
+        let phase = [Complex::ONE, Complex::I, -Complex::ONE, -Complex::I][num_y % 4];
+        let sum = self
+            .amplitudes
+            .iter()
+            .enumerate()
+            .fold(Complex::ZERO, |acc, (i, &amp)| {
+                let term = self.amplitudes[i ^ x_mask].conj() * amp;
+                if (i & z_mask).count_ones() % 2 == 0 { acc + term } else { acc - term }
+            });
+        Ok((phase * sum).re)
+    }
+
+    /// Compute Wootters' concurrence of a two-qubit state.
+    ///
+    /// For a pure state the general formula (square roots of the
+    /// eigenvalues of ρρ̃, with ρ̃ the spin-flipped ρ) reduces to
+    /// C = |⟨ψ|σy⊗σy|ψ*⟩| = 2|a₀₀a₁₁ − a₀₁a₁₀|. It is 0 for product states
+    /// and 1 for maximally entangled ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the state does not have exactly two qubits.
+    pub fn concurrence(&self) -> Result<f64> {
+        if self.num_qubits != 2 {
+            return Err(HomayaError::QubitMismatch {
+                expected: 2,
+                got: self.num_qubits,
+            });
+        }
+
+        let a = &self.amplitudes;
+        Ok(2.0 * (a[0b00] * a[0b11] - a[0b01] * a[0b10]).abs())
+    }
+
+    /// Compute the reduced density matrix of the qubits in `keep`.
+    ///
+    /// Traces out every other qubit, giving the 2^k × 2^k matrix
+    /// ρ_A = Tr_B |ψ⟩⟨ψ| for k = `keep.len()`. Qubit `keep[j]` becomes bit
+    /// `j` of the row and column indices. Unlike going through
+    /// [`DensityMatrix::partial_trace`](crate::DensityMatrix::partial_trace),
+    /// this never builds the full 2^n × 2^n matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keep` contains an out-of-range or duplicate qubit.
+    pub fn reduced_density_matrix(&self, keep: &[usize]) -> Vec<Vec<Complex>> {
+        for (j, &q) in keep.iter().enumerate() {
+            assert!(q < self.num_qubits, "qubit {} out of range for {} qubits", q, self.num_qubits);
+            assert!(!keep[..j].contains(&q), "duplicate qubit {} in partial trace", q);
+        }
+
+        let keep_mask = keep.iter().fold(0usize, |acc, &q| acc | (1 << q));
+        let traced: Vec<usize> = (0..self.num_qubits).filter(|q| keep_mask >> q & 1 == 0).collect();
+
+        // Split ψ into one vector over the kept qubits per traced-out basis state
+        let dim = 1 << keep.len();
+        let mut blocks = vec![vec![Complex::ZERO; dim]; 1 << traced.len()];
+        for (i, &amp) in self.amplitudes.iter().enumerate() {
+            blocks[extract_bits(i, &traced)][extract_bits(i, keep)] = amp;
+        }
+
+        // ρ_A[a][a'] = Σ_b ψ[(a,b)] ψ*[(a',b)]
+        let mut reduced = vec![vec![Complex::ZERO; dim]; dim];
+        for block in &blocks {
+            for (row, a) in reduced.iter_mut().zip(block) {
+                for (entry, b) in row.iter_mut().zip(block) {
+                    *entry += *a * b.conj();
+                }
+            }
+        }
+        reduced
+    }
+
+    /// Compute the purity Tr(ρ²) of the subsystem on the qubits in `keep`.
+    ///
+    /// ρ is the [reduced density matrix](Self::reduced_density_matrix), and
+    /// since it is Hermitian, Tr(ρ²) is the sum of |ρᵢⱼ|². The purity is 1
+    /// when the subsystem is unentangled with the rest and falls to 2⁻ᵏ for
+    /// k maximally entangled qubits, e.g. 0.5 for one qubit of a Bell pair.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `keep` contains an out-of-range or duplicate qubit.
+    pub fn subsystem_purity(&self, keep: &[usize]) -> f64 {
+        self.reduced_density_matrix(keep)
+            .iter()
+            .flatten()
+            .map(|entry| entry.norm_sqr())
+            .sum()
+    }
+
+    /// Get the Bloch vector (x, y, z) of one qubit.
+    ///
+    /// Computed from the qubit's reduced density matrix as
+    /// x = 2 Re ρ₁₀, y = 2 Im ρ₁₀, z = ρ₀₀ − ρ₁₁, so it is defined for
+    /// entangled qubits too: a qubit of a Bell pair sits at the origin.
+    /// Returns `None` if `qubit` is out of range.
+    pub fn bloch_vector(&self, qubit: usize) -> Option<(f64, f64, f64)> {
+        if qubit >= self.num_qubits {
+            return None;
+        }
+        let rho = self.reduced_density_matrix(&[qubit]);
+        Some((2.0 * rho[1][0].re, 2.0 * rho[1][0].im, rho[0][0].re - rho[1][1].re))
+    }
+
+    /// Compute the overlaps `⟨ref_k|ψ⟩` with a set of reference states.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if any reference state has a different dimension.
+    pub fn overlaps(&self, refs: &[StateVector]) -> Result<Vec<Complex>> {
+        refs.iter()
+            .map(|r| {
+                if r.dimension() != self.dimension() {
+                    return Err(HomayaError::StateDimensionMismatch {
+                        expected: self.dimension(),
+                        got: r.dimension(),
+                    });
+                }
+                Ok(r.inner_product(self))
+            })
+            .collect()
+    }
+}
+
+/// Gather the bits of `index` at positions `qubits` into bits `0..qubits.len()`.
+fn extract_bits(index: usize, qubits: &[usize]) -> usize {
+    qubits
+        .iter()
+        .enumerate()
+        .fold(0usize, |acc, (j, &q)| acc | (((index >> q) & 1) << j))
+}
+
+/// Choose the outcome whose cumulative probability first exceeds `random`.
+///
+/// Falls back to the last outcome with nonzero probability when rounding
+/// leaves the total just below `random`, so an impossible outcome is never
+/// chosen.
+fn pick_outcome(probs: &[f64], random: f64) -> usize {
+    let mut cumulative = 0.0;
+    for (k, &p) in probs.iter().enumerate() {
+        cumulative += p;
+        if random < cumulative {
+            return k;
+        }
+    }
+    probs.iter().rposition(|&p| p > 0.0).unwrap_or(probs.len() - 1)
 }
 
 impl PartialEq for StateVector {
@@ -391,10 +882,446 @@ mod tests {
         assert_eq!(state.get(1), Complex::ZERO);
     }
 
+    #[test]
+    fn test_measure_joint_bell() {
+        let h = Complex::from_real(std::f64::consts::FRAC_1_SQRT_2);
+        let bell = StateVector::from_amplitudes(vec![h, Complex::ZERO, Complex::ZERO, h]).unwrap();
+
+        for step in 0..20 {
+            let random = step as f64 / 20.0;
+            let mut state = bell.clone();
+            let outcome = state.measure_joint(&[0, 1], random);
+            assert!(outcome == 0b00 || outcome == 0b11);
+            assert!((state.probability(outcome) - 1.0).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_joint_outcome_skips_trailing_zero_probability() {
+        // |+⟩ on qubit 0, |0⟩ on qubit 1: outcome 1 of qubit 1 is impossible
+        let h = Complex::from_real(std::f64::consts::FRAC_1_SQRT_2);
+        let state = StateVector::from_amplitudes(vec![h, h, Complex::ZERO, Complex::ZERO]).unwrap();
+        assert_eq!(state.sample_qubits(&[1], 1.0), vec![0]);
+
+        let mut collapsed = state.clone();
+        assert_eq!(collapsed.measure_joint(&[1], 1.0), 0);
+        assert!(collapsed.amplitudes().iter().all(|amp| amp.re.is_finite() && amp.im.is_finite()));
+        assert!(collapsed.fidelity(&state) > 1.0 - 1e-10);
+    }
+
+    #[test]
+    fn test_measure_joint_partial_subset() {
+        // |+⟩ on qubit 0, |1⟩ on qubit 2
+        let h = Complex::from_real(std::f64::consts::FRAC_1_SQRT_2);
+        let mut amps = vec![Complex::ZERO; 8];
+        amps[0b100] = h;
+        amps[0b101] = h;
+        let mut state = StateVector::from_amplitudes(amps).unwrap();
+
+        // Outcome bit 0 is qubit 2, bit 1 is qubit 1
+        assert_eq!(state.measure_joint(&[2, 1], 0.9), 0b01);
+        // Qubit 0 is untouched
+        assert!((state.probability(0b100) - 0.5).abs() < 1e-10);
+        assert!((state.probability(0b101) - 0.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_measure_qubits_bell_agree() {
+        let h = Complex::from_real(std::f64::consts::FRAC_1_SQRT_2);
+        let bell = StateVector::from_amplitudes(vec![h, Complex::ZERO, Complex::ZERO, h]).unwrap();
+
+        let mut draws = [0.1, 0.7, 0.4, 0.9, 0.6, 0.2].into_iter().cycle();
+        let mut random = || draws.next().unwrap();
+        let mut seen = [false; 2];
+        for _ in 0..6 {
+            let mut state = bell.clone();
+            let bits = state.measure_qubits(&[1, 0], &mut random);
+            assert_eq!(bits.len(), 2);
+            assert_eq!(bits[0], bits[1]);
+            assert!((state.probability(bits[0] as usize * 0b11) - 1.0).abs() < 1e-10);
+            seen[bits[0] as usize] = true;
+        }
+        assert_eq!(seen, [true, true]);
+    }
+
+    #[test]
+    fn test_sample_qubits_bell_agree() {
+        let h = Complex::from_real(std::f64::consts::FRAC_1_SQRT_2);
+        let bell = StateVector::from_amplitudes(vec![h, Complex::ZERO, Complex::ZERO, h]).unwrap();
+
+        for step in 0..20 {
+            let bits = bell.sample_qubits(&[0, 1], (step as f64 + 0.5) / 20.0);
+            assert_eq!(bits[0], bits[1]);
+            assert_eq!(bits[0], u8::from(step >= 10));
+        }
+        // Sampling leaves the state alone
+        assert!((bell.probability(0b00) - 0.5).abs() < 1e-10);
+        assert!((bell.probability(0b11) - 0.5).abs() < 1e-10);
+
+        // Subset order is respected: qubit 2 is |1⟩, qubit 0 is |0⟩
+        assert_eq!(StateVector::basis(3, 0b100).sample_qubits(&[2, 0], 0.5), vec![1, 0]);
+    }
+
     #[test]
     fn test_fidelity_with_self() {
         let state = StateVector::uniform(3);
         let fidelity = state.fidelity(&state);
         assert!((fidelity - 1.0).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_add_qubit() {
+        let h = Complex::from_real(std::f64::consts::FRAC_1_SQRT_2);
+        let mut state = StateVector::from_amplitudes(vec![h, h]).unwrap();
+
+        state.add_qubit(false);
+        assert_eq!(state.num_qubits(), 2);
+        // |+⟩ on qubit 0, ancilla |0⟩ on qubit 1
+        let expected = StateVector::from_amplitudes(vec![h, h, Complex::ZERO, Complex::ZERO]).unwrap();
+        assert_eq!(state, expected);
+
+        state.add_qubit(true);
+        assert_eq!(state.num_qubits(), 3);
+        assert!(state.get(0b100).approx_eq(h, 1e-10));
+        assert!(state.get(0b101).approx_eq(h, 1e-10));
+        assert!((state.probabilities().iter().sum::<f64>() - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_overlaps_bell_with_basis() {
+        let h = Complex::from_real(std::f64::consts::FRAC_1_SQRT_2);
+        let bell = StateVector::from_amplitudes(vec![h, Complex::ZERO, Complex::ZERO, h]).unwrap();
+
+        let basis: Vec<StateVector> = (0..4)
+            .map(|k| {
+                let mut amps = vec![Complex::ZERO; 4];
+                amps[k] = Complex::ONE;
+                StateVector::from_amplitudes(amps).unwrap()
+            })
+            .collect();
+
+        let overlaps = bell.overlaps(&basis).unwrap();
+        let expected = [h, Complex::ZERO, Complex::ZERO, h];
+        for (o, e) in overlaps.iter().zip(expected.iter()) {
+            assert!(o.approx_eq(*e, 1e-10));
+        }
+
+        assert!(bell.overlaps(&[StateVector::new(1)]).is_err());
+    }
+
+    #[test]
+    fn test_sample_boundary_skips_zero_probability_tail() {
+        // Probabilities sum to just under 1; the tail states are empty
+        let amps = vec![
+            Complex::from_real(0.5f64.sqrt()),
+            Complex::from_real((0.5f64 - 1e-12).sqrt()),
+            Complex::ZERO,
+            Complex::ZERO,
+        ];
+        let state = StateVector::from_amplitudes(amps).unwrap();
+
+        assert_eq!(state.sample(1.0), 1);
+        assert_eq!(state.sample(1.0 - 1e-13), 1);
+        assert_eq!(state.sample(0.25), 0);
+    }
+
+    #[test]
+    fn test_apply_global_phase() {
+        let mut state = StateVector::uniform(2);
+        state.apply_global_phase(std::f64::consts::FRAC_PI_2);
+        for amp in state.amplitudes() {
+            assert!(amp.approx_eq(Complex::new(0.0, 0.5), 1e-12));
+        }
+        assert!((state.fidelity(&StateVector::uniform(2)) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_phase_aligned_eq() {
+        let original = StateVector::from_amplitudes(vec![
+            Complex::ZERO,
+            Complex::new(0.6, 0.0),
+            Complex::new(0.0, -0.8),
+            Complex::ZERO,
+        ])
+        .unwrap();
+        let mut rotated = original.clone();
+        rotated.apply_global_phase(2.1);
+
+        assert_ne!(original, rotated);
+        assert!(original.phase_aligned_eq(&rotated, 1e-10));
+        assert!(rotated.phase_aligned_eq(&original, 1e-10));
+
+        // A relative phase is not a global one
+        let mut relative = original.clone();
+        relative.set(2, Complex::new(0.0, 0.8));
+        assert!(!original.phase_aligned_eq(&relative, 1e-10));
+
+        assert!(!StateVector::new(1).phase_aligned_eq(&StateVector::new(2), 1e-10));
+        assert!(!StateVector::new(1).phase_aligned_eq(&StateVector::basis(1, 1), 1e-10));
+    }
+
+    #[test]
+    fn test_expectation_pauli() {
+        // |+i⟩ = (|0⟩ + i|1⟩)/√2 is the +1 eigenstate of Y
+        let h = std::f64::consts::FRAC_1_SQRT_2;
+        let plus_i = StateVector::from_amplitudes(vec![Complex::from_real(h), Complex::new(0.0, h)]).unwrap();
+        assert!((plus_i.expectation_pauli(&[(0, 'Y')]).unwrap() - 1.0).abs() < 1e-12);
+        assert!(plus_i.expectation_pauli(&[(0, 'x')]).unwrap().abs() < 1e-12);
+        assert!(plus_i.expectation_pauli(&[(0, 'Z')]).unwrap().abs() < 1e-12);
+        assert!((plus_i.expectation_pauli(&[]).unwrap() - 1.0).abs() < 1e-12);
+
+        // Z on qubit 1 of |10⟩
+        let state = StateVector::basis(2, 0b10);
+        assert!((state.expectation_pauli(&[(1, 'Z')]).unwrap() + 1.0).abs() < 1e-12);
+        assert!((state.expectation_pauli(&[(0, 'Z'), (1, 'I')]).unwrap() - 1.0).abs() < 1e-12);
+
+        assert!(matches!(
+            state.expectation_pauli(&[(2, 'Z')]),
+            Err(HomayaError::QubitOutOfRange { qubit: 2, max: 2 })
+        ));
+        assert!(matches!(
+            state.expectation_pauli(&[(0, 'X'), (0, 'Z')]),
+            Err(HomayaError::DuplicateQubit { qubit: 0 })
+        ));
+        assert!(state.expectation_pauli(&[(0, 'Q')]).is_err());
+    }
+
+    #[test]
+    fn test_concurrence() {
+        let h = Complex::from_real(std::f64::consts::FRAC_1_SQRT_2);
+        let bell = StateVector::from_amplitudes(vec![h, Complex::ZERO, Complex::ZERO, h]).unwrap();
+        assert!((bell.concurrence().unwrap() - 1.0).abs() < 1e-10);
+
+        // |+⟩ ⊗ |+⟩ is a product state
+        assert!(StateVector::uniform(2).concurrence().unwrap().abs() < 1e-10);
+
+        // cos θ|00⟩ + sin θ|11⟩ has concurrence sin 2θ
+        let theta: f64 = 0.3;
+        let partial = StateVector::from_amplitudes(vec![
+            Complex::from_real(theta.cos()),
+            Complex::ZERO,
+            Complex::ZERO,
+            Complex::from_real(theta.sin()),
+        ])
+        .unwrap();
+        let c = partial.concurrence().unwrap();
+        assert!((c - (2.0 * theta).sin()).abs() < 1e-10);
+        assert!(c > 0.0 && c < 1.0);
+
+        assert!(StateVector::new(3).concurrence().is_err());
+    }
+
+    #[test]
+    fn test_reduced_density_matrix_of_bell_state_is_mixed() {
+        let h = Complex::from_real(std::f64::consts::FRAC_1_SQRT_2);
+        let bell = StateVector::from_amplitudes(vec![h, Complex::ZERO, Complex::ZERO, h]).unwrap();
+
+        for keep in [0, 1] {
+            let rho = bell.reduced_density_matrix(&[keep]);
+            assert_eq!(rho.len(), 2);
+            assert!(rho[0][0].approx_eq(Complex::from_real(0.5), 1e-10));
+            assert!(rho[1][1].approx_eq(Complex::from_real(0.5), 1e-10));
+            assert!(rho[0][1].is_zero(1e-10) && rho[1][0].is_zero(1e-10));
+        }
+    }
+
+    #[test]
+    fn test_reduced_density_matrix_of_product_state_is_projector() {
+        // |ψ⟩ = |1⟩ ⊗ (|0⟩ + i|1⟩)/√2 ⊗ |+⟩ on qubits 2, 1, 0
+        let h = std::f64::consts::FRAC_1_SQRT_2;
+        let mut amps = vec![Complex::ZERO; 8];
+        for (i, amp) in amps.iter_mut().enumerate().filter(|(i, _)| i & 0b100 != 0) {
+            let phase = if i & 0b010 != 0 { Complex::I } else { Complex::ONE };
+            *amp = phase * (h * h);
+        }
+        let state = StateVector::from_amplitudes(amps).unwrap();
+
+        // Keep qubits 1 and 2, in the order (2, 1): |ψ_A⟩ = (|01⟩ + i|11⟩)/√2
+        let rho = state.reduced_density_matrix(&[2, 1]);
+        let psi = [Complex::ZERO, Complex::from_real(h), Complex::ZERO, Complex::new(0.0, h)];
+        for (r, row) in rho.iter().enumerate() {
+            for (c, &entry) in row.iter().enumerate() {
+                assert!(entry.approx_eq(psi[r] * psi[c].conj(), 1e-10), "ρ[{}][{}]", r, c);
+            }
+        }
+
+        // Rank 1: ρ² = ρ
+        for r in 0..4 {
+            for c in 0..4 {
+                let square = (0..4).fold(Complex::ZERO, |acc, k| acc + rho[r][k] * rho[k][c]);
+                assert!(square.approx_eq(rho[r][c], 1e-10));
+            }
+        }
+
+        // Keeping everything returns the full projector
+        assert_eq!(state.reduced_density_matrix(&[0, 1, 2]).len(), 8);
+    }
+
+    #[test]
+    fn test_subsystem_purity() {
+        let h = Complex::from_real(std::f64::consts::FRAC_1_SQRT_2);
+        let bell = StateVector::from_amplitudes(vec![h, Complex::ZERO, Complex::ZERO, h]).unwrap();
+        assert!((bell.subsystem_purity(&[0]) - 0.5).abs() < 1e-10);
+        assert!((bell.subsystem_purity(&[1]) - 0.5).abs() < 1e-10);
+        assert!((bell.subsystem_purity(&[0, 1]) - 1.0).abs() < 1e-10);
+
+        // |+⟩ ⊗ |+⟩ ⊗ |+⟩: every subsystem is pure
+        let product = StateVector::uniform(3);
+        for keep in [&[0][..], &[1], &[2, 0], &[0, 1, 2]] {
+            assert!((product.subsystem_purity(keep) - 1.0).abs() < 1e-10, "keep {:?}", keep);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate qubit")]
+    fn test_reduced_density_matrix_rejects_duplicates() {
+        StateVector::uniform(2).reduced_density_matrix(&[1, 1]);
+    }
+
+    #[test]
+    fn test_try_new_checks_size() {
+        assert_eq!(StateVector::try_new(3).unwrap(), StateVector::new(3));
+        assert!(StateVector::try_new(StateVector::MAX_QUBITS + 1).is_err());
+        assert!(matches!(
+            StateVector::try_new(64),
+            Err(HomayaError::CircuitTooLarge { qubits: 64, max: StateVector::MAX_QUBITS })
+        ));
+    }
+
+    #[test]
+    fn test_basis_state() {
+        let state = StateVector::basis(3, 0b110);
+        assert_eq!(state.num_qubits(), 3);
+        assert_eq!(state.get(0b110), Complex::ONE);
+        assert!((state.probability(0b110) - 1.0).abs() < 1e-12);
+        assert_eq!(StateVector::basis(2, 0), StateVector::new(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_basis_state_out_of_range() {
+        StateVector::basis(2, 4);
+    }
+
+    #[test]
+    fn test_try_inner_product_checks_sizes() {
+        let plus = StateVector::from_amplitudes(vec![Complex::from_real(homaya_core::INV_SQRT_2), Complex::from_real(homaya_core::INV_SQRT_2)]).unwrap();
+        let zero = StateVector::new(1);
+        assert!((plus.try_fidelity(&zero).unwrap() - 0.5).abs() < 1e-12);
+        assert!((plus.try_inner_product(&zero).unwrap().re - homaya_core::INV_SQRT_2).abs() < 1e-12);
+
+        // Zipping would silently compare only the first two amplitudes
+        let wide = StateVector::new(2);
+        assert_eq!(
+            plus.try_inner_product(&wide),
+            Err(HomayaError::StateDimensionMismatch { expected: 2, got: 4 })
+        );
+        assert_eq!(
+            wide.try_fidelity(&plus),
+            Err(HomayaError::StateDimensionMismatch { expected: 4, got: 2 })
+        );
+    }
+
+    #[test]
+    fn test_from_bitstring() {
+        let state = StateVector::from_bitstring("101").unwrap();
+        assert_eq!(state, StateVector::basis(3, 0b101));
+        assert_eq!(StateVector::from_bitstring("110").unwrap(), StateVector::basis(3, 0b110));
+
+        // Leading zeros still count as qubits
+        assert_eq!(StateVector::from_bitstring("0001").unwrap().num_qubits(), 4);
+
+        for bad in ["", "10a", "1 0", "2"] {
+            assert!(matches!(
+                StateVector::from_bitstring(bad),
+                Err(HomayaError::InvalidBitstring { bits }) if bits == bad
+            ));
+        }
+        assert!(matches!(
+            StateVector::from_bitstring(&"1".repeat(StateVector::MAX_QUBITS + 1)),
+            Err(HomayaError::CircuitTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_qubit_probability() {
+        // |1⟩ on qubit 0, |+⟩ on qubit 1, |0⟩ on qubit 2
+        let h = Complex::from_real(0.5f64.sqrt());
+        let mut amps = vec![Complex::ZERO; 8];
+        amps[0b001] = h;
+        amps[0b011] = h;
+        let state = StateVector::from_amplitudes(amps).unwrap();
+
+        assert!((state.qubit_probability(0) - 1.0).abs() < 1e-12);
+        assert!((state.qubit_probability(1) - 0.5).abs() < 1e-12);
+        assert!(state.qubit_probability(2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_bloch_vector() {
+        let close = |v: Option<(f64, f64, f64)>, e: (f64, f64, f64)| {
+            let (x, y, z) = v.unwrap();
+            (x - e.0).abs() < 1e-10 && (y - e.1).abs() < 1e-10 && (z - e.2).abs() < 1e-10
+        };
+
+        assert!(close(StateVector::new(1).bloch_vector(0), (0.0, 0.0, 1.0)));
+        assert!(close(StateVector::basis(1, 1).bloch_vector(0), (0.0, 0.0, -1.0)));
+        assert!(close(StateVector::uniform(1).bloch_vector(0), (1.0, 0.0, 0.0)));
+
+        // (|0⟩ + i|1⟩)/√2 points along +y
+        let h = std::f64::consts::FRAC_1_SQRT_2;
+        let plus_i = StateVector::from_amplitudes(vec![Complex::from_real(h), Complex::new(0.0, h)]).unwrap();
+        assert!(close(plus_i.bloch_vector(0), (0.0, 1.0, 0.0)));
+
+        // Each half of a Bell pair is maximally mixed
+        let bell = StateVector::from_amplitudes(vec![
+            Complex::from_real(h),
+            Complex::ZERO,
+            Complex::ZERO,
+            Complex::from_real(h),
+        ])
+        .unwrap();
+        assert!(close(bell.bloch_vector(1), (0.0, 0.0, 0.0)));
+        assert!(bell.bloch_vector(2).is_none());
+    }
+
+    #[test]
+    fn test_from_probabilities() {
+        let state = StateVector::from_probabilities(&[0.5, 0.0, 0.0, 0.5]).unwrap();
+        let h = Complex::from_real(std::f64::consts::FRAC_1_SQRT_2);
+        assert_eq!(state.num_qubits(), 2);
+        assert!(state.amplitudes()[0].approx_eq(h, 1e-12));
+        assert!(state.amplitudes()[3].approx_eq(h, 1e-12));
+        assert!(state.amplitudes()[1].is_zero(1e-12));
+
+        assert!(matches!(
+            StateVector::from_probabilities(&[0.5, 0.5, 0.0]),
+            Err(HomayaError::StateDimensionMismatch { .. })
+        ));
+        assert!(matches!(
+            StateVector::from_probabilities(&[0.5, 0.2]),
+            Err(HomayaError::StateNotNormalized { .. })
+        ));
+        assert!(matches!(
+            StateVector::from_probabilities(&[1.5, -0.5]),
+            Err(HomayaError::InvalidProbability { value }) if value == 1.5
+        ));
+    }
+
+    #[test]
+    fn test_apply_qubit_permutation_reverses_bits() {
+        // |0001⟩ (qubit 0 set) under bit reversal becomes |1000⟩
+        let mut amps = vec![Complex::ZERO; 16];
+        amps[0b0001] = Complex::ONE;
+        let mut state = StateVector::from_amplitudes(amps).unwrap();
+        state.apply_qubit_permutation(&[3, 2, 1, 0]);
+        assert!((state.probability(0b1000) - 1.0).abs() < 1e-12);
+
+        // Three-cycle: qubit 0 → 1 → 2 → 0
+        let mut amps = vec![Complex::ZERO; 8];
+        amps[0b011] = Complex::ONE;
+        let mut state = StateVector::from_amplitudes(amps).unwrap();
+        state.apply_qubit_permutation(&[1, 2, 0]);
+        assert!((state.probability(0b110) - 1.0).abs() < 1e-12);
+    }
 }
@@ -16,6 +16,7 @@
 //! - [`ClassicalBit`] - Index of a classical bit
 //! - [`Amplitude`] - Complex amplitude of a quantum state
 //! - [`Probability`] - Real probability value [0, 1]
+//! - [`ValidProbability`] - Probability checked to lie in [0, 1]
 //!
 //! ## Core Traits
 //!
@@ -50,6 +51,105 @@ pub type Probability = f64;
 /// Angle in radians for rotation gates.
 pub type Angle = f64;
 
+// ============================================================================
+// VALIDATED PROBABILITY
+// ============================================================================
+
+/// A probability guaranteed to lie in [0, 1].
+///
+/// Unlike the bare [`Probability`] alias, this can only be built from a
+/// valid value. Arithmetic saturates at the ends of the range.
+///
+/// # Example
+///
+/// ```rust
+/// use homaya_types::ValidProbability;
+///
+/// let p = ValidProbability::new(0.3).unwrap();
+/// assert_eq!(*p, 0.3);
+/// assert!(ValidProbability::new(1.5).is_err());
+/// assert_eq!(*(p + p + p + p), 1.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidProbability(f64);
+
+impl ValidProbability {
+    /// Probability zero.
+    pub const ZERO: Self = Self(0.0);
+
+    /// Probability one.
+    pub const ONE: Self = Self(1.0);
+
+    /// Create a probability, rejecting values outside [0, 1] (and NaN).
+    pub fn new(p: f64) -> Result<Self> {
+        if (0.0..=1.0).contains(&p) {
+            Ok(Self(p))
+        } else {
+            Err(HomayaError::InvalidProbability { value: p })
+        }
+    }
+
+    /// Create a probability, clamping into [0, 1]. NaN becomes zero.
+    pub fn saturating(p: f64) -> Self {
+        if p.is_nan() {
+            Self::ZERO
+        } else {
+            Self(p.clamp(0.0, 1.0))
+        }
+    }
+
+    /// The probability of the complementary event, 1 - p.
+    pub fn complement(self) -> Self {
+        Self(1.0 - self.0)
+    }
+}
+
+impl std::ops::Deref for ValidProbability {
+    type Target = f64;
+
+    fn deref(&self) -> &f64 {
+        &self.0
+    }
+}
+
+impl From<ValidProbability> for f64 {
+    fn from(p: ValidProbability) -> f64 {
+        p.0
+    }
+}
+
+impl std::ops::Add for ValidProbability {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::saturating(self.0 + rhs.0)
+    }
+}
+
+impl std::ops::Sub for ValidProbability {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::saturating(self.0 - rhs.0)
+    }
+}
+
+impl std::ops::Mul for ValidProbability {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        // Already in [0, 1]; no clamping needed
+        Self(self.0 * rhs.0)
+    }
+}
+
+impl fmt::Display for ValidProbability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 // ============================================================================
 // COMPLEX AMPLITUDE
 // ============================================================================
@@ -186,6 +286,78 @@ impl StandardGate {
     pub const fn is_controlled(&self) -> bool {
         matches!(self, Self::CX | Self::CY | Self::CZ | Self::CH | Self::CCX | Self::CSwap)
     }
+
+    /// Returns the gate's unitary matrix (2×2, 4×4 or 8×8).
+    ///
+    /// Rows and columns are indexed little-endian by the operands in order:
+    /// `b0 + 2·b1 + 4·b2`, where `b0` is the state of the first operand
+    /// (the control, for controlled gates). This matches
+    /// `homaya_core::Gate::matrix_4x4`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use homaya_types::{Amplitude, StandardGate};
+    ///
+    /// let x = StandardGate::X.matrix();
+    /// assert_eq!(x[0][1], Amplitude::ONE);
+    /// assert_eq!(StandardGate::CCX.matrix().len(), 8);
+    /// ```
+    pub fn matrix(&self) -> Vec<Vec<Amplitude>> {
+        let zero = Amplitude::ZERO;
+        let one = Amplitude::ONE;
+        let h = Amplitude::real(constants::INV_SQRT_2);
+        let t = Amplitude::new(constants::INV_SQRT_2, constants::INV_SQRT_2);
+
+        let single = |m: [[Amplitude; 2]; 2]| m.iter().map(|row| row.to_vec()).collect();
+        // Apply `u` to the second operand when the first is |1⟩
+        let controlled = |u: Self| {
+            let u = u.matrix();
+            let mut m = permutation(4, |i| i);
+            for (r, row) in u.iter().enumerate() {
+                for (c, &value) in row.iter().enumerate() {
+                    m[1 + 2 * r][1 + 2 * c] = value;
+                }
+            }
+            m
+        };
+
+        match self {
+            Self::I => single([[one, zero], [zero, one]]),
+            Self::X => single([[zero, one], [one, zero]]),
+            Self::Y => single([[zero, Amplitude::imag(-1.0)], [Amplitude::I, zero]]),
+            Self::Z => single([[one, zero], [zero, Amplitude::real(-1.0)]]),
+            Self::H => single([[h, h], [h, Amplitude::real(-constants::INV_SQRT_2)]]),
+            Self::S => single([[one, zero], [zero, Amplitude::I]]),
+            Self::Sdg => single([[one, zero], [zero, Amplitude::imag(-1.0)]]),
+            Self::T => single([[one, zero], [zero, t]]),
+            Self::Tdg => single([[one, zero], [zero, t.conj()]]),
+            Self::CX => controlled(Self::X),
+            Self::CY => controlled(Self::Y),
+            Self::CZ => controlled(Self::Z),
+            Self::CH => controlled(Self::H),
+            Self::Swap => permutation(4, |i| match i {
+                0b01 => 0b10,
+                0b10 => 0b01,
+                _ => i,
+            }),
+            Self::CCX => permutation(8, |i| if i & 0b011 == 0b011 { i ^ 0b100 } else { i }),
+            Self::CSwap => permutation(8, |i| match i {
+                0b011 => 0b101,
+                0b101 => 0b011,
+                _ => i,
+            }),
+        }
+    }
+}
+
+/// Matrix of the permutation sending basis state `i` to `image(i)`.
+fn permutation(dim: usize, image: impl Fn(usize) -> usize) -> Vec<Vec<Amplitude>> {
+    let mut m = vec![vec![Amplitude::ZERO; dim]; dim];
+    for col in 0..dim {
+        m[image(col)][col] = Amplitude::ONE;
+    }
+    m
 }
 
 // ============================================================================
@@ -212,7 +384,21 @@ impl MeasurementResult {
     }
 
     /// Get the probability of a specific outcome.
+    #[cfg(not(feature = "validated-probability"))]
     pub fn probability(&self, outcome: &str) -> Probability {
+        self.frequency(outcome)
+    }
+
+    /// Get the probability of a specific outcome.
+    ///
+    /// Zero shots give probability zero.
+    #[cfg(feature = "validated-probability")]
+    pub fn probability(&self, outcome: &str) -> ValidProbability {
+        ValidProbability::saturating(self.frequency(outcome))
+    }
+
+    /// Fraction of shots that gave `outcome`.
+    fn frequency(&self, outcome: &str) -> f64 {
         self.counts.get(outcome).copied().unwrap_or(0) as f64 / self.shots as f64
     }
 
@@ -223,6 +409,57 @@ impl MeasurementResult {
             .max_by_key(|(_, &count)| count)
             .map(|(outcome, &count)| (outcome.as_str(), count as f64 / self.shots as f64))
     }
+
+    /// Estimate the parity expectation ⟨Z⊗…⊗Z⟩ and its standard error.
+    ///
+    /// Each outcome contributes +1 if it has an even number of '1's and -1
+    /// otherwise. The standard error is √((1 - ⟨O⟩²) / shots), which lets
+    /// you judge how many shots are needed for a target precision.
+    pub fn expectation_with_stderr(&self) -> (f64, f64) {
+        if self.shots == 0 {
+            return (0.0, 0.0);
+        }
+
+        let signed: i64 = self
+            .counts
+            .iter()
+            .map(|(outcome, &count)| {
+                let ones = outcome.chars().filter(|&c| c == '1').count();
+                if ones % 2 == 0 { count as i64 } else { -(count as i64) }
+            })
+            .sum();
+
+        let expectation = signed as f64 / self.shots as f64;
+        let variance = (1.0 - expectation * expectation).max(0.0);
+        (expectation, (variance / self.shots as f64).sqrt())
+    }
+
+    /// Add the counts and shots of another run into this one.
+    pub fn merge(&mut self, other: &MeasurementResult) {
+        for (outcome, &count) in &other.counts {
+            *self.counts.entry(outcome.clone()).or_insert(0) += count;
+        }
+        self.shots += other.shots;
+    }
+
+    /// Shannon entropy of the outcome frequencies, in bits.
+    ///
+    /// H = -Σ p log₂ p: 0 when every shot gave the same outcome, and
+    /// log₂ k for k equally frequent outcomes. Zero shots give 0.
+    pub fn shannon_entropy(&self) -> f64 {
+        if self.shots == 0 {
+            return 0.0;
+        }
+
+        self.counts
+            .values()
+            .filter(|&&count| count > 0)
+            .map(|&count| {
+                let p = count as f64 / self.shots as f64;
+                -p * p.log2()
+            })
+            .sum()
+    }
 }
 
 // ============================================================================
@@ -246,6 +483,11 @@ pub enum HomayaError {
         /// Error message
         message: String,
     },
+    /// Probability outside [0, 1]
+    InvalidProbability {
+        /// The invalid value
+        value: f64,
+    },
     /// Operation not supported
     NotSupported {
         /// Description of unsupported operation
@@ -267,6 +509,9 @@ impl fmt::Display for HomayaError {
             Self::InvalidParameter { name, message } => {
                 write!(f, "Invalid parameter '{}': {}", name, message)
             }
+            Self::InvalidProbability { value } => {
+                write!(f, "Invalid probability: {} not in [0, 1]", value)
+            }
             Self::NotSupported { operation } => {
                 write!(f, "Operation not supported: {}", operation)
             }
@@ -314,7 +559,7 @@ pub mod constants {
     pub const SQRT_2: f64 = std::f64::consts::SQRT_2;
 
     /// 1/√2 (used frequently in quantum gates)
-    pub const INV_SQRT_2: f64 = 0.7071067811865476;
+    pub const INV_SQRT_2: f64 = std::f64::consts::FRAC_1_SQRT_2;
 
     /// Default tolerance for floating point comparisons
     pub const EPSILON: f64 = 1e-10;
@@ -350,7 +595,61 @@ mod tests {
         assert_eq!(StandardGate::CCX.num_qubits(), 3);
     }
 
+    /// Real 0/1 matrix from rows of integers.
+    fn real_matrix(rows: &[&[u8]]) -> Vec<Vec<Amplitude>> {
+        rows.iter()
+            .map(|row| row.iter().map(|&x| Amplitude::real(x as f64)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_gate_matrices() {
+        assert_eq!(StandardGate::X.matrix(), real_matrix(&[&[0, 1], &[1, 0]]));
+
+        // Control is operand 0, the low bit: |c=1,t=0⟩ = 1 ↔ |c=1,t=1⟩ = 3
+        assert_eq!(
+            StandardGate::CX.matrix(),
+            real_matrix(&[&[1, 0, 0, 0], &[0, 0, 0, 1], &[0, 0, 1, 0], &[0, 1, 0, 0]])
+        );
+
+        // Toffoli flips bit 2 when bits 0 and 1 are set: 3 ↔ 7
+        let expected: Vec<Vec<Amplitude>> = (0..8)
+            .map(|row| {
+                let col = match row {
+                    3 => 7,
+                    7 => 3,
+                    _ => row,
+                };
+                (0..8).map(|c| if c == col { Amplitude::ONE } else { Amplitude::ZERO }).collect()
+            })
+            .collect();
+        assert_eq!(StandardGate::CCX.matrix(), expected);
+    }
+
+    #[test]
+    fn test_gate_matrices_are_unitary() {
+        use StandardGate::*;
+
+        for gate in [I, X, Y, Z, H, S, Sdg, T, Tdg, CX, CY, CZ, CH, Swap, CCX, CSwap] {
+            let m = gate.matrix();
+            let dim = 1 << gate.num_qubits();
+            assert_eq!(m.len(), dim);
+            for a in 0..dim {
+                for b in 0..dim {
+                    // (M†M)[a][b] = Σ conj(M[k][a]) M[k][b]
+                    let (re, im) = (0..dim).fold((0.0, 0.0), |(re, im), k| {
+                        let (x, y) = (m[k][a].conj(), m[k][b]);
+                        (re + x.re * y.re - x.im * y.im, im + x.re * y.im + x.im * y.re)
+                    });
+                    let expected = if a == b { 1.0 } else { 0.0 };
+                    assert!((re - expected).abs() < 1e-12 && im.abs() < 1e-12, "{:?} at ({}, {})", gate, a, b);
+                }
+            }
+        }
+    }
+
     #[test]
+    #[cfg(not(feature = "validated-probability"))]
     fn test_measurement_probability() {
         let mut result = MeasurementResult::new(100);
         result.counts.insert("00".to_string(), 60);
@@ -360,4 +659,90 @@ mod tests {
         assert!((result.probability("11") - 0.4).abs() < 1e-10);
         assert!((result.probability("01") - 0.0).abs() < 1e-10);
     }
+
+    #[test]
+    #[cfg(feature = "validated-probability")]
+    fn test_validated_measurement_probability() {
+        let mut result = MeasurementResult::new(100);
+        result.counts.insert("00".to_string(), 60);
+        assert!((*result.probability("00") - 0.6).abs() < 1e-10);
+        assert_eq!(MeasurementResult::new(0).probability("00"), ValidProbability::ZERO);
+    }
+
+    #[test]
+    fn test_valid_probability() {
+        assert_eq!(
+            ValidProbability::new(1.5),
+            Err(HomayaError::InvalidProbability { value: 1.5 })
+        );
+        assert!(ValidProbability::new(-0.1).is_err());
+        assert!(ValidProbability::new(f64::NAN).is_err());
+
+        let p = ValidProbability::new(0.3).unwrap();
+        assert_eq!(*p, 0.3);
+        assert!((*p.complement() - 0.7).abs() < 1e-12);
+        assert_eq!(p - ValidProbability::ONE, ValidProbability::ZERO);
+        assert_eq!(ValidProbability::ONE + p, ValidProbability::ONE);
+        assert!((*(p * p) - 0.09).abs() < 1e-12);
+        assert_eq!(ValidProbability::saturating(f64::NAN), ValidProbability::ZERO);
+    }
+
+    #[test]
+    fn test_expectation_with_stderr() {
+        let mut deterministic = MeasurementResult::new(100);
+        deterministic.counts.insert("00".to_string(), 100);
+        let (exp, err) = deterministic.expectation_with_stderr();
+        assert!((exp - 1.0).abs() < 1e-10);
+        assert!(err.abs() < 1e-10);
+
+        let mut even_split = MeasurementResult::new(100);
+        even_split.counts.insert("00".to_string(), 50);
+        even_split.counts.insert("01".to_string(), 50);
+        let (exp, max_err) = even_split.expectation_with_stderr();
+        assert!(exp.abs() < 1e-10);
+        assert!((max_err - 0.1).abs() < 1e-10);
+
+        let mut skewed = MeasurementResult::new(100);
+        skewed.counts.insert("11".to_string(), 80);
+        skewed.counts.insert("10".to_string(), 20);
+        let (exp, err) = skewed.expectation_with_stderr();
+        assert!((exp - 0.6).abs() < 1e-10);
+        assert!(err < max_err);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut first = MeasurementResult::new(100);
+        first.counts.insert("00".to_string(), 70);
+        first.counts.insert("11".to_string(), 30);
+        let mut second = MeasurementResult::new(50);
+        second.counts.insert("11".to_string(), 20);
+        second.counts.insert("01".to_string(), 30);
+
+        first.merge(&second);
+        assert_eq!(first.shots, 150);
+        assert_eq!(first.counts.len(), 3);
+        assert_eq!(first.counts["00"], 70);
+        assert_eq!(first.counts["11"], 50);
+        assert_eq!(first.counts["01"], 30);
+    }
+
+    #[test]
+    fn test_shannon_entropy() {
+        let mut uniform = MeasurementResult::new(200);
+        uniform.counts.insert("0".to_string(), 100);
+        uniform.counts.insert("1".to_string(), 100);
+        assert!((uniform.shannon_entropy() - 1.0).abs() < 1e-12);
+
+        let mut deterministic = MeasurementResult::new(10);
+        deterministic.counts.insert("101".to_string(), 10);
+        assert_eq!(deterministic.shannon_entropy(), 0.0);
+
+        let mut four = MeasurementResult::new(400);
+        for outcome in ["00", "01", "10", "11"] {
+            four.counts.insert(outcome.to_string(), 100);
+        }
+        assert!((four.shannon_entropy() - 2.0).abs() < 1e-12);
+        assert_eq!(MeasurementResult::new(0).shannon_entropy(), 0.0);
+    }
 }
@@ -0,0 +1,69 @@
+//! HOMAYA Simulator Benchmark - Hadamard layer on 22 qubits
+//!
+//! Times the simulator's vectorized kernel against a plain scalar loop
+//! doing the same amplitude updates.
+//!
+//! Run with: cargo run --release --example bench_h_layer
+
+use std::time::Instant;
+
+use homaya_core::{Circuit, Complex};
+use homaya_sim::Simulator;
+
+const NUM_QUBITS: usize = 22;
+const REPEATS: usize = 5;
+
+/// Best wall time of `REPEATS` runs of `f`, in seconds.
+fn best_of(mut f: impl FnMut()) -> f64 {
+    let mut best = f64::INFINITY;
+    for _ in 0..REPEATS {
+        let start = Instant::now();
+        f();
+        best = best.min(start.elapsed().as_secs_f64());
+    }
+    best
+}
+
+/// H on every qubit, one amplitude pair at a time.
+fn scalar_h_layer(amplitudes: &mut [Complex]) {
+    let h = Complex::new(std::f64::consts::FRAC_1_SQRT_2, 0.0);
+    for q in 0..NUM_QUBITS {
+        let mask = 1 << q;
+        for block in amplitudes.chunks_exact_mut(mask << 1) {
+            let (lo, hi) = block.split_at_mut(mask);
+            for (a0, a1) in lo.iter_mut().zip(hi.iter_mut()) {
+                let (x0, x1) = (*a0, *a1);
+                *a0 = h * x0 + h * x1;
+                *a1 = h * x0 - h * x1;
+            }
+        }
+    }
+}
+
+fn main() {
+    let mut circuit = Circuit::new(NUM_QUBITS);
+    for q in 0..NUM_QUBITS {
+        circuit = circuit.h(q);
+    }
+    let uniform = 1.0 / (1u64 << NUM_QUBITS) as f64;
+
+    let mut sim = Simulator::new();
+    let simd = best_of(|| {
+        let state = sim.run(&circuit).unwrap();
+        assert!((state.probability(0) - uniform).abs() < 1e-12);
+    });
+
+    let scalar = best_of(|| {
+        let mut amplitudes = vec![Complex::ZERO; 1 << NUM_QUBITS];
+        amplitudes[0] = Complex::ONE;
+        scalar_h_layer(&mut amplitudes);
+        assert!((amplitudes[0].norm_sqr() - uniform).abs() < 1e-12);
+    });
+
+    println!("{}-qubit H layer, best of {} runs:", NUM_QUBITS, REPEATS);
+    for (name, best) in [("simd", simd), ("scalar", scalar)] {
+        let gates_per_sec = NUM_QUBITS as f64 / best;
+        println!("  {:<6} {:>8.2} ms  {:>6.0} single-qubit gates/s", name, best * 1e3, gates_per_sec);
+    }
+    println!("  speedup {:.2}x", scalar / simd);
+}
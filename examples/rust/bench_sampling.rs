@@ -0,0 +1,47 @@
+//! HOMAYA Simulator Benchmark - sampling a terminally measured circuit
+//!
+//! Run with: cargo run --release --example bench_sampling
+
+use std::time::Instant;
+
+use homaya_core::Circuit;
+use homaya_sim::Simulator;
+
+const NUM_QUBITS: usize = 20;
+const LAYERS: usize = 5;
+const SHOTS: usize = 10_000;
+/// Shots timed on the per-shot path; the rest is extrapolated
+const SLOW_SHOTS: usize = 20;
+
+fn main() {
+    let mut circuit = Circuit::new(NUM_QUBITS);
+    for layer in 0..LAYERS {
+        for q in 0..NUM_QUBITS {
+            circuit = circuit.h(q).rz(0.1 * (layer + q) as f64, q);
+        }
+        for q in 0..NUM_QUBITS - 1 {
+            circuit = circuit.cx(q, q + 1);
+        }
+    }
+    let gates = circuit.len();
+    let circuit = circuit.measure_all();
+
+    // All measurements are terminal: one simulation, then SHOTS draws
+    let mut sim = Simulator::with_seed(1);
+    let start = Instant::now();
+    let counts = sim.sample(&circuit, SHOTS).unwrap();
+    let fast = start.elapsed().as_secs_f64();
+    assert_eq!(counts.values().sum::<usize>(), SHOTS);
+
+    // What sampling cost before: one full simulation per shot
+    let start = Instant::now();
+    for _ in 0..SLOW_SHOTS {
+        sim.run_with_measurements(&circuit).unwrap();
+    }
+    let slow = start.elapsed().as_secs_f64() * (SHOTS / SLOW_SHOTS) as f64;
+
+    println!("{} qubits, {} gates, {} shots", NUM_QUBITS, gates, SHOTS);
+    println!("  sample (single run):   {:.3} s", fast);
+    println!("  per-shot simulation:   {:.1} s (extrapolated from {} shots)", slow, SLOW_SHOTS);
+    println!("  speedup:               {:.0}x", slow / fast);
+}
@@ -0,0 +1,60 @@
+//! HOMAYA Simulator Benchmark - T-heavy circuit on 20 qubits
+//!
+//! Run with: cargo run --release --example bench_t_layers
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use homaya_core::{Circuit, Complex, Gate, PI};
+use homaya_sim::Simulator;
+
+const NUM_QUBITS: usize = 20;
+const LAYERS: usize = 20;
+const REPEATS: usize = 5;
+/// T matrices built when timing matrix construction alone
+const MATRICES: usize = 10_000_000;
+
+fn main() {
+    let mut circuit = Circuit::new(NUM_QUBITS);
+    for q in 0..NUM_QUBITS {
+        circuit = circuit.h(q);
+    }
+    for layer in 0..LAYERS {
+        for q in 0..NUM_QUBITS {
+            circuit = if (layer + q) % 2 == 0 { circuit.t(q) } else { circuit.tdg(q) };
+        }
+    }
+    let gates = circuit.len();
+
+    let mut sim = Simulator::new();
+    let mut best = f64::INFINITY;
+    for _ in 0..REPEATS {
+        let start = Instant::now();
+        let state = sim.run(&circuit).unwrap();
+        best = best.min(start.elapsed().as_secs_f64());
+        // T and Tdg only change phases: the H layer's distribution survives
+        assert!((state.probability(0) - 1.0 / (1u64 << NUM_QUBITS) as f64).abs() < 1e-12);
+    }
+
+    println!("{}-qubit circuit, {} T/Tdg layers: best of {} runs = {:.2} ms", NUM_QUBITS, LAYERS, REPEATS, best * 1e3);
+    println!("  {:.0} gates/s over 2^{} amplitudes", gates as f64 / best, NUM_QUBITS);
+
+    // Matrix construction alone: precomputed phase vs. recomputing it
+    let start = Instant::now();
+    for _ in 0..MATRICES {
+        black_box(black_box(Gate::t()).matrix_2x2());
+    }
+    let constant = start.elapsed().as_secs_f64();
+
+    let start = Instant::now();
+    for _ in 0..MATRICES {
+        let phase = Complex::from_polar(1.0, black_box(PI) / 4.0);
+        black_box([[Complex::ONE, Complex::ZERO], [Complex::ZERO, phase]]);
+    }
+    let trig = start.elapsed().as_secs_f64();
+
+    println!();
+    println!("{} T matrices:", MATRICES);
+    println!("  constant phase: {:.2} ns each", constant / MATRICES as f64 * 1e9);
+    println!("  from_polar:     {:.2} ns each", trig / MATRICES as f64 * 1e9);
+}
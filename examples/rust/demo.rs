@@ -2,7 +2,7 @@
 //!
 //! Run with: cargo run --example demo
 
-use homaya_core::{Circuit, Gate, GateType};
+use homaya_core::{Circuit, Gate};
 
 fn main() {
     println!("╔═══════════════════════════════════════════════════════════════╗");
@@ -24,12 +24,7 @@ fn main() {
         .cx(0, 1)    // CNOT: control=0, target=1
         .measure_all();
 
-    println!("  ┌───┐     ");
-    println!("q0┤ H ├──●──M");
-    println!("  └───┘  │   ");
-    println!("       ┌─┴─┐ ");
-    println!("q1─────┤ X ├─M");
-    println!("       └───┘ ");
+    print!("{}", bell.draw());
     println!();
     println!("Circuit stats:");
     println!("  • Qubits: {}", bell.num_qubits());
@@ -51,15 +46,7 @@ fn main() {
         .cx(1, 2)
         .measure_all();
 
-    println!("  ┌───┐          ");
-    println!("q0┤ H ├──●───────M");
-    println!("  └───┘  │        ");
-    println!("       ┌─┴─┐      ");
-    println!("q1─────┤ X ├──●──M");
-    println!("       └───┘  │   ");
-    println!("            ┌─┴─┐ ");
-    println!("q2──────────┤ X ├M");
-    println!("            └───┘ ");
+    print!("{}", ghz.draw());
     println!();
     println!("Circuit stats:");
     println!("  • Qubits: {}", ghz.num_qubits());
@@ -79,9 +66,7 @@ fn main() {
         .ry(pi / 2.0, 0)
         .rz(pi, 0);
 
-    println!("  ┌─────────┐┌─────────┐┌─────────┐");
-    println!("q0┤ Rx(π/4) ├┤ Ry(π/2) ├┤  Rz(π)  ├");
-    println!("  └─────────┘└─────────┘└─────────┘");
+    print!("{}", rotations.draw());
     println!();
     println!("Circuit stats:");
     println!("  • Qubits: {}", rotations.num_qubits());
@@ -107,9 +107,9 @@ fn main() {
     println!();
     println!("State amplitudes:");
     let amps = state.amplitudes();
-    for i in 0..4 {
-        let re = amps[i].re;
-        let im = amps[i].im;
+    for (i, amp) in amps.iter().enumerate() {
+        let re = amp.re;
+        let im = amp.im;
         let basis = match i {
             0 => "00",
             1 => "01",
@@ -156,7 +156,7 @@ fn main() {
                     .unwrap()
                     .0;
 
-                let out_c1 = (output_idx >> 0) & 1;
+                let out_c1 = output_idx & 1;
                 let out_c2 = (output_idx >> 1) & 1;
                 let out_t = (output_idx >> 2) & 1;
 
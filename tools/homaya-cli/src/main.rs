@@ -3,7 +3,13 @@
 //! Learn more: https://bskiller.com
 //! Enterprise: https://dataxlr8.ai
 
+use std::path::Path;
+
+use std::collections::{BTreeMap, HashMap};
+
 use clap::Parser;
+use homaya_core::{Circuit, GateType};
+use homaya_sim::Simulator;
 
 const BANNER: &str = r#"
 ╦ ╦╔═╗╔╦╗╔═╗╦ ╦╔═╗
@@ -26,11 +32,30 @@ struct Cli {
 enum Commands {
     /// Run a quantum circuit
     Run {
-        /// Path to circuit file
+        /// Path to circuit file (.qasm for OpenQASM 2.0, .json from Circuit::to_json)
         file: String,
         /// Number of shots
         #[arg(short, long, default_value = "1000")]
         shots: u32,
+        /// How to print the results
+        #[arg(short, long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// Print a circuit diagram
+    Draw {
+        /// Path to circuit file (.qasm for OpenQASM 2.0, .json from Circuit::to_json)
+        file: String,
+    },
+    /// Print the final state vector
+    Statevector {
+        /// Path to circuit file (.qasm for OpenQASM 2.0, .json from Circuit::to_json)
+        file: String,
+        /// Hide basis states whose amplitude magnitude is below this
+        #[arg(short, long, default_value = "1e-6")]
+        threshold: f64,
+        /// Print probabilities instead of amplitudes
+        #[arg(short, long)]
+        probabilities: bool,
     },
     /// Show version and system info
     Version,
@@ -40,15 +65,38 @@ enum Commands {
     Gates,
 }
 
+/// Output format for `homaya run`.
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum OutputFormat {
+    /// Counts and probabilities, sorted by bitstring
+    Table,
+    /// `{"counts": {...}, "shots": N}` on stdout, for other tools
+    Json,
+    /// Bar chart, most frequent outcome first
+    Histogram,
+}
+
 fn main() {
     let cli = Cli::parse();
 
     match cli.command {
-        Some(Commands::Run { file, shots }) => {
-            println!("Running circuit from: {} ({} shots)", file, shots);
-            println!("\nNote: Circuit file format coming soon.");
-            println!("For now, use the Rust API directly.");
-            println!("\nLearn how: https://bskiller.com");
+        Some(Commands::Run { file, shots, format }) => {
+            if let Err(message) = run_file(&file, shots as usize, format) {
+                eprintln!("error: {}", message);
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Draw { file }) => {
+            if let Err(message) = draw_file(&file) {
+                eprintln!("error: {}", message);
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Statevector { file, threshold, probabilities }) => {
+            if let Err(message) = statevector_file(&file, threshold, probabilities) {
+                eprintln!("error: {}", message);
+                std::process::exit(1);
+            }
         }
         Some(Commands::Version) => {
             print_version();
@@ -68,12 +116,139 @@ fn main() {
     }
 }
 
+/// Load a circuit file, sample it and print the results.
+fn run_file(file: &str, shots: usize, format: OutputFormat) -> Result<(), String> {
+    let circuit = load_circuit(Path::new(file))?;
+
+    let counts = Simulator::new()
+        .sample(&circuit, shots)
+        .map_err(|e| format!("simulation failed: {}", e))?;
+
+    if let OutputFormat::Json = format {
+        // Sorted keys keep the output stable
+        let counts: BTreeMap<&String, &usize> = counts.iter().collect();
+        let json = serde_json::json!({ "counts": counts, "shots": shots });
+        println!("{}", serde_json::to_string_pretty(&json).map_err(|e| e.to_string())?);
+        return Ok(());
+    }
+
+    println!("Running circuit from: {} ({} shots)", file, shots);
+    println!(
+        "  {} qubits, {} classical bits, {} instructions",
+        circuit.num_qubits(),
+        circuit.num_clbits(),
+        circuit.len()
+    );
+    println!();
+
+    match format {
+        OutputFormat::Histogram => print_histogram(&counts, shots),
+        _ => print_table(&counts, shots),
+    }
+    Ok(())
+}
+
+/// Load a circuit file and print its diagram.
+fn draw_file(file: &str) -> Result<(), String> {
+    let circuit = load_circuit(Path::new(file))?;
+
+    // Measurements, resets, barriers and snapshots are not gates
+    let gates = circuit.instructions().iter().filter(|inst| inst.gate.is_unitary()).count();
+    println!("{}", circuit.draw());
+    println!(
+        "{} qubits, {} classical bits, {} gates, depth {}",
+        circuit.num_qubits(),
+        circuit.num_clbits(),
+        gates,
+        circuit.depth()
+    );
+    Ok(())
+}
+
+/// Load a circuit file, run it without its final measurements and print
+/// the final amplitudes.
+///
+/// Basis states are labelled with the highest qubit first, as kets are
+/// usually written.
+fn statevector_file(file: &str, threshold: f64, probabilities: bool) -> Result<(), String> {
+    let circuit = load_circuit(Path::new(file))?.remove_final_measurements();
+    if circuit
+        .instructions()
+        .iter()
+        .any(|inst| matches!(inst.gate.gate_type, GateType::Measure | GateType::Reset))
+    {
+        eprintln!("warning: the circuit measures or resets qubits mid-circuit; showing one sampled outcome");
+    }
+
+    let state = Simulator::new()
+        .run(&circuit)
+        .map_err(|e| format!("simulation failed: {}", e))?;
+
+    let n = state.num_qubits();
+    for (index, amp) in state.amplitudes().iter().enumerate() {
+        if amp.abs() < threshold {
+            continue;
+        }
+        let label = format!("{:0width$b}", index, width = n);
+        let probability = amp.norm_sqr() * 100.0;
+        if probabilities {
+            println!("|{}⟩: {:.2}%", label, probability);
+        } else {
+            println!("|{}⟩: {:+.4}{:+.4}i ({:.2}%)", label, amp.re, amp.im, probability);
+        }
+    }
+    Ok(())
+}
+
+/// Parse a circuit, choosing the format by file extension.
+fn load_circuit(path: &Path) -> Result<Circuit, String> {
+    let source = std::fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("qasm") => Circuit::from_qasm(&source).map_err(|e| format!("{}: {}", path.display(), e)),
+        Some("json") => Circuit::from_json(&source).map_err(|e| format!("{}: {}", path.display(), e)),
+        _ => Err(format!(
+            "{}: unknown circuit format (expected a .qasm or .json file)",
+            path.display()
+        )),
+    }
+}
+
+/// Print counts sorted by bitstring, with bit 0 first in each bitstring.
+fn print_table(counts: &HashMap<String, usize>, shots: usize) {
+    let mut outcomes: Vec<(&String, &usize)> = counts.iter().collect();
+    outcomes.sort();
+    let width = outcomes.iter().map(|(bits, _)| bits.len()).max().unwrap_or(0).max("bits".len());
+
+    println!("  {:<width$}  {:>8}  {:>7}", "bits", "count", "prob", width = width);
+    for (bits, &count) in outcomes {
+        let fraction = count as f64 / shots.max(1) as f64;
+        println!("  {:<width$}  {:>8}  {:>6.2}%", bits, count, fraction * 100.0, width = width);
+    }
+}
+
+/// Print a bar chart of the counts, most frequent first.
+fn print_histogram(counts: &HashMap<String, usize>, shots: usize) {
+    const BAR_WIDTH: usize = 50;
+
+    let mut outcomes: Vec<(&String, &usize)> = counts.iter().collect();
+    outcomes.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    let count_width = shots.to_string().len();
+
+    for (bits, &count) in outcomes {
+        let bar_len = (count as f64 / shots.max(1) as f64 * BAR_WIDTH as f64).round() as usize;
+        println!("  |{}⟩: {:>width$} {}", bits, count, "█".repeat(bar_len), width = count_width);
+    }
+}
+
 fn print_banner() {
     println!("{}", BANNER);
     println!("Quantum Computing Framework");
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     println!();
     println!("  homaya run <file>     Run a quantum circuit");
+    println!("  homaya draw <file>    Draw a circuit diagram");
+    println!("  homaya statevector <file>  Print the final amplitudes");
     println!("  homaya gates          List available gates");
     println!("  homaya verify         Verify simulator correctness");
     println!("  homaya version        Show version info");
@@ -112,21 +287,42 @@ fn print_gates() {
     println!("  Z   - Pauli-Z (phase flip)");
     println!("  H   - Hadamard (superposition)");
     println!("  S   - S gate (√Z)");
+    println!("  Sdg - S-dagger (inverse of S)");
     println!("  T   - T gate (π/8)");
+    println!("  Tdg - T-dagger (inverse of T)");
     println!("  Rx  - X-rotation by angle");
     println!("  Ry  - Y-rotation by angle");
     println!("  Rz  - Z-rotation by angle");
+    println!("  P   - Phase by angle");
+    println!("  U   - General single-qubit gate U(θ, φ, λ)");
     println!();
     println!("Two-Qubit Gates:");
-    println!("  CX   - Controlled-X (CNOT)");
-    println!("  CY   - Controlled-Y");
-    println!("  CZ   - Controlled-Z");
-    println!("  SWAP - Swap two qubits");
+    println!("  CX       - Controlled-X (CNOT)");
+    println!("  CY       - Controlled-Y");
+    println!("  CZ       - Controlled-Z");
+    println!("  CH       - Controlled-Hadamard");
+    println!("  CP       - Controlled-phase");
+    println!("  CU       - Controlled-U");
+    println!("  SWAP     - Swap two qubits");
+    println!("  iSWAP    - Swap with an i phase on |01⟩ and |10⟩");
+    println!("  √SWAP    - Square root of SWAP");
+    println!("  Rxx      - XX Ising coupling");
+    println!("  Ryy      - YY Ising coupling");
+    println!("  Rzz      - ZZ Ising coupling");
     println!();
     println!("Three-Qubit Gates:");
     println!("  CCX   - Toffoli (AND gate)");
     println!("  CSWAP - Fredkin (controlled swap)");
     println!();
+    println!("Multi-Qubit Gates:");
+    println!("  MCX   - Multi-controlled X; the last qubit is the target");
+    println!();
+    println!("Non-Unitary Instructions:");
+    println!("  Measure  - Measure a qubit into a classical bit");
+    println!("  Reset    - Reset a qubit to |0⟩");
+    println!("  Barrier  - Keep gates from moving across it");
+    println!("  Snapshot - Capture the simulator state under a label");
+    println!();
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     println!("Learn how to use these → https://bskiller.com");
     println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
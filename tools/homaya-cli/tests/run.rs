@@ -0,0 +1,187 @@
+//! Integration tests for the `homaya` subcommands that load circuit files.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn data(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data").join(name)
+}
+
+fn homaya(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_homaya"))
+        .args(args)
+        .output()
+        .expect("failed to launch homaya")
+}
+
+#[test]
+fn test_run_qasm_prints_histogram() {
+    let file = data("bell.qasm");
+    let output = homaya(&["run", file.to_str().unwrap(), "--shots", "500"]);
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let counts = histogram(&stdout);
+    assert_eq!(counts.len(), 2, "stdout: {}", stdout);
+    assert!(counts.iter().all(|(bits, _)| bits == "00" || bits == "11"));
+    assert_eq!(counts.iter().map(|(_, c)| c).sum::<usize>(), 500);
+}
+
+/// Outcome lines of the histogram as (bits, count).
+fn histogram(stdout: &str) -> Vec<(String, usize)> {
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let bits = fields.next()?;
+            let count = fields.next()?.parse().ok()?;
+            bits.chars().all(|c| c == '0' || c == '1').then(|| (bits.to_string(), count))
+        })
+        .collect()
+}
+
+#[test]
+fn test_run_deterministic_circuit() {
+    let file = data("flip.qasm");
+    let output = homaya(&["run", file.to_str().unwrap(), "-s", "10"]);
+    assert!(output.status.success());
+
+    // Bit 0 is printed first
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(histogram(&stdout), [("101".to_string(), 10)]);
+    assert!(stdout.contains("100.00%"));
+}
+
+#[test]
+fn test_json_output_parses_back() {
+    let file = data("bell.qasm");
+    let output = homaya(&["run", file.to_str().unwrap(), "--shots", "300", "--format", "json"]);
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).expect("stdout is JSON");
+    assert_eq!(json["shots"], 300);
+    let counts: HashMap<String, usize> = serde_json::from_value(json["counts"].clone()).unwrap();
+    assert!(counts.keys().all(|bits| bits == "00" || bits == "11"), "{:?}", counts);
+    assert_eq!(counts.values().sum::<usize>(), 300);
+
+    let file = data("flip.qasm");
+    let output = homaya(&["run", file.to_str().unwrap(), "-s", "7", "-f", "json"]);
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let counts: HashMap<String, usize> = serde_json::from_value(json["counts"].clone()).unwrap();
+    assert_eq!(counts, HashMap::from([("101".to_string(), 7)]));
+}
+
+#[test]
+fn test_histogram_output() {
+    let file = data("flip.qasm");
+    let output = homaya(&["run", file.to_str().unwrap(), "-s", "10", "--format", "histogram"]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let line = stdout.lines().find(|line| line.contains("|101⟩")).expect("outcome line");
+    assert_eq!(line.matches('█').count(), 50);
+}
+
+#[test]
+fn test_run_json_circuit() {
+    let circuit = homaya_core::Circuit::new(2).x(1).measure_all();
+    let path = std::env::temp_dir().join(format!("homaya-cli-{}.json", std::process::id()));
+    std::fs::write(&path, circuit.to_json()).unwrap();
+
+    let output = homaya(&["run", path.to_str().unwrap(), "-s", "20"]);
+    std::fs::remove_file(&path).unwrap();
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(histogram(&String::from_utf8(output.stdout).unwrap()), [("01".to_string(), 20)]);
+}
+
+#[test]
+fn test_run_reports_errors() {
+    let output = homaya(&["run", "does-not-exist.qasm"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("cannot read"));
+
+    let file = data("bell.qasm");
+    let renamed = std::env::temp_dir().join(format!("homaya-cli-{}.txt", std::process::id()));
+    std::fs::copy(&file, &renamed).unwrap();
+    let output = homaya(&["run", renamed.to_str().unwrap()]);
+    std::fs::remove_file(&renamed).unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown circuit format"));
+}
+
+#[test]
+fn test_draw_bell_circuit() {
+    let file = data("bell.qasm");
+    let output = homaya(&["draw", file.to_str().unwrap()]);
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("┤ H ├"), "{}", stdout);
+    // CNOT: control dot, connector into the target box
+    assert!(stdout.contains('●') && stdout.contains("┌─┴─┐") && stdout.contains("┤ X ├"), "{}", stdout);
+    // The two measurements are not gates
+    assert!(stdout.contains("2 qubits, 2 classical bits, 2 gates"), "{}", stdout);
+
+    let output = homaya(&["draw", "missing.json"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_gates_lists_newer_gates() {
+    let output = homaya(&["gates"]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    for name in ["Sdg", "U ", "CH", "iSWAP", "Rzz", "MCX", "Reset", "Snapshot"] {
+        assert!(stdout.contains(name), "missing {}: {}", name, stdout);
+    }
+}
+
+#[test]
+fn test_statevector_bell() {
+    let file = data("bell_state.qasm");
+    let output = homaya(&["statevector", file.to_str().unwrap()]);
+    assert!(output.status.success(), "stderr: {}", String::from_utf8_lossy(&output.stderr));
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines, ["|00⟩: +0.7071+0.0000i (50.00%)", "|11⟩: +0.7071+0.0000i (50.00%)"]);
+
+    let output = homaya(&["statevector", file.to_str().unwrap(), "--probabilities"]);
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.lines().collect::<Vec<_>>(), ["|00⟩: 50.00%", "|11⟩: 50.00%"]);
+
+    // Nothing survives a threshold above every amplitude
+    let output = homaya(&["statevector", file.to_str().unwrap(), "-t", "0.8"]);
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_statevector_labels_highest_qubit_first() {
+    let file = data("x0.qasm");
+    let output = homaya(&["statevector", file.to_str().unwrap(), "-p"]);
+    assert!(output.status.success());
+    // X on qubit 0 only; the final measurements are not run
+    assert_eq!(String::from_utf8(output.stdout).unwrap().trim(), "|001⟩: 100.00%");
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn test_statevector_skips_final_measurements() {
+    let file = data("bell.qasm");
+    let output = homaya(&["statevector", file.to_str().unwrap(), "-p"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap().lines().collect::<Vec<_>>(), ["|00⟩: 50.00%", "|11⟩: 50.00%"]);
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn test_statevector_warns_on_mid_circuit_reset() {
+    let file = data("reset.qasm");
+    let output = homaya(&["statevector", file.to_str().unwrap(), "-p"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap().trim(), "|10⟩: 100.00%");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("warning"));
+}